@@ -8,7 +8,7 @@ use common::{
     BenchmarkConfig, CLONE_COUNTS, PerformanceStats, Pipe, benchmark_configurations, test_items,
 };
 use criterion::{BenchmarkId, Criterion, black_box, criterion_group, criterion_main};
-use futures::{StreamExt, future, stream};
+use futures::{Stream, StreamExt, future, stream};
 
 /// Combined benchmark testing clone count x item count combinations
 fn benchmark_item_throughput(c: &mut Criterion) {
@@ -105,9 +105,141 @@ fn benchmark_clone_creation(c: &mut Criterion) {
     group.finish();
 }
 
+/// Polling clones while other tasks keep inspecting the shared fork
+fn benchmark_lock_contention(c: &mut Criterion) {
+    use std::sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    };
+
+    const INSPECTORS: usize = 4;
+
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let mut group = c.benchmark_group("Lock contention");
+    group.sample_size(15);
+    group.measurement_time(Duration::from_secs(3));
+    group.warm_up_time(Duration::from_secs(1));
+
+    CLONE_COUNTS.iter().for_each(|&clone_count| {
+        group.bench_with_input(
+            BenchmarkId::new("clones", clone_count),
+            &clone_count,
+            |bencher, &count| {
+                bencher.iter(|| {
+                    rt.block_on(async move {
+                        let forked = test_items(100)
+                            .pipe(stream::iter)
+                            .pipe(clone_stream::ForkStream::fork);
+                        let done = Arc::new(AtomicBool::new(false));
+                        let inspectors = (0..INSPECTORS)
+                            .map(|_| {
+                                let inspected = forked.clone();
+                                let done = done.clone();
+                                tokio::spawn(async move {
+                                    while !done.load(Ordering::Relaxed) {
+                                        black_box(inspected.n_queued_items());
+                                        black_box(inspected.size_hint());
+                                        tokio::task::yield_now().await;
+                                    }
+                                })
+                            })
+                            .collect::<Vec<_>>();
+                        let consumers = (0..count)
+                            .map(|_| forked.clone())
+                            .map(|clone| {
+                                tokio::spawn(async move { clone.collect::<Vec<_>>().await.len() })
+                            })
+                            .collect::<Vec<_>>();
+                        drop(forked);
+                        let lengths = future::try_join_all(consumers).await.unwrap();
+                        done.store(true, Ordering::Relaxed);
+                        future::try_join_all(inspectors).await.unwrap();
+                        black_box(lengths)
+                    })
+                });
+            },
+        );
+    });
+
+    group.finish();
+}
+
+/// In-order consumption with each queue backend
+fn benchmark_queue_backend(c: &mut Criterion) {
+    use clone_stream::{ForkConfig, ForkStream, QueueBackend};
+
+    let mut group = c.benchmark_group("Queue backend");
+    group.sample_size(15);
+    group.measurement_time(Duration::from_secs(3));
+    group.warm_up_time(Duration::from_secs(1));
+
+    [QueueBackend::Sparse, QueueBackend::Dense]
+        .into_iter()
+        .for_each(|queue_backend| {
+            group.bench_with_input(
+                BenchmarkId::new("backend", format!("{queue_backend:?}")),
+                &queue_backend,
+                |bencher, &queue_backend| {
+                    bencher.iter(|| {
+                        let first =
+                            test_items(1000)
+                                .pipe(stream::iter)
+                                .fork_with_config(ForkConfig {
+                                    queue_backend,
+                                    ..ForkConfig::default()
+                                });
+                        let clones = (0..4).map(|_| first.clone()).collect::<Vec<_>>();
+                        drop(first);
+                        clones
+                            .into_iter()
+                            .map(|clone| futures::executor::block_on(clone.count()))
+                            .pipe(black_box)
+                            .sum::<usize>()
+                    });
+                },
+            );
+        });
+
+    group.finish();
+}
+
+/// A clone created late catching up on a large queued backlog
+fn benchmark_late_clone_catch_up(c: &mut Criterion) {
+    use clone_stream::ForkStream;
+
+    let mut group = c.benchmark_group("Late clone catch-up");
+    group.sample_size(15);
+    group.measurement_time(Duration::from_secs(3));
+    group.warm_up_time(Duration::from_secs(1));
+
+    let backlog = 10_000;
+    group.bench_function(BenchmarkId::new("backlog", backlog), |bencher| {
+        bencher.iter_batched(
+            || {
+                let mut early = test_items(backlog).pipe(stream::iter).fork();
+                let holder = early.clone();
+                futures::executor::block_on(early.by_ref().count());
+                (early, holder)
+            },
+            |(early, holder)| {
+                let late = holder.clone();
+                drop(holder);
+                drop(early);
+                futures::executor::block_on(late.count()).pipe(black_box)
+            },
+            criterion::BatchSize::LargeInput,
+        );
+    });
+
+    group.finish();
+}
+
 criterion_group!(
     fork_clone_benchmarks,
     benchmark_item_throughput,
-    benchmark_clone_creation
+    benchmark_clone_creation,
+    benchmark_lock_contention,
+    benchmark_queue_backend,
+    benchmark_late_clone_catch_up
 );
 criterion_main!(fork_clone_benchmarks);
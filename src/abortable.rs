@@ -0,0 +1,65 @@
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use futures::{
+    FutureExt, Stream, StreamExt,
+    future::{self, AbortHandle, Abortable, Pending},
+};
+
+use crate::CloneStream;
+
+/// A clone that can be stopped from elsewhere with an [`AbortHandle`].
+///
+/// Created with [`CloneStream::abortable`]. Once aborted, the clone is closed
+/// like with [`CloneStream::close`] and yields `None`, even when it is waiting
+/// for the base stream at that moment.
+pub struct AbortableCloneStream<BaseStream>
+where
+    BaseStream: Stream<Item: Clone>,
+{
+    clone: CloneStream<BaseStream>,
+    /// Completes once the handle aborts, and wakes the polling task then.
+    aborted: Abortable<Pending<()>>,
+}
+
+impl<BaseStream> AbortableCloneStream<BaseStream>
+where
+    BaseStream: Stream<Item: Clone>,
+{
+    pub(crate) fn new(clone: CloneStream<BaseStream>) -> (Self, AbortHandle) {
+        let (aborted, handle) = future::abortable(future::pending());
+        (Self { clone, aborted }, handle)
+    }
+
+    /// Checks whether the handle aborted this clone.
+    #[must_use]
+    pub fn is_aborted(&self) -> bool {
+        self.aborted.is_aborted()
+    }
+}
+
+impl<BaseStream> Stream for AbortableCloneStream<BaseStream>
+where
+    BaseStream: Stream<Item: Clone>,
+{
+    type Item = BaseStream::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        if this.aborted.poll_unpin(cx).is_ready() {
+            this.clone.close();
+            return Poll::Ready(None);
+        }
+        this.clone.poll_next_unpin(cx)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.is_aborted() {
+            (0, Some(0))
+        } else {
+            self.clone.size_hint()
+        }
+    }
+}
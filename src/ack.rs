@@ -0,0 +1,125 @@
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, Mutex, Weak},
+};
+
+use futures::Stream;
+
+use crate::fork::Fork;
+
+/// Acknowledges an item received with
+/// [`CloneStream::next_with_ack`](crate::CloneStream::next_with_ack).
+///
+/// The item stays queued until the token is acknowledged with
+/// [`AckToken::ack`] or dropped, even after every clone has received it.
+pub struct AckToken<BaseStream>
+where
+    BaseStream: Stream<Item: Clone>,
+{
+    fork: Weak<Mutex<Fork<BaseStream>>>,
+    /// Sequence number of the queued item, or `None` if the item was never
+    /// queued, for example because it was replayed.
+    sequence: Option<u64>,
+}
+
+impl<BaseStream> AckToken<BaseStream>
+where
+    BaseStream: Stream<Item: Clone>,
+{
+    pub(crate) fn new(fork: Weak<Mutex<Fork<BaseStream>>>, sequence: Option<u64>) -> Self {
+        Self { fork, sequence }
+    }
+
+    /// Marks the item as processed by the clone that received it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the internal fork lock is poisoned.
+    pub fn ack(mut self) {
+        if let Some((fork, sequence)) = self.take() {
+            fork.lock()
+                .expect("Fork lock poisoned during ack")
+                .ack(sequence);
+        }
+    }
+
+    /// Takes the fork and the sequence number of a queued item that was not
+    /// acknowledged yet.
+    fn take(&mut self) -> Option<(Arc<Mutex<Fork<BaseStream>>>, u64)> {
+        let sequence = self.sequence.take()?;
+        Some((self.fork.upgrade()?, sequence))
+    }
+}
+
+impl<BaseStream> Drop for AckToken<BaseStream>
+where
+    BaseStream: Stream<Item: Clone>,
+{
+    fn drop(&mut self) {
+        if let Some((fork, sequence)) = self.take() {
+            if let Ok(mut fork) = fork.lock() {
+                fork.ack(sequence);
+            } else {
+                log::warn!("Failed to acquire lock to acknowledge item {sequence}");
+            }
+        }
+    }
+}
+
+/// Queued items that were received with
+/// [`CloneStream::next_with_ack`](crate::CloneStream::next_with_ack) and not
+/// acknowledged yet.
+#[derive(Default)]
+pub(crate) struct Acks {
+    /// Number of unacknowledged tokens of each queued item, by sequence
+    /// number.
+    unacked: BTreeMap<u64, usize>,
+    /// The clone that is being polled for an item it acknowledges later, and
+    /// the sequence number of that item once it is kept in the queue.
+    receiving: Option<(usize, Option<u64>)>,
+}
+
+impl Acks {
+    pub(crate) fn begin_receiving(&mut self, clone_id: usize) {
+        self.receiving = Some((clone_id, None));
+    }
+
+    /// Stops receiving and returns the sequence number of the item that was
+    /// kept for the receiving clone, if any.
+    pub(crate) fn finish_receiving(&mut self) -> Option<u64> {
+        self.receiving.take().and_then(|(_, sequence)| sequence)
+    }
+
+    pub(crate) fn is_receiving(&self, clone_id: usize) -> bool {
+        self.receiving
+            .is_some_and(|(receiving_id, _)| receiving_id == clone_id)
+    }
+
+    /// Keeps the item with `sequence` queued until the receiving clone
+    /// acknowledges it.
+    pub(crate) fn hold(&mut self, sequence: u64) {
+        *self.unacked.entry(sequence).or_default() += 1;
+        if let Some((_, held)) = &mut self.receiving {
+            *held = Some(sequence);
+        }
+    }
+
+    /// Releases one token of the item with `sequence`. Returns whether no
+    /// other token holds the item anymore.
+    pub(crate) fn release(&mut self, sequence: u64) -> bool {
+        match self.unacked.get_mut(&sequence) {
+            Some(count) if *count > 1 => {
+                *count -= 1;
+                false
+            }
+            _ => {
+                self.unacked.remove(&sequence);
+                true
+            }
+        }
+    }
+
+    pub(crate) fn is_held(&self, sequence: u64) -> bool {
+        self.unacked.contains_key(&sequence)
+    }
+}
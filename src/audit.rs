@@ -0,0 +1,94 @@
+use std::{
+    fmt::Debug,
+    sync::{Arc, Mutex},
+};
+
+use futures::{Stream, StreamExt};
+
+/// Records the items every watched clone receives, to check that each clone
+/// received every source item exactly once and in order.
+///
+/// ```rust
+/// use clone_stream::{DeliveryAuditor, ForkConfig, ForkStream};
+/// use futures::{StreamExt, executor::block_on, future::join, stream};
+///
+/// let mut auditor = DeliveryAuditor::new();
+/// let first = stream::iter(0..3).fork_with_config(ForkConfig::delivering_to_idle_clones());
+/// let second = auditor.watch(first.clone());
+/// let first = auditor.watch(first);
+/// block_on(join(first.count(), second.count()));
+/// auditor.assert_exactly_once_broadcast(&[0, 1, 2]);
+/// ```
+pub struct DeliveryAuditor<Item> {
+    received: Vec<Arc<Mutex<Vec<Item>>>>,
+}
+
+impl<Item> Default for DeliveryAuditor<Item> {
+    fn default() -> Self {
+        Self {
+            received: Vec::new(),
+        }
+    }
+}
+
+impl<Item> DeliveryAuditor<Item>
+where
+    Item: Clone + Debug + PartialEq,
+{
+    /// Creates an auditor that watches no clones yet.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Wraps a clone so every item it yields is recorded.
+    ///
+    /// # Panics
+    ///
+    /// The returned stream panics if recording an item panicked before.
+    pub fn watch<Watched>(
+        &mut self,
+        clone: Watched,
+    ) -> impl Stream<Item = Item> + use<Watched, Item>
+    where
+        Watched: Stream<Item = Item>,
+    {
+        let received = Arc::new(Mutex::new(Vec::new()));
+        self.received.push(received.clone());
+        clone.inspect(move |item| {
+            received
+                .lock()
+                .expect("Received items lock poisoned during watch")
+                .push(item.clone());
+        })
+    }
+
+    /// Checks that every watched clone received exactly the `source` items,
+    /// in order.
+    ///
+    /// # Panics
+    ///
+    /// Panics with the first difference if a watched clone missed, repeated
+    /// or reordered any of the `source` items.
+    pub fn assert_exactly_once_broadcast(&self, source: &[Item]) {
+        for (clone, received) in self.received.iter().enumerate() {
+            let received = received
+                .lock()
+                .expect("Received items lock poisoned during audit");
+            if received.as_slice() == source {
+                continue;
+            }
+            let position = received
+                .iter()
+                .zip(source)
+                .position(|(received, expected)| received != expected)
+                .unwrap_or(received.len().min(source.len()));
+            panic!(
+                "Clone {clone} diverged at position {position}: expected {:?}, received {:?}\n  \
+                 expected: {source:?}\n  received: {received:?}",
+                source.get(position),
+                received.get(position),
+            );
+        }
+    }
+}
@@ -0,0 +1,68 @@
+use std::{
+    future,
+    sync::{Arc, Mutex},
+    task::Poll,
+};
+
+use crate::waker_set::WakerSet;
+
+/// A rendezvous point for a fixed number of tasks, returned together with the
+/// clones by [`CloneStream::split_synced`](crate::CloneStream::split_synced).
+///
+/// Every call to [`Barrier::wait`] waits until as many calls as there are
+/// parties have been made. The barrier can be reused afterwards. It does not
+/// depend on a particular async runtime.
+#[derive(Clone)]
+pub struct Barrier {
+    state: Arc<Mutex<BarrierState>>,
+}
+
+struct BarrierState {
+    parties: usize,
+    arrived: usize,
+    /// Number of times all parties have arrived.
+    generation: u64,
+    wakers: WakerSet,
+}
+
+impl Barrier {
+    pub(crate) fn new(parties: usize) -> Self {
+        Self {
+            state: Arc::new(Mutex::new(BarrierState {
+                parties,
+                arrived: 0,
+                generation: 0,
+                wakers: WakerSet::default(),
+            })),
+        }
+    }
+
+    /// Waits until all parties have called this method.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the internal barrier lock is poisoned.
+    pub async fn wait(&self) {
+        let generation = {
+            let mut state = self.state.lock().expect("Barrier lock poisoned");
+            state.arrived += 1;
+            if state.arrived >= state.parties {
+                state.arrived = 0;
+                state.generation += 1;
+                state.wakers.wake_all();
+                return;
+            }
+            state.generation
+        };
+        future::poll_fn(|cx| {
+            let mut state = self.state.lock().expect("Barrier lock poisoned");
+            if state.generation == generation {
+                state.wakers.insert(cx.waker());
+                Poll::Pending
+            } else {
+                Poll::Ready(())
+            }
+        })
+        .await;
+    }
+}
@@ -0,0 +1,111 @@
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use futures::{Stream, StreamExt, ready, stream};
+use log::warn;
+use tokio::sync::broadcast::{self, Receiver, Sender, error::RecvError};
+
+use crate::{CloneStream, ForkConfig, ForkStream};
+
+/// Future that forwards the items of a clone into a
+/// [`tokio::sync::broadcast`] channel.
+///
+/// Created with [`CloneStream::into_broadcast`]. Spawn or await it to pump
+/// items. It completes when the clone ends, which closes the channel once the
+/// bridge is dropped. Items sent while no receiver exists are discarded, as
+/// with [`Sender::send`].
+#[must_use = "futures do nothing unless polled"]
+pub struct BroadcastBridge<BaseStream>
+where
+    BaseStream: Stream<Item: Clone>,
+{
+    clone: CloneStream<BaseStream>,
+    sender: Sender<BaseStream::Item>,
+}
+
+impl<BaseStream> BroadcastBridge<BaseStream>
+where
+    BaseStream: Stream<Item: Clone>,
+{
+    pub(crate) fn new(
+        clone: CloneStream<BaseStream>,
+        capacity: usize,
+    ) -> (Self, Receiver<BaseStream::Item>) {
+        let (sender, receiver) = broadcast::channel(capacity);
+        (Self { clone, sender }, receiver)
+    }
+
+    /// Creates another receiver of the channel, which receives the items
+    /// forwarded from now on.
+    #[must_use]
+    pub fn subscribe(&self) -> Receiver<BaseStream::Item> {
+        self.sender.subscribe()
+    }
+}
+
+impl<BaseStream> Future for BroadcastBridge<BaseStream>
+where
+    BaseStream: Stream<Item: Clone>,
+{
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let this = self.get_mut();
+        while let Some(item) = ready!(this.clone.poll_next_unpin(cx)) {
+            // Fails only when no receiver exists, then the item is not needed.
+            let _ = this.sender.send(item);
+        }
+        Poll::Ready(())
+    }
+}
+
+/// Extension trait to fork a [`tokio::sync::broadcast::Receiver`].
+pub trait ForkReceiver<Item>
+where
+    Item: Clone + Send + 'static,
+{
+    /// Turns the receiver into a cloneable stream of its items.
+    ///
+    /// Every clone receives every item, like another receiver of the channel,
+    /// so it forks with [`ForkConfig::deliver_to_idle_clones`]. Items the
+    /// receiver missed because it lagged behind the channel are skipped
+    /// with a warning. The stream ends when the channel is closed.
+    ///
+    /// ```rust
+    /// use clone_stream::ForkReceiver;
+    /// use futures::StreamExt;
+    /// use tokio::sync::broadcast;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let (sender, receiver) = broadcast::channel(4);
+    /// let stream = receiver.fork();
+    /// sender.send(1).unwrap();
+    /// drop(sender);
+    /// assert_eq!(stream.collect::<Vec<_>>().await, vec![1]);
+    /// # }
+    /// ```
+    fn fork(self) -> CloneStream<impl Stream<Item = Item>>;
+}
+
+impl<Item> ForkReceiver<Item> for Receiver<Item>
+where
+    Item: Clone + Send + 'static,
+{
+    fn fork(self) -> CloneStream<impl Stream<Item = Item>> {
+        stream::unfold(self, |mut receiver| async move {
+            loop {
+                match receiver.recv().await {
+                    Ok(item) => return Some((item, receiver)),
+                    Err(RecvError::Lagged(missed)) => {
+                        warn!("Broadcast receiver lagged behind and missed {missed} items.");
+                    }
+                    Err(RecvError::Closed) => return None,
+                }
+            }
+        })
+        .fork_with_config(ForkConfig::delivering_to_idle_clones())
+    }
+}
@@ -0,0 +1,31 @@
+use std::{sync::Arc, time::Instant};
+
+/// Source of the current time for the time-based behaviour of a fork, such as
+/// [`CloneStream::readiness`](crate::CloneStream::readiness).
+///
+/// Forks use [`SystemClock`] unless another clock is passed to
+/// [`ForkStream::fork_with_clock`](crate::ForkStream::fork_with_clock), for
+/// example a mock clock that tests advance by hand instead of sleeping.
+pub trait Clock: Send + Sync {
+    /// Returns the current time.
+    fn now(&self) -> Instant;
+}
+
+/// Clock that reads the system's monotonic clock with [`Instant::now`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+impl<Inner> Clock for Arc<Inner>
+where
+    Inner: Clock + ?Sized,
+{
+    fn now(&self) -> Instant {
+        (**self).now()
+    }
+}
@@ -1,13 +1,38 @@
+#[cfg(feature = "metrics")]
+use std::time::Instant;
 use std::{
+    any::Any,
+    collections::{VecDeque, vec_deque},
+    iter,
+    ops::Range,
     pin::Pin,
-    sync::{Arc, RwLock},
-    task::{Context, Poll},
+    sync::{Arc, Mutex, MutexGuard, TryLockError},
+    task::{Context, Poll, Waker, ready},
+    vec,
 };
 
-use futures::{Stream, stream::FusedStream};
-use log::trace;
+#[cfg(feature = "bytes")]
+use bytes::Bytes;
+use futures::{
+    FutureExt, Sink, Stream, StreamExt,
+    future::{self, AbortHandle, FusedFuture},
+    stream::{self, FusedStream},
+    task::noop_waker_ref,
+};
+use log::{error, trace};
 
-use crate::fork::Fork;
+#[cfg(feature = "broadcast")]
+use crate::BroadcastBridge;
+#[cfg(feature = "metrics")]
+use crate::PollMetrics;
+use crate::{
+    AbortableCloneStream, AckToken, Barrier, CloneStreamError, ForkConfig, ForkGroup, ForkStream,
+    SharedHandle, TryRecvError,
+    fork::Fork,
+    reentrancy::PollingGuard,
+    states::CloneState,
+    trace::{PollEvent, PollOutcome, Readiness},
+};
 
 /// A stream that implements `Clone` and returns cloned items from a base
 /// stream.
@@ -44,15 +69,39 @@ use crate::fork::Fork;
 /// Items are cached internally until all clones have consumed them. The memory
 /// usage grows with the number of items that haven't been consumed by all
 /// clones yet.
+///
+/// # Cancellation safety
+///
+/// [`StreamExt::next`] is cancellation safe. A clone only advances past an
+/// item when a poll returns that item, never while it is pending, so dropping
+/// a pending `next()` future (for example in a `select!` branch that lost)
+/// does not lose or duplicate items. The next poll delivers the item the
+/// cancelled future would have returned.
 pub struct CloneStream<BaseStream>
 where
     BaseStream: Stream<Item: Clone>,
 {
-    pub(crate) fork: Arc<RwLock<Fork<BaseStream>>>,
+    pub(crate) fork: Arc<Mutex<Fork<BaseStream>>>,
     /// Unique identifier for this clone within the fork
     pub id: usize,
+    fuse_terminated: bool,
+    terminated: bool,
+    /// Whether [`CloneStream::close`] unregistered this clone already.
+    closed: bool,
+    /// Items given back with [`CloneStream::push_back`], the next one last.
+    /// Boxed, so a clone can be moved even when its items cannot.
+    pushed_back: Vec<Box<BaseStream::Item>>,
+    /// Items this clone receives before it closes itself, if limited with
+    /// [`CloneStream::clone_for`].
+    items_left: Option<usize>,
 }
 
+/// A [`CloneStream`] of a boxed base stream, which hides the type of the base
+/// stream.
+///
+/// Created with [`ForkStream::fork_boxed`].
+pub type BoxedCloneStream<Item> = CloneStream<Pin<Box<dyn Stream<Item = Item> + Send>>>;
+
 impl<BaseStream> From<Fork<BaseStream>> for CloneStream<BaseStream>
 where
     BaseStream: Stream<Item: Clone>,
@@ -63,10 +112,77 @@ where
             .register()
             .expect("Failed to register initial clone");
 
-        Self {
-            id,
-            fork: Arc::new(RwLock::new(fork)),
-        }
+        Self::new(Arc::new(Mutex::new(fork)), id)
+    }
+}
+
+impl<Items> CloneStream<stream::Iter<Items>>
+where
+    Items: Iterator<Item: Clone>,
+{
+    /// Creates a cloneable stream that yields the given items.
+    ///
+    /// Shorthand for wrapping the items with [`stream::iter`] and forking it
+    /// with [`ForkConfig::deliver_to_idle_clones`],
+    /// so every clone receives every item, no matter when it is polled.
+    ///
+    /// ```rust
+    /// use clone_stream::CloneStream;
+    ///
+    /// let stream = CloneStream::from_items(vec![1, 2, 3]);
+    /// let clone = stream.clone();
+    /// ```
+    pub fn from_items(items: impl IntoIterator<IntoIter = Items>) -> Self {
+        stream::iter(items).fork_with_config(ForkConfig::delivering_to_idle_clones())
+    }
+}
+
+impl<Streams> CloneStream<stream::Flatten<Streams>>
+where
+    Streams: Stream<Item: Stream<Item: Clone>>,
+{
+    /// Creates a cloneable stream of the items of a stream of streams.
+    ///
+    /// Shorthand for [`StreamExt::flatten`] followed by forking with
+    /// [`ForkConfig::deliver_to_idle_clones`]. Inner streams are consumed one
+    /// after another: clones receive every item of an inner stream before any
+    /// item of the next one. Only the
+    /// items need to implement [`Clone`], the inner streams do not.
+    ///
+    /// ```rust
+    /// use clone_stream::CloneStream;
+    /// use futures::{StreamExt, executor::block_on, stream};
+    ///
+    /// let streams = stream::iter([stream::iter(0..2), stream::iter(5..7)]);
+    /// let stream = CloneStream::fork_from_stream_of_streams(streams);
+    /// let items: Vec<_> = block_on(stream.collect());
+    /// assert_eq!(items, vec![0, 1, 5, 6]);
+    /// ```
+    pub fn fork_from_stream_of_streams(streams: Streams) -> Self {
+        Self::from(Fork::with_config(
+            streams.flatten(),
+            ForkConfig::delivering_to_idle_clones(),
+        ))
+    }
+}
+
+impl<Item> From<Vec<Item>> for CloneStream<stream::Iter<vec::IntoIter<Item>>>
+where
+    Item: Clone,
+{
+    /// Creates a cloneable stream that yields the items of the vector.
+    fn from(items: Vec<Item>) -> Self {
+        Self::from_items(items)
+    }
+}
+
+impl<Item> From<VecDeque<Item>> for CloneStream<stream::Iter<vec_deque::IntoIter<Item>>>
+where
+    Item: Clone,
+{
+    /// Creates a cloneable stream that yields the items of the deque in order.
+    fn from(items: VecDeque<Item>) -> Self {
+        Self::from_items(items)
     }
 }
 
@@ -79,22 +195,15 @@ where
     /// # Panics
     ///
     /// Panics if the maximum number of clones has been exceeded for this
-    /// stream. The limit is set when creating the stream with
-    /// [`ForkStream::fork_with_limits`].
+    /// stream, or if the new clone would exceed
+    /// [`ForkConfig::max_total_memory_bytes`](crate::ForkConfig::max_total_memory_bytes).
+    /// The limits are set when creating the stream, for example with
+    /// [`ForkStream::fork_with_limits`]. Use [`CloneStream::try_clone`] to
+    /// handle these errors.
     ///
     /// [`ForkStream::fork_with_limits`]: crate::ForkStream::fork_with_limits
     fn clone(&self) -> Self {
-        let mut fork = self.fork.write().expect("Fork lock poisoned during clone");
-        let clone_id = fork
-            .clone_registry
-            .register()
-            .expect("Failed to register clone - clone limit exceeded");
-        drop(fork);
-
-        Self {
-            fork: self.fork.clone(),
-            id: clone_id,
-        }
+        Self::register(&self.fork)
     }
 }
 
@@ -105,22 +214,24 @@ where
     type Item = BaseStream::Item;
 
     fn poll_next(self: Pin<&mut Self>, current_task: &mut Context) -> Poll<Option<Self::Item>> {
-        trace!("Polling next item for clone {}.", self.id);
-        let waker = current_task.waker();
-        let mut fork = self
-            .fork
-            .write()
-            .expect("Fork lock poisoned during poll_next");
-        fork.poll_clone(self.id, waker)
+        let this = self.get_mut();
+        if let Some(item) = this.take_pushed_back() {
+            return Poll::Ready(Some(item));
+        }
+        let poll_result = this.poll_fork(current_task, Fork::poll_clone);
+        if matches!(poll_result, Poll::Ready(Some(_))) {
+            this.count_item();
+        }
+        poll_result
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
         let fork = self
             .fork
-            .read()
+            .lock()
             .expect("Fork lock poisoned during size_hint");
-        let (lower, upper) = fork.size_hint();
-        let n_cached = fork.remaining_queued_items(self.id);
+        let (lower, upper) = fork.base_size_hint();
+        let n_cached = fork.remaining_queued_items(self.id) + self.pushed_back.len();
         (lower + n_cached, upper.map(|u| u + n_cached))
     }
 }
@@ -135,11 +246,15 @@ where
     /// 1. The underlying base stream is terminated
     /// 2. This clone has no remaining queued items to consume
     fn is_terminated(&self) -> bool {
+        if self.terminated {
+            return true;
+        }
         let fork = self
             .fork
-            .read()
+            .lock()
             .expect("Fork lock poisoned during is_terminated");
-        fork.is_terminated() && fork.remaining_queued_items(self.id) == 0
+        fork.base().is_none_or(FusedStream::is_terminated)
+            && fork.remaining_queued_items(self.id) == 0
     }
 }
 
@@ -148,8 +263,12 @@ where
     BaseStream: Stream<Item: Clone>,
 {
     fn drop(&mut self) {
-        if let Ok(mut fork) = self.fork.try_write() {
-            fork.unregister(self.id);
+        if let Ok(mut fork) = self.fork.try_lock() {
+            if self.closed {
+                fork.release(self.id);
+            } else {
+                fork.unregister(self.id);
+            }
         } else {
             log::warn!(
                 "Failed to acquire lock during clone drop for clone {}",
@@ -159,37 +278,1830 @@ where
     }
 }
 
+/// Keeps a clone receiving items while it waits in
+/// [`CloneStream::wait_until_buffered`].
+struct BufferWait<'fork, BaseStream>
+where
+    BaseStream: Stream<Item: Clone>,
+{
+    fork: &'fork Mutex<Fork<BaseStream>>,
+    clone_id: usize,
+}
+
+impl<BaseStream> Drop for BufferWait<'_, BaseStream>
+where
+    BaseStream: Stream<Item: Clone>,
+{
+    fn drop(&mut self) {
+        if let Ok(mut fork) = self.fork.lock() {
+            fork.end_buffer_wait(self.clone_id);
+        } else {
+            log::warn!(
+                "Failed to acquire lock to end the buffer wait of clone {}",
+                self.clone_id
+            );
+        }
+    }
+}
+
 impl<BaseStream> CloneStream<BaseStream>
 where
     BaseStream: Stream<Item: Clone>,
 {
-    /// Returns the number of items currently queued for this clone.
+    /// Registers a new clone with an existing fork.
+    pub(crate) fn register(fork: &Arc<Mutex<Fork<BaseStream>>>) -> Self {
+        let registration = fork
+            .lock()
+            .expect("Fork lock poisoned during clone")
+            .register_clone();
+        Self::new(fork.clone(), Self::expect_registered(registration))
+    }
+
+    /// Returns the ID of a registered clone, or panics with the reason the
+    /// clone could not be registered.
+    fn expect_registered(registration: crate::Result<usize>) -> usize {
+        match registration {
+            Ok(clone_id) => clone_id,
+            Err(error @ CloneStreamError::MemoryLimitExceeded { .. }) => {
+                panic!("Failed to register clone - {error}")
+            }
+            Err(error) => panic!("Failed to register clone - clone limit exceeded: {error}"),
+        }
+    }
+
+    /// Returns a future that creates a new clone of this stream once the fork
+    /// is not locked.
     ///
-    /// This represents items that have been produced by the base stream but not
-    /// yet consumed by this particular clone. Other clones may have
-    /// different queue lengths depending on their consumption patterns.
+    /// [`Clone::clone`] waits for the fork lock, which deadlocks when the
+    /// lock is held by the current thread, for example when cloning from
+    /// inside the base stream while it is polled, or from inside
+    /// [`CloneStream::with_base`]. This future never waits for the lock.
+    /// While the lock is held, it returns pending and wakes its task to try
+    /// again on the next poll. The future does not borrow this clone.
     ///
     /// # Panics
     ///
-    /// Panics if the internal fork lock is poisoned. This should not happen
-    /// under normal circumstances.
+    /// Panics if the internal fork lock is poisoned, or for the same reasons
+    /// as [`Clone::clone`] when the clone cannot be registered.
     ///
-    /// # Examples
+    /// ```rust
+    /// use clone_stream::ForkStream;
+    /// use futures::{StreamExt, executor::block_on, stream};
+    ///
+    /// let clone = stream::iter([1, 2]).fork();
+    /// let mut later = block_on(clone.spawn_clone_deferred());
+    /// assert_eq!(block_on(later.next()), Some(1));
+    /// ```
+    pub fn spawn_clone_deferred(&self) -> impl Future<Output = Self> + use<BaseStream> {
+        let fork = self.fork.clone();
+        future::poll_fn(move |cx| {
+            let registration = match fork.try_lock() {
+                Ok(mut fork) => fork.register_clone(),
+                Err(TryLockError::WouldBlock) => {
+                    trace!("Fork is locked, deferring clone registration.");
+                    cx.waker().wake_by_ref();
+                    return Poll::Pending;
+                }
+                Err(TryLockError::Poisoned(_)) => {
+                    panic!("Fork lock poisoned during spawn_clone_deferred")
+                }
+            };
+            Poll::Ready(Self::new(
+                fork.clone(),
+                Self::expect_registered(registration),
+            ))
+        })
+    }
+
+    /// Creates a new clone of this stream, or returns why it could not be
+    /// created.
+    ///
+    /// # Errors
+    ///
+    /// Returns
+    /// [`CloneStreamError::MaxClonesExceeded`](crate::CloneStreamError::MaxClonesExceeded)
+    /// if the clone limit is reached, and
+    /// [`CloneStreamError::MemoryLimitExceeded`](crate::CloneStreamError::MemoryLimitExceeded)
+    /// if the items queued for the new clone would exceed
+    /// [`ForkConfig::max_total_memory_bytes`](crate::ForkConfig::max_total_memory_bytes).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the internal fork lock is poisoned.
+    pub fn try_clone(&self) -> crate::Result<Self> {
+        let clone_id = self
+            .fork
+            .lock()
+            .expect("Fork lock poisoned during try_clone")
+            .register_clone()?;
+        Ok(Self::new(self.fork.clone(), clone_id))
+    }
+
+    /// Polls this clone with `poll`, which receives the locked fork, the ID
+    /// of this clone and the waker of the current task.
+    fn poll_fork<Output>(
+        &mut self,
+        current_task: &mut Context,
+        poll: impl FnOnce(&mut Fork<BaseStream>, usize, &Waker) -> Poll<Option<Output>>,
+    ) -> Poll<Option<Output>> {
+        if self.terminated {
+            trace!("Clone {} already terminated.", self.id);
+            return Poll::Ready(None);
+        }
+        trace!("Polling next item for clone {}.", self.id);
+        // Draining a long backlog never returns pending by itself, so give the
+        // scheduler a chance to run other tasks once the budget is used up.
+        #[cfg(feature = "tokio")]
+        let coop = ready!(tokio::task::coop::poll_proceed(current_task));
+        let Some(_guard) = PollingGuard::enter(Arc::as_ptr(&self.fork).addr()) else {
+            error!(
+                "Clone {} was polled from inside the base stream of its own fork. Returning \
+                 pending instead of deadlocking.",
+                self.id
+            );
+            return Poll::Pending;
+        };
+        let waker = current_task.waker();
+        let poll_result = poll(&mut self.lock_for_poll(), self.id, waker);
+        #[cfg(feature = "tokio")]
+        if poll_result.is_ready() {
+            coop.made_progress();
+        }
+        if self.fuse_terminated && matches!(poll_result, Poll::Ready(None)) {
+            self.terminated = true;
+        }
+        poll_result
+    }
+
+    /// Locks the fork for polling.
+    #[cfg(not(feature = "metrics"))]
+    fn lock_for_poll(&self) -> MutexGuard<'_, Fork<BaseStream>> {
+        self.fork
+            .lock()
+            .expect("Fork lock poisoned during poll_next")
+    }
+
+    /// Locks the fork for polling and counts waiting for a lock held by
+    /// another clone.
+    #[cfg(feature = "metrics")]
+    fn lock_for_poll(&self) -> MutexGuard<'_, Fork<BaseStream>> {
+        match self.fork.try_lock() {
+            Ok(fork) => fork,
+            Err(TryLockError::Poisoned(_)) => panic!("Fork lock poisoned during poll_next"),
+            Err(TryLockError::WouldBlock) => {
+                let waiting_since = Instant::now();
+                let fork = self
+                    .fork
+                    .lock()
+                    .expect("Fork lock poisoned during poll_next");
+                fork.poll_counters.record_lock_wait(waiting_since.elapsed());
+                fork
+            }
+        }
+    }
+
+    /// Counts a received item towards the limit of [`Self::clone_for`], and
+    /// closes this clone after the last one.
+    fn count_item(&mut self) {
+        if let Some(items_left) = &mut self.items_left {
+            *items_left -= 1;
+            if *items_left == 0 {
+                self.close();
+            }
+        }
+    }
+
+    /// Takes the last pushed back item. It already counted towards the limit
+    /// of [`Self::clone_for`] when it was first received.
+    fn take_pushed_back(&mut self) -> Option<BaseStream::Item> {
+        self.pushed_back.pop().map(|item| *item)
+    }
+
+    fn new(fork: Arc<Mutex<Fork<BaseStream>>>, id: usize) -> Self {
+        Self {
+            fork,
+            id,
+            fuse_terminated: false,
+            terminated: false,
+            closed: false,
+            pushed_back: Vec::new(),
+            items_left: None,
+        }
+    }
+
+    /// Makes this clone remember when it has returned `None`.
+    ///
+    /// Afterwards, polling returns `None` immediately without locking the
+    /// fork or running the state machine. Only use this for base streams that
+    /// do not produce new items after returning `None` once. It applies to
+    /// this clone only, not to clones created from it later.
     ///
     /// ```rust
     /// use clone_stream::ForkStream;
-    /// use futures::stream;
+    /// use futures::{StreamExt, stream};
     ///
-    /// let stream = stream::iter(vec![1, 2, 3]);
-    /// let clone_stream = stream.fork();
-    /// assert_eq!(clone_stream.n_queued_items(), 0);
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let mut clone = stream::iter(0..1).fork().fuse_terminated();
+    /// assert_eq!(clone.next().await, Some(0));
+    /// assert_eq!(clone.next().await, None);
+    /// assert_eq!(clone.next().await, None);
+    /// # }
     /// ```
     #[must_use]
-    pub fn n_queued_items(&self) -> usize {
-        trace!("Getting the number of queued items for clone {}.", self.id);
+    pub fn fuse_terminated(mut self) -> Self {
+        self.fuse_terminated = true;
+        self
+    }
+
+    /// Creates a clone that receives at most `items` items and then closes
+    /// itself, as with [`CloneStream::close`].
+    ///
+    /// Unlike [`StreamExt::take`], the clone stops holding back queued items
+    /// as soon as it has received its last item, even while it is not
+    /// dropped. Only items returned by polling the clone as a stream count.
+    /// After [`CloneStream::resubscribe`], the clone is no longer limited.
+    ///
+    /// # Panics
+    ///
+    /// Panics like [`Clone::clone`] if the clone cannot be created.
+    ///
+    /// ```rust
+    /// use clone_stream::ForkStream;
+    /// use futures::{StreamExt, executor::block_on, stream};
+    ///
+    /// let stream = stream::iter(0..10).fork();
+    /// let first_two = stream.clone_for(2);
+    /// assert_eq!(block_on(first_two.collect::<Vec<_>>()), vec![0, 1]);
+    /// ```
+    #[must_use]
+    pub fn clone_for(&self, items: usize) -> Self {
+        let mut clone = self.clone();
+        if items == 0 {
+            clone.close();
+        } else {
+            clone.items_left = Some(items);
+        }
+        clone
+    }
+
+    /// Stops this clone from receiving further items without dropping it.
+    ///
+    /// Afterwards, polling returns `None` and the clone no longer counts as
+    /// registered with the fork. Queued items that only this clone still
+    /// needed are discarded. [`CloneStream::resubscribe`] reopens the clone.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the internal fork lock is poisoned.
+    ///
+    /// ```rust
+    /// use clone_stream::ForkStream;
+    /// use futures::{StreamExt, executor::block_on, stream};
+    ///
+    /// let mut clone = stream::iter(0..3).fork();
+    /// clone.close();
+    /// assert_eq!(block_on(clone.next()), None);
+    /// ```
+    pub fn close(&mut self) {
+        if self.closed {
+            return;
+        }
+        self.closed = true;
+        self.terminated = true;
         self.fork
-            .read()
-            .expect("Fork lock poisoned during n_queued_items")
-            .remaining_queued_items(self.id)
+            .lock()
+            .expect("Fork lock poisoned during close")
+            .close(self.id);
+    }
+
+    /// Ends every clone of the fork and drops the base stream, even while
+    /// the base stream could still produce items.
+    ///
+    /// All clones, including those created afterwards, return `None` on
+    /// their next poll. Clones waiting for the base stream are woken, and
+    /// queued items are discarded.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the internal fork lock is poisoned.
+    ///
+    /// ```rust
+    /// use clone_stream::ForkStream;
+    /// use futures::{StreamExt, executor::block_on, stream};
+    ///
+    /// let mut clone = stream::repeat(1).fork();
+    /// let sibling = clone.clone();
+    /// assert_eq!(block_on(clone.next()), Some(1));
+    /// sibling.shutdown();
+    /// assert_eq!(block_on(clone.next()), None);
+    /// ```
+    pub fn shutdown(&self) {
+        self.fork
+            .lock()
+            .expect("Fork lock poisoned during shutdown")
+            .shutdown();
+    }
+
+    /// Checks whether the fork was shut down with [`CloneStream::shutdown`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the internal fork lock is poisoned.
+    #[must_use]
+    pub fn is_shut_down(&self) -> bool {
+        self.fork
+            .lock()
+            .expect("Fork lock poisoned during is_shut_down")
+            .is_shut_down()
+    }
+
+    /// Queues `item` for every clone as if the base stream produced it, and
+    /// wakes clones waiting for the base stream.
+    ///
+    /// Lets tests drive consumers without wiring up a channel as base
+    /// stream. The base stream is not polled.
+    ///
+    /// # Errors
+    ///
+    /// Returns
+    /// [`CloneStreamError::BaseStreamEnded`](crate::CloneStreamError::BaseStreamEnded)
+    /// if the base stream has ended or was shut down.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the internal fork lock is poisoned.
+    ///
+    /// ```rust
+    /// use clone_stream::ForkStream;
+    /// use futures::{StreamExt, executor::block_on, stream};
+    ///
+    /// let mut clone = stream::pending::<u8>().fork();
+    /// clone.inject(7).unwrap();
+    /// assert_eq!(block_on(clone.next()), Some(7));
+    /// ```
+    #[cfg(feature = "test-util")]
+    pub fn inject(&self, item: BaseStream::Item) -> crate::Result<()> {
+        self.fork
+            .lock()
+            .expect("Fork lock poisoned during inject")
+            .inject(self.id, item)
+    }
+
+    /// Makes this clone stoppable from elsewhere, for example from another
+    /// task.
+    ///
+    /// Aborting the returned handle closes the clone, which then yields
+    /// `None` promptly, while other clones keep receiving items.
+    ///
+    /// ```rust
+    /// use clone_stream::ForkStream;
+    /// use futures::{StreamExt, executor::block_on, stream};
+    ///
+    /// let (mut clone, handle) = stream::iter(0..3).fork().abortable();
+    /// assert_eq!(block_on(clone.next()), Some(0));
+    /// handle.abort();
+    /// assert_eq!(block_on(clone.next()), None);
+    /// ```
+    #[must_use]
+    pub fn abortable(self) -> (AbortableCloneStream<BaseStream>, AbortHandle) {
+        AbortableCloneStream::new(self)
+    }
+
+    /// Makes this clone receive only items produced from now on, reopening
+    /// it if it was closed.
+    ///
+    /// Like [`tokio::sync::broadcast::Receiver::resubscribe`], items that are
+    /// queued for this clone are skipped. If the base stream has ended
+    /// already, the next poll returns `None`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the internal fork lock is poisoned.
+    ///
+    /// [`tokio::sync::broadcast::Receiver::resubscribe`]: https://docs.rs/tokio/latest/tokio/sync/broadcast/struct.Receiver.html#method.resubscribe
+    pub fn resubscribe(&mut self) {
+        self.fork
+            .lock()
+            .expect("Fork lock poisoned during resubscribe")
+            .resubscribe(self.id);
+        self.closed = false;
+        self.terminated = false;
+        self.items_left = None;
+    }
+
+    /// Allows this clone to receive `n` more items, for forks created with
+    /// [`ForkStream::fork_with_credits`].
+    ///
+    /// A clone without credits stays pending until credits are granted, so
+    /// the base stream is not advanced on its behalf. Each item, and the end
+    /// of the base stream, uses one credit. Has no effect on other forks.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the internal fork lock is poisoned.
+    pub fn grant_credits(&self, n: usize) {
+        if let Some(credits) = &mut self
+            .fork
+            .lock()
+            .expect("Fork lock poisoned during grant_credits")
+            .credits
+        {
+            credits.grant(self.id, n);
+        }
+    }
+
+    /// Returns how many more items this clone may receive, for forks created
+    /// with [`ForkStream::fork_with_credits`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the internal fork lock is poisoned.
+    #[must_use]
+    pub fn credits(&self) -> Option<usize> {
+        self.fork
+            .lock()
+            .expect("Fork lock poisoned during credits")
+            .credits
+            .as_ref()
+            .map(|credits| credits.balance(self.id))
+    }
+
+    /// Gives `item` back to this clone, so the next poll returns it again.
+    ///
+    /// Useful for lookahead, for example when a parser reads an item that
+    /// belongs to the next record. Only this clone receives the item again.
+    /// Items pushed back later are returned first.
+    ///
+    /// ```rust
+    /// use clone_stream::ForkStream;
+    /// use futures::{StreamExt, executor::block_on, stream};
+    ///
+    /// let mut clone = stream::iter(0..3).fork();
+    /// let first = block_on(clone.next()).unwrap();
+    /// clone.push_back(first);
+    /// assert_eq!(block_on(clone.collect::<Vec<_>>()), vec![0, 1, 2]);
+    /// ```
+    pub fn push_back(&mut self, item: BaseStream::Item) {
+        self.pushed_back.push(Box::new(item));
+    }
+
+    /// Recovers the base stream from the last remaining clone.
+    ///
+    /// The base stream is returned in the pinned box the fork stored it in,
+    /// so it does not need to be [`Unpin`].
+    ///
+    /// # Errors
+    ///
+    /// Returns this clone unchanged when other clones or [`SharedHandle`]s of
+    /// the fork exist, when items are still queued for this clone, or when
+    /// the fork was shut down.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the internal fork lock is poisoned.
+    ///
+    /// ```rust
+    /// use clone_stream::ForkStream;
+    /// use futures::stream;
+    ///
+    /// let stream = stream::iter(0..3).fork();
+    /// let clone = stream.clone();
+    /// let stream = stream.into_inner_pinned().unwrap_err();
+    /// drop(clone);
+    /// assert!(stream.into_inner_pinned().is_ok());
+    /// ```
+    pub fn into_inner_pinned(self) -> Result<Pin<Box<BaseStream>>, Self> {
+        if Arc::strong_count(&self.fork) > 1 || self.has_buffered() || self.is_shut_down() {
+            return Err(self);
+        }
+        let fork = self.fork.clone();
+        drop(self);
+        let fork = Arc::into_inner(fork)
+            .expect("No other references to the fork exist")
+            .into_inner()
+            .expect("Fork lock poisoned during into_inner_pinned");
+        Ok(fork
+            .base_stream
+            .expect("The fork was not shut down, so it still has its base stream"))
+    }
+
+    /// Recovers the base stream from the last remaining clone.
+    ///
+    /// Use [`CloneStream::into_inner_pinned`] for base streams that are not
+    /// [`Unpin`].
+    ///
+    /// # Errors
+    ///
+    /// Returns this clone unchanged when other clones or [`SharedHandle`]s of
+    /// the fork exist, when items are still queued for this clone, or when
+    /// the fork was shut down.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the internal fork lock is poisoned.
+    pub fn into_inner(self) -> Result<BaseStream, Self>
+    where
+        BaseStream: Unpin,
+    {
+        self.into_inner_pinned()
+            .map(|base_stream| *Pin::into_inner(base_stream))
+    }
+
+    /// Returns a [`SharedHandle`] to the fork of this clone.
+    ///
+    /// The handle keeps the base stream alive without being a consumer
+    /// itself, so it never holds back items in the queue. Use
+    /// [`SharedHandle::subscribe`] to create new clones from it.
+    ///
+    /// ```rust
+    /// use clone_stream::ForkStream;
+    /// use futures::stream;
+    ///
+    /// let stream = stream::iter(0..3).fork();
+    /// let handle = stream.handle();
+    /// drop(stream);
+    /// let subscriber = handle.subscribe();
+    /// ```
+    #[must_use]
+    pub fn handle(&self) -> SharedHandle<BaseStream> {
+        SharedHandle::new(self.fork.clone())
+    }
+
+    /// Attaches read-only context that every clone of the fork can read with
+    /// [`CloneStream::context`].
+    ///
+    /// Attaching context again replaces the previous one for all clones.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the internal fork lock is poisoned.
+    ///
+    /// ```rust
+    /// use clone_stream::ForkStream;
+    /// use futures::stream;
+    ///
+    /// let stream = stream::iter(0..3).fork().with_shared_context("numbers");
+    /// let clone = stream.clone();
+    /// assert_eq!(clone.context::<&str>(), Some("numbers"));
+    /// ```
+    #[must_use]
+    pub fn with_shared_context<Context>(self, context: Context) -> Self
+    where
+        Context: Any + Send + Sync,
+    {
+        self.fork
+            .lock()
+            .expect("Fork lock poisoned during with_shared_context")
+            .context = Some(Arc::new(context));
+        self
+    }
+
+    /// Runs `flush` on the base stream after every poll of it, for base
+    /// streams that buffer internally and need to be flushed between polls.
+    ///
+    /// Setting a flush function again replaces the previous one for all
+    /// clones.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the internal fork lock is poisoned.
+    ///
+    /// ```rust
+    /// use clone_stream::ForkStream;
+    /// use futures::{Stream, StreamExt, executor::block_on, stream};
+    ///
+    /// let mut clone = stream::iter(0..3)
+    ///     .fork()
+    ///     .with_flush(|base| log::trace!("Flushing {:?}", base.size_hint()));
+    /// assert_eq!(block_on(clone.next()), Some(0));
+    /// ```
+    #[must_use]
+    pub fn with_flush<Flush>(self, mut flush: Flush) -> Self
+    where
+        BaseStream: Unpin,
+        Flush: FnMut(&mut BaseStream) + Send + 'static,
+    {
+        self.fork
+            .lock()
+            .expect("Fork lock poisoned during with_flush")
+            .flush = Some(Box::new(move |base| flush(Pin::get_mut(base))));
+        self
+    }
+
+    /// Protects items from eviction when the queue is full.
+    ///
+    /// When the queue is full, the oldest queued item for which `may_evict`
+    /// returns `true` is evicted. Items for which it returns `false`, such as
+    /// key frames of a video stream, stay queued even when they are the
+    /// oldest. If every queued item is protected, the oldest one is evicted
+    /// anyway. Setting a filter again replaces the previous one for all
+    /// clones.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the internal fork lock is poisoned.
+    ///
+    /// ```rust
+    /// use clone_stream::{ForkConfig, ForkStream};
+    /// use futures::stream;
+    ///
+    /// let config = ForkConfig {
+    ///     max_queue_size: 16,
+    ///     ..ForkConfig::default()
+    /// };
+    /// let stream = stream::iter(0..100)
+    ///     .fork_with_config(config)
+    ///     .with_eviction_filter(|item| item % 10 != 0);
+    /// ```
+    #[must_use]
+    pub fn with_eviction_filter<Filter>(self, may_evict: Filter) -> Self
+    where
+        Filter: Fn(&BaseStream::Item) -> bool + Send + Sync + 'static,
+    {
+        self.fork
+            .lock()
+            .expect("Fork lock poisoned during with_eviction_filter")
+            .eviction_filter = Some(Box::new(may_evict));
+        self
+    }
+
+    /// Estimates the memory of each item with `item_size`, for
+    /// [`ForkConfig::max_total_memory_bytes`](crate::ForkConfig::max_total_memory_bytes).
+    ///
+    /// Use this for items that own heap memory, since [`size_of`] only counts
+    /// the inline part of an item.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the internal fork lock is poisoned.
+    ///
+    /// ```rust
+    /// use clone_stream::{ForkConfig, ForkStream};
+    /// use futures::stream;
+    ///
+    /// let config = ForkConfig {
+    ///     max_total_memory_bytes: Some(1 << 20),
+    ///     ..ForkConfig::default()
+    /// };
+    /// let lines = stream::iter(vec![String::from("hello")])
+    ///     .fork_with_config(config)
+    ///     .with_item_size(|line| size_of::<String>() + line.capacity());
+    /// ```
+    #[must_use]
+    pub fn with_item_size<Size>(self, item_size: Size) -> Self
+    where
+        Size: Fn(&BaseStream::Item) -> usize + Send + Sync + 'static,
+    {
+        self.fork
+            .lock()
+            .expect("Fork lock poisoned during with_item_size")
+            .item_size = Some(Box::new(item_size));
+        self
+    }
+
+    /// Returns a copy of the context attached with
+    /// [`CloneStream::with_shared_context`].
+    ///
+    /// Returns `None` when no context was attached or when it is not of type
+    /// `Context`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the internal fork lock is poisoned.
+    #[must_use]
+    pub fn context<Context>(&self) -> Option<Context>
+    where
+        Context: Any + Clone,
+    {
+        self.fork
+            .lock()
+            .expect("Fork lock poisoned during context")
+            .context
+            .as_ref()?
+            .downcast_ref::<Context>()
+            .cloned()
+    }
+
+    /// Calls `f` with the base stream, for example to read the state of a
+    /// connection that the stream exposes through its own methods.
+    ///
+    /// The fork stays locked while `f` runs, so `f` should not poll clones of
+    /// this fork.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the fork was shut down with [`CloneStream::shutdown`], or if
+    /// the internal fork lock is poisoned.
+    ///
+    /// ```rust
+    /// use clone_stream::ForkStream;
+    /// use futures::{Stream, stream};
+    ///
+    /// let clone = stream::iter(0..3).fork();
+    /// assert_eq!(clone.with_base(|base| base.size_hint()), (3, Some(3)));
+    /// ```
+    pub fn with_base<Output>(&self, f: impl FnOnce(&BaseStream) -> Output) -> Output {
+        f(self
+            .fork
+            .lock()
+            .expect("Fork lock poisoned during with_base")
+            .base()
+            .expect("The base stream was dropped by shutdown"))
+    }
+
+    /// Returns the number of items currently queued for this clone.
+    ///
+    /// This represents items that have been produced by the base stream but not
+    /// yet consumed by this particular clone. Other clones may have
+    /// different queue lengths depending on their consumption patterns.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the internal fork lock is poisoned. This should not happen
+    /// under normal circumstances.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use clone_stream::ForkStream;
+    /// use futures::stream;
+    ///
+    /// let stream = stream::iter(vec![1, 2, 3]);
+    /// let clone_stream = stream.fork();
+    /// assert_eq!(clone_stream.n_queued_items(), 0);
+    /// ```
+    #[must_use]
+    pub fn n_queued_items(&self) -> usize {
+        trace!("Getting the number of queued items for clone {}.", self.id);
+        self.fork
+            .lock()
+            .expect("Fork lock poisoned during n_queued_items")
+            .remaining_queued_items(self.id)
+    }
+
+    /// Returns the largest number of items that were queued at once, shared
+    /// by all clones of this stream.
+    ///
+    /// Compare it with [`ForkConfig::max_queue_size`] after a representative
+    /// run to size the queue.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the internal fork lock is poisoned.
+    ///
+    /// [`ForkConfig::max_queue_size`]: crate::ForkConfig::max_queue_size
+    #[must_use]
+    pub fn buffer_high_water_mark(&self) -> usize {
+        self.fork
+            .lock()
+            .expect("Fork lock poisoned during buffer_high_water_mark")
+            .high_water
+    }
+
+    /// Restarts tracking [`CloneStream::buffer_high_water_mark`] from the
+    /// number of items queued right now.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the internal fork lock is poisoned.
+    pub fn reset_high_water(&self) {
+        let mut fork = self
+            .fork
+            .lock()
+            .expect("Fork lock poisoned during reset_high_water");
+        fork.high_water = fork.item_buffer.len();
+    }
+
+    /// Roughly estimates how many bytes the fork holds on behalf of this
+    /// clone: the items it will still receive, like [`CloneStream::len`]
+    /// counts them, plus its own bookkeeping.
+    ///
+    /// Items are counted at [`size_of`] their type, so the estimate is only
+    /// accurate for items without heap allocations. For byte chunks, see
+    /// `buffered_bytes` with the `bytes` feature.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the internal fork lock is poisoned.
+    #[must_use]
+    pub fn estimated_memory(&self) -> usize {
+        let fork = self
+            .fork
+            .lock()
+            .expect("Fork lock poisoned during estimated_memory");
+        size_of::<BaseStream::Item>() * self.remaining_len(&fork) + size_of::<CloneState>()
+    }
+
+    /// Returns how many items this clone missed because they stayed queued
+    /// longer than [`ForkConfig::item_ttl`](crate::ForkConfig::item_ttl).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the internal fork lock is poisoned.
+    #[must_use]
+    pub fn n_expired_items(&self) -> u64 {
+        self.fork
+            .lock()
+            .expect("Fork lock poisoned during n_expired_items")
+            .n_expired_items(self.id)
+    }
+
+    /// Returns the highest sequence number the base stream has produced, for
+    /// forks created with
+    /// [`ForkStream::fork_sequenced`](crate::ForkStream::fork_sequenced).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the internal fork lock is poisoned.
+    #[must_use]
+    pub fn last_sequence(&self) -> Option<u64> {
+        self.fork
+            .lock()
+            .expect("Fork lock poisoned during last_sequence")
+            .sequence_tracker
+            .as_ref()
+            .and_then(|tracker| tracker.last)
+    }
+
+    /// Returns the ranges of sequence numbers the base stream skipped, oldest
+    /// first, for forks created with
+    /// [`ForkStream::fork_sequenced`](crate::ForkStream::fork_sequenced).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the internal fork lock is poisoned.
+    #[must_use]
+    pub fn missing_sequences(&self) -> Vec<Range<u64>> {
+        self.fork
+            .lock()
+            .expect("Fork lock poisoned during missing_sequences")
+            .sequence_tracker
+            .as_ref()
+            .map(|tracker| tracker.missing.clone())
+            .unwrap_or_default()
+    }
+
+    /// Returns whether this clone has queued items it can consume right away.
+    ///
+    /// Cheaper than comparing [`CloneStream::n_queued_items`] with zero, since
+    /// it stops at the first unseen item.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the internal fork lock is poisoned.
+    #[must_use]
+    pub fn has_buffered(&self) -> bool {
+        !self.pushed_back.is_empty()
+            || self
+                .fork
+                .lock()
+                .expect("Fork lock poisoned during has_buffered")
+                .has_unseen_items(self.id)
+    }
+
+    /// Estimates how soon this clone will receive its next item.
+    ///
+    /// Returns [`Readiness::Ready`] when an item is queued for this clone,
+    /// [`Readiness::SourceActive`] when the base stream produced an item within
+    /// [`ForkConfig::idle_after`], and [`Readiness::Idle`] otherwise.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the internal fork lock is poisoned.
+    ///
+    /// [`ForkConfig::idle_after`]: crate::ForkConfig::idle_after
+    #[must_use]
+    pub fn readiness(&self) -> Readiness {
+        self.fork
+            .lock()
+            .expect("Fork lock poisoned during readiness")
+            .readiness(self.id)
+    }
+
+    /// Returns the queue index of the oldest item that some clone of the fork
+    /// has not received or not acknowledged yet, or `None` if no clone is
+    /// behind.
+    ///
+    /// This is where the slowest clone continues. Queued items before it are
+    /// no longer needed and are freed on the next poll.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the internal fork lock is poisoned.
+    #[must_use]
+    pub fn oldest_needed_index(&self) -> Option<usize> {
+        self.fork
+            .lock()
+            .expect("Fork lock poisoned during oldest_needed_index")
+            .oldest_needed_index()
+    }
+
+    /// Returns the position of this clone in the base stream, as an epoch
+    /// to pass to [`CloneStream::items_since_epoch`] later.
+    ///
+    /// The epoch is the number of items of the base stream this clone is
+    /// past, so epochs of different clones of a fork can be compared. Marking
+    /// an epoch does not change the stream.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the internal fork lock is poisoned.
+    ///
+    /// ```rust
+    /// use clone_stream::ForkStream;
+    /// use futures::{StreamExt, executor::block_on, stream};
+    ///
+    /// let mut clone = stream::iter(0..10).fork();
+    /// block_on(clone.next());
+    /// let epoch = clone.mark_epoch();
+    /// block_on(clone.next());
+    /// assert_eq!(clone.items_since_epoch(epoch), 1);
+    /// ```
+    #[must_use]
+    pub fn mark_epoch(&self) -> u64 {
+        self.fork
+            .lock()
+            .expect("Fork lock poisoned during mark_epoch")
+            .epoch(self.id)
+    }
+
+    /// Returns how many items of the base stream this clone received since
+    /// `epoch`, which was returned by [`CloneStream::mark_epoch`] on any clone
+    /// of the fork.
+    ///
+    /// Items this clone missed, for example because they were evicted, count
+    /// as received. Returns zero while this clone has not reached `epoch`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the internal fork lock is poisoned.
+    #[must_use]
+    pub fn items_since_epoch(&self, epoch: u64) -> usize {
+        let now = self
+            .fork
+            .lock()
+            .expect("Fork lock poisoned during items_since_epoch")
+            .epoch(self.id);
+        now.saturating_sub(epoch) as usize
+    }
+
+    /// Returns how many times the clones of the fork polled the base stream
+    /// together.
+    ///
+    /// Each item is polled from the base stream once and queued for the other
+    /// clones, so this grows with the number of items and pending polls, not
+    /// with the number of clones.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the internal fork lock is poisoned.
+    #[must_use]
+    pub fn base_poll_count(&self) -> u64 {
+        self.fork
+            .lock()
+            .expect("Fork lock poisoned during base_poll_count")
+            .base_polls
+    }
+
+    /// Returns the ID of the clone that most recently advanced the base
+    /// stream, or `None` if no clone has received an item from it yet.
+    ///
+    /// The clone that polls first after the base stream wakes receives the
+    /// item directly and queues it for the others. When one clone is always
+    /// ahead, it drives the base stream for all of them.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the internal fork lock is poisoned.
+    #[must_use]
+    pub fn base_stream_driver(&self) -> Option<usize> {
+        self.fork
+            .lock()
+            .expect("Fork lock poisoned during base_stream_driver")
+            .base_stream_driver
+    }
+
+    /// Returns the base-stream polls and queue operations recorded so far by
+    /// the fork, in the order they happened.
+    ///
+    /// The trace is shared by all clones of the fork and is empty unless
+    /// [`ForkConfig::record_poll_order`] was enabled.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the internal fork lock is poisoned.
+    ///
+    /// [`ForkConfig::record_poll_order`]: crate::ForkConfig::record_poll_order
+    #[must_use]
+    pub fn poll_trace(&self) -> Vec<PollEvent> {
+        self.fork
+            .lock()
+            .expect("Fork lock poisoned during poll_trace")
+            .poll_trace
+            .clone()
+            .unwrap_or_default()
+    }
+
+    /// Returns the poll and wakeup counters of the fork, shared by all its
+    /// clones.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the internal fork lock is poisoned.
+    #[cfg(feature = "metrics")]
+    #[must_use]
+    pub fn poll_metrics(&self) -> PollMetrics {
+        self.fork
+            .lock()
+            .expect("Fork lock poisoned during poll_metrics")
+            .poll_counters
+            .snapshot()
+    }
+
+    /// Returns how many times the fork cloned an item, to compare against
+    /// the number of items and spot unexpected cloning.
+    ///
+    /// A single clone receives items without cloning them. Items are cloned
+    /// when they are queued for other clones and when a clone receives a
+    /// queued item that other clones still need. Only counted in debug
+    /// builds with the `metrics` feature.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the internal fork lock is poisoned.
+    #[cfg(all(debug_assertions, feature = "metrics"))]
+    #[must_use]
+    pub fn clone_op_count(&self) -> u64 {
+        self.fork
+            .lock()
+            .expect("Fork lock poisoned during clone_op_count")
+            .poll_counters
+            .item_clones()
+    }
+
+    /// Returns the number of clones currently registered with the fork,
+    /// including this one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the internal fork lock is poisoned.
+    #[must_use]
+    pub fn active_clone_count(&self) -> usize {
+        self.fork
+            .lock()
+            .expect("Fork lock poisoned during active_clone_count")
+            .clone_registry
+            .count()
+    }
+
+    /// Returns how many more clones can be created before the clone limit is
+    /// reached and [`Clone::clone`] panics.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the internal fork lock is poisoned.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use clone_stream::ForkStream;
+    /// use futures::stream;
+    ///
+    /// let stream = stream::iter(0..3).fork_with_limits(100, 3);
+    /// assert_eq!(stream.clones_remaining(), 2);
+    /// ```
+    #[must_use]
+    pub fn clones_remaining(&self) -> usize {
+        self.fork
+            .lock()
+            .expect("Fork lock poisoned during clones_remaining")
+            .clone_registry
+            .remaining()
+    }
+
+    /// Polls for the next item like [`Stream::poll_next`], but tells why the
+    /// clone is pending.
+    ///
+    /// The reason is decided while the fork is locked for the poll. The
+    /// waker of `cx` is registered as for [`Stream::poll_next`] whenever the
+    /// outcome is not an item. Returns [`Poll::Pending`] only when the clone
+    /// could not reach the fork, for example because it was polled from
+    /// inside its own base stream.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the internal fork lock is poisoned.
+    ///
+    /// ```rust
+    /// use std::task::{Context, Poll};
+    ///
+    /// use clone_stream::{ForkStream, PollOutcome};
+    /// use futures::{stream, task::noop_waker_ref};
+    ///
+    /// let mut clone = stream::pending::<u32>().fork();
+    /// let mut cx = Context::from_waker(noop_waker_ref());
+    /// assert_eq!(
+    ///     clone.poll_next_diagnostic(&mut cx),
+    ///     Poll::Ready(PollOutcome::PendingBaseStream)
+    /// );
+    /// ```
+    pub fn poll_next_diagnostic(
+        &mut self,
+        cx: &mut Context,
+    ) -> Poll<PollOutcome<BaseStream::Item>> {
+        if let Some(item) = self.take_pushed_back() {
+            return Poll::Ready(PollOutcome::Item(Some(item)));
+        }
+        let mut pending = None;
+        let poll_result = self.poll_fork(cx, |fork, clone_id, waker| {
+            match fork.poll_clone_outcome(clone_id, waker) {
+                PollOutcome::Item(item) => Poll::Ready(item),
+                outcome => {
+                    pending = Some(outcome);
+                    Poll::Pending
+                }
+            }
+        });
+        match poll_result {
+            Poll::Ready(item) => {
+                if item.is_some() {
+                    self.count_item();
+                }
+                Poll::Ready(PollOutcome::Item(item))
+            }
+            Poll::Pending => pending.map_or(Poll::Pending, Poll::Ready),
+        }
+    }
+
+    /// Polls for the next item and passes a reference to it to `inspect`,
+    /// returning its result.
+    ///
+    /// This advances the clone like [`StreamExt::poll_next_unpin`], but never
+    /// clones an item that stays queued for other clones. `inspect` borrows
+    /// the queued copy instead, which saves a clone when only a part of the
+    /// item is needed. Items pushed back with [`Self::push_back`] are
+    /// inspected first.
+    ///
+    /// ```rust
+    /// use std::task::{Context, Poll};
+    ///
+    /// use clone_stream::ForkStream;
+    /// use futures::{stream, task::noop_waker_ref};
+    ///
+    /// let mut clone = stream::iter([(1, "one")]).fork();
+    /// let mut cx = Context::from_waker(noop_waker_ref());
+    /// let name = clone.with_next(&mut cx, |&(_, name)| name.len());
+    /// assert_eq!(name, Poll::Ready(Some(3)));
+    /// ```
+    pub fn with_next<Output>(
+        &mut self,
+        cx: &mut Context,
+        inspect: impl FnOnce(&BaseStream::Item) -> Output,
+    ) -> Poll<Option<Output>> {
+        if let Some(item) = self.take_pushed_back() {
+            return Poll::Ready(Some(inspect(&item)));
+        }
+        let poll_result = self.poll_fork(cx, |fork, clone_id, waker| {
+            fork.poll_clone_with(clone_id, waker, inspect)
+        });
+        if matches!(poll_result, Poll::Ready(Some(_))) {
+            self.count_item();
+        }
+        poll_result
+    }
+
+    /// Returns a future of the next item that can be used directly in
+    /// [`futures::select!`], which needs futures to be [`FusedFuture`].
+    ///
+    /// Once it has completed, the future reports itself as terminated, so a
+    /// `select!` that polls it again takes its `complete` branch instead.
+    ///
+    /// ```rust
+    /// use clone_stream::ForkStream;
+    /// use futures::{executor::block_on, select, stream};
+    ///
+    /// let mut clone = stream::iter([1]).fork();
+    /// let item = block_on(async {
+    ///     select! {
+    ///         item = clone.next_fused() => item,
+    ///     }
+    /// });
+    /// assert_eq!(item, Some(1));
+    /// ```
+    pub fn next_fused(
+        &mut self,
+    ) -> impl FusedFuture<Output = Option<BaseStream::Item>> + Unpin + '_ {
+        self.next().fuse()
+    }
+
+    /// Returns the next item together with a token to acknowledge it.
+    ///
+    /// The item stays queued until the token is acknowledged with
+    /// [`AckToken::ack`] or dropped, even after every clone has received it,
+    /// so it can still be inspected in the queue while it is being
+    /// processed. An item received by several clones with this method is
+    /// removed only after all of them have acknowledged it. Items received
+    /// with [`StreamExt::next`] need no acknowledgement. A clone created
+    /// while an item waits for acknowledgement receives it as well, so a
+    /// consumer that crashed before acknowledging can be replaced.
+    ///
+    /// ```rust
+    /// use clone_stream::ForkStream;
+    /// use futures::{executor::block_on, stream};
+    ///
+    /// let mut clone = stream::iter([1]).fork();
+    /// let (item, token) = block_on(clone.next_with_ack()).unwrap();
+    /// assert_eq!(item, 1);
+    /// token.ack();
+    /// ```
+    pub fn next_with_ack(
+        &mut self,
+    ) -> impl Future<Output = Option<(BaseStream::Item, AckToken<BaseStream>)>> + '_ {
+        future::poll_fn(move |cx| {
+            let fork = Arc::downgrade(&self.fork);
+            if let Some(item) = self.take_pushed_back() {
+                return Poll::Ready(Some((item, AckToken::new(fork, None))));
+            }
+            let poll_result = self.poll_fork(cx, Fork::poll_clone_acked);
+            if matches!(poll_result, Poll::Ready(Some(_))) {
+                self.count_item();
+            }
+            poll_result.map(|received| {
+                received.map(|(item, sequence)| (item, AckToken::new(fork, sequence)))
+            })
+        })
+    }
+
+    /// Takes the next item if one is available right now, without waiting.
+    ///
+    /// This polls the clone once with a no-op waker, so it does not register
+    /// the current task for a wakeup.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TryRecvError::Empty`] when no item is available yet and
+    /// [`TryRecvError::Disconnected`] when the base stream has ended.
+    ///
+    /// ```rust
+    /// use clone_stream::{ForkStream, TryRecvError};
+    /// use futures::stream;
+    ///
+    /// let mut clone = stream::iter([1]).fork();
+    /// assert_eq!(clone.try_recv(), Ok(1));
+    /// assert_eq!(clone.try_recv(), Err(TryRecvError::Disconnected));
+    /// ```
+    pub fn try_recv(&mut self) -> Result<BaseStream::Item, TryRecvError> {
+        match self.poll_next_unpin(&mut Context::from_waker(noop_waker_ref())) {
+            Poll::Ready(Some(item)) => Ok(item),
+            Poll::Ready(None) => Err(TryRecvError::Disconnected),
+            Poll::Pending => Err(TryRecvError::Empty),
+        }
+    }
+
+    /// Polls every clone once without waiting and returns what each of them
+    /// yielded, in the same order.
+    ///
+    /// A clone without an item that is ready right now, or whose stream has
+    /// ended, yields `None`. Like [`CloneStream::try_recv`], this does not
+    /// register the current task for a wakeup. Useful as a building block for
+    /// custom schedulers.
+    ///
+    /// With [`ForkConfig::scheduler_seed`](crate::ForkConfig::scheduler_seed)
+    /// set on the fork of the first clone,
+    /// the clones are polled in a pseudo-random order determined by the seed,
+    /// but the results are still returned in the order of `clones`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the internal fork lock is poisoned.
+    ///
+    /// ```rust
+    /// use clone_stream::{CloneStream, ForkConfig, ForkStream};
+    /// use futures::stream;
+    ///
+    /// let config = ForkConfig::delivering_to_idle_clones();
+    /// let first = stream::iter(0..2).fork_with_config(config);
+    /// let mut clones = [first.clone(), first];
+    /// assert_eq!(
+    ///     CloneStream::poll_all_ready(&mut clones),
+    ///     vec![Some(0), Some(0)]
+    /// );
+    /// ```
+    pub fn poll_all_ready(clones: &mut [Self]) -> Vec<Option<BaseStream::Item>> {
+        let order = clones.first().and_then(|first| {
+            first
+                .fork
+                .lock()
+                .expect("Fork lock poisoned during poll_all_ready")
+                .scheduler
+                .as_mut()
+                .map(|scheduler| scheduler.poll_order(clones.len()))
+        });
+        let Some(order) = order else {
+            return clones
+                .iter_mut()
+                .map(|clone| clone.try_recv().ok())
+                .collect();
+        };
+        let mut results: Vec<_> = iter::repeat_with(|| None).take(clones.len()).collect();
+        for index in order {
+            results[index] = clones[index].try_recv().ok();
+        }
+        results
+    }
+
+    /// Creates `n` new clones together with a [`Barrier`] for `n` parties.
+    ///
+    /// Each consumer can await [`Barrier::wait`] to rendezvous with the
+    /// others at a common point in their consumption before continuing.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the clone limit is exceeded or the internal fork lock is
+    /// poisoned.
+    ///
+    /// ```rust
+    /// use clone_stream::ForkStream;
+    /// use futures::stream;
+    ///
+    /// let stream = stream::iter(0..3).fork();
+    /// let (clones, barrier) = stream.split_synced(2);
+    /// assert_eq!(clones.len(), 2);
+    /// ```
+    #[must_use]
+    pub fn split_synced(&self, n: usize) -> (Vec<Self>, Barrier) {
+        let clones = (0..n).map(|_| self.clone()).collect();
+        (clones, Barrier::new(n))
+    }
+
+    /// Creates a [`ForkGroup`] of `n` new clones that are consumed together.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the clone limit is exceeded or the internal fork lock is
+    /// poisoned.
+    ///
+    /// ```rust
+    /// use clone_stream::{ForkConfig, ForkStream};
+    /// use futures::{executor::block_on, stream};
+    ///
+    /// let config = ForkConfig::delivering_to_idle_clones();
+    /// let mut group = stream::iter(0..2).fork_with_config(config).group(2);
+    /// assert_eq!(block_on(group.drain_all()), vec![vec![0, 1], vec![0, 1]]);
+    /// ```
+    #[must_use]
+    pub fn group(&self, n: usize) -> ForkGroup<BaseStream> {
+        ForkGroup::new((0..n).map(|_| self.clone()).collect())
+    }
+
+    /// Moves every clone back to the position of the slowest one among them,
+    /// so they all receive the same items from there on.
+    ///
+    /// Faster clones receive the items they already had again, starting right
+    /// after the last item the slowest clone received. This creates a
+    /// consistent checkpoint across consumers.
+    ///
+    /// Only items that are still queued can be received again. Items that
+    /// were evicted because the queue was full, or that no other clone needed
+    /// when they were produced, are not replayed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the clones do not all belong to the same fork, or if the
+    /// internal fork lock is poisoned.
+    ///
+    /// ```rust
+    /// use clone_stream::{CloneStream, ForkConfig, ForkStream};
+    /// use futures::{StreamExt, executor::block_on, stream};
+    ///
+    /// let config = ForkConfig::delivering_to_idle_clones();
+    /// let mut fast = stream::iter(0..3).fork_with_config(config);
+    /// let slow = fast.clone();
+    /// assert_eq!(block_on(fast.next()), Some(0));
+    ///
+    /// let clones = [fast, slow];
+    /// CloneStream::align_to_slowest(&clones);
+    /// let [fast, _slow] = clones;
+    /// assert_eq!(block_on(fast.collect::<Vec<_>>()), vec![0, 1, 2]);
+    /// ```
+    pub fn align_to_slowest(clones: &[Self]) {
+        let Some(first) = clones.first() else {
+            return;
+        };
+        assert!(
+            clones
+                .iter()
+                .all(|clone| Arc::ptr_eq(&clone.fork, &first.fork)),
+            "Only clones of the same fork can be aligned"
+        );
+        let clone_ids: Vec<_> = clones.iter().map(|clone| clone.id).collect();
+        first
+            .fork
+            .lock()
+            .expect("Fork lock poisoned during align_to_slowest")
+            .align_to_slowest(&clone_ids);
+    }
+
+    /// Checks whether this clone and `other` have received the same items so
+    /// far, to assert that clones advance in lockstep.
+    ///
+    /// Clones of different forks are never at the same position.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the internal fork lock is poisoned.
+    ///
+    /// ```rust
+    /// use clone_stream::{ForkConfig, ForkStream};
+    /// use futures::{StreamExt, executor::block_on, stream};
+    ///
+    /// let config = ForkConfig::delivering_to_idle_clones();
+    /// let mut first = stream::iter(0..3).fork_with_config(config);
+    /// let mut second = first.clone();
+    /// block_on(first.next());
+    /// assert!(!first.same_position(&second));
+    /// block_on(second.next());
+    /// assert!(first.same_position(&second));
+    /// ```
+    #[must_use]
+    pub fn same_position(&self, other: &Self) -> bool {
+        if !Arc::ptr_eq(&self.fork, &other.fork) {
+            return false;
+        }
+        self.fork
+            .lock()
+            .expect("Fork lock poisoned during same_position")
+            .same_position(self.id, other.id)
+    }
+
+    /// Takes every item that is available right now, without waiting.
+    ///
+    /// Stops at the first item that is not ready yet or at the end of the
+    /// stream. Like [`CloneStream::try_recv`], this does not register the
+    /// current task for a wakeup.
+    ///
+    /// ```rust
+    /// use clone_stream::ForkStream;
+    /// use futures::stream;
+    ///
+    /// let mut clone = stream::iter(0..3).fork();
+    /// assert_eq!(clone.drain_ready(), vec![0, 1, 2]);
+    /// ```
+    pub fn drain_ready(&mut self) -> Vec<BaseStream::Item> {
+        iter::from_fn(|| self.try_recv().ok()).collect()
+    }
+
+    /// Calls `observer` on every item this clone yields, before passing it
+    /// on.
+    ///
+    /// Other clones of the same fork are not observed.
+    ///
+    /// ```rust
+    /// use clone_stream::ForkStream;
+    /// use futures::{StreamExt, executor::block_on, stream};
+    ///
+    /// let mut seen = 0;
+    /// let stream = stream::iter(0..3).fork().observe(|_| seen += 1);
+    /// let items: Vec<_> = block_on(stream.collect());
+    /// assert_eq!(items, vec![0, 1, 2]);
+    /// assert_eq!(seen, 3);
+    /// ```
+    pub fn observe<Observer>(self, observer: Observer) -> impl Stream<Item = BaseStream::Item>
+    where
+        Observer: FnMut(&BaseStream::Item),
+    {
+        self.inspect(observer)
+    }
+
+    /// Counts the items this clone will still receive, without consuming
+    /// them.
+    ///
+    /// This polls the base stream until it ends and queues all its items, so
+    /// it only completes for finite streams. It keeps polling beyond
+    /// [`ForkConfig::high_water`](crate::ForkConfig::high_water), but items
+    /// beyond [`ForkConfig::max_queue_size`](crate::ForkConfig::max_queue_size)
+    /// are evicted from the queue and not counted. The result is what
+    /// [`CloneStream::len`] returns afterwards, including pushed back and
+    /// replayed items. Like a poll of this clone, it stays pending when called
+    /// from inside the base stream of its own fork.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the internal fork lock is poisoned.
+    ///
+    /// ```rust
+    /// use clone_stream::ForkStream;
+    /// use futures::{StreamExt, executor::block_on, stream};
+    ///
+    /// let mut clone = stream::iter(0..3).fork();
+    /// assert_eq!(block_on(clone.count_remaining()), 3);
+    /// assert_eq!(block_on(clone.collect::<Vec<_>>()), vec![0, 1, 2]);
+    /// ```
+    pub async fn count_remaining(&self) -> usize {
+        future::poll_fn(|cx| {
+            let Some(_guard) = PollingGuard::enter(Arc::as_ptr(&self.fork).addr()) else {
+                error!(
+                    "Clone {} counted its items from inside the base stream of its own fork. \
+                     Returning pending instead of deadlocking.",
+                    self.id
+                );
+                return Poll::Pending;
+            };
+            let mut fork = self
+                .fork
+                .lock()
+                .expect("Fork lock poisoned during count_remaining");
+            ready!(fork.poll_buffer_to_end(self.id, cx.waker()));
+            Poll::Ready(self.remaining_len(&fork))
+        })
+        .await
+    }
+
+    /// Returns exactly how many items this clone will still receive, once the
+    /// base stream has ended, or `None` while it may still produce items.
+    ///
+    /// Unlike [`Stream::size_hint`], this is exact, because every remaining
+    /// item is queued by then. See [`CloneStream::count_remaining`] to pump
+    /// the base stream to its end first.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the internal fork lock is poisoned.
+    ///
+    /// ```rust
+    /// use clone_stream::ForkStream;
+    /// use futures::{StreamExt, executor::block_on, stream};
+    ///
+    /// let mut clone = stream::iter(0..3).fork();
+    /// assert_eq!(clone.len(), None);
+    /// block_on(clone.count_remaining());
+    /// assert_eq!(clone.len(), Some(3));
+    /// ```
+    #[must_use]
+    pub fn len(&self) -> Option<usize> {
+        let fork = self.fork.lock().expect("Fork lock poisoned during len");
+        fork.base_exhausted.then(|| self.remaining_len(&fork))
+    }
+
+    /// Counts the pushed back, replayed and queued items this clone will
+    /// still receive.
+    fn remaining_len(&self, fork: &Fork<BaseStream>) -> usize {
+        self.pushed_back.len() + fork.remaining_items(self.id)
+    }
+
+    /// Checks whether this clone will not receive any more items, once the
+    /// base stream has ended, like [`CloneStream::len`].
+    #[must_use]
+    pub fn is_empty(&self) -> Option<bool> {
+        self.len().map(|len| len == 0)
+    }
+
+    /// Waits until at least `n` items are queued for this clone.
+    ///
+    /// Items are only queued when another clone polls them from the base
+    /// stream, so this does not poll the base stream itself. While it waits,
+    /// this clone receives the items other clones poll, even though it is not
+    /// polled itself. Useful in tests to wait for buffering without sleeping.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the internal fork lock is poisoned.
+    pub fn wait_until_buffered(&self, n: usize) -> impl Future<Output = ()> + '_ {
+        let mut buffer_wait = None;
+        future::poll_fn(move |cx| {
+            let mut fork = self
+                .fork
+                .lock()
+                .expect("Fork lock poisoned during wait_until_buffered");
+            if fork.remaining_queued_items(self.id) >= n {
+                return Poll::Ready(());
+            }
+            if buffer_wait.is_none() {
+                fork.start_buffer_wait(self.id);
+                buffer_wait = Some(BufferWait {
+                    fork: &self.fork,
+                    clone_id: self.id,
+                });
+            }
+            fork.wake_on_enqueue(cx.waker());
+            Poll::Pending
+        })
+    }
+
+    /// Turns this clone into a stream that only yields the most recent item.
+    ///
+    /// Every poll takes the items already queued for this clone, polls the
+    /// base stream at most once more, and yields only the newest of them,
+    /// discarding the older ones for this clone. Other clones still receive
+    /// every item. A base stream that is always ready yields one new item per
+    /// poll.
+    ///
+    /// # Panics
+    ///
+    /// The returned stream panics if the internal fork lock is poisoned.
+    ///
+    /// ```rust
+    /// use clone_stream::{ForkConfig, ForkStream};
+    /// use futures::{StreamExt, executor::block_on, stream};
+    ///
+    /// let config = ForkConfig::delivering_to_idle_clones();
+    /// let mut every = stream::iter(0..5).fork_with_config(config);
+    /// let mut latest = every.clone().latest_only();
+    /// block_on(async {
+    ///     assert_eq!(every.next().await, Some(0));
+    ///     assert_eq!(every.next().await, Some(1));
+    ///     assert_eq!(latest.next().await, Some(2));
+    /// });
+    /// ```
+    pub fn latest_only(mut self) -> impl Stream<Item = BaseStream::Item> {
+        let mut ended = false;
+        stream::poll_fn(move |cx| {
+            if ended {
+                return Poll::Ready(None);
+            }
+            let n_queued = {
+                let fork = self
+                    .fork
+                    .lock()
+                    .expect("Fork lock poisoned during latest_only");
+                self.remaining_len(&fork)
+            };
+            let mut latest = None;
+            for _ in 0..=n_queued {
+                match self.poll_next_unpin(cx) {
+                    Poll::Ready(Some(item)) => latest = Some(item),
+                    Poll::Ready(None) => {
+                        ended = true;
+                        return Poll::Ready(latest);
+                    }
+                    Poll::Pending => break,
+                }
+            }
+            latest.map_or(Poll::Pending, |item| Poll::Ready(Some(item)))
+        })
+    }
+
+    /// Turns this clone into a stream that only yields the items for which
+    /// `keep` returns `true`.
+    ///
+    /// Unlike filtering before forking, every clone can filter differently
+    /// while sharing one queue. Skipped items still move this clone past
+    /// them, so they are not held back for it, and they are never cloned for
+    /// it. Kept items are only cloned while other clones still need them, like
+    /// with [`StreamExt::next`]. Only the yielded items count towards the limit
+    /// of [`Self::clone_for`]. Pushed back items for which `keep` returns
+    /// `false` are discarded.
+    ///
+    /// ```rust
+    /// use clone_stream::ForkStream;
+    /// use futures::{StreamExt, executor::block_on, stream};
+    ///
+    /// let evens = stream::iter(0..5)
+    ///     .fork()
+    ///     .filtered_view(|item| item % 2 == 0);
+    /// assert_eq!(block_on(evens.collect::<Vec<_>>()), vec![0, 2, 4]);
+    /// ```
+    pub fn filtered_view<Keep>(mut self, keep: Keep) -> impl Stream<Item = BaseStream::Item>
+    where
+        Keep: Fn(&BaseStream::Item) -> bool,
+    {
+        stream::poll_fn(move |cx| {
+            while let Some(item) = self.take_pushed_back() {
+                if keep(&item) {
+                    return Poll::Ready(Some(item));
+                }
+            }
+            loop {
+                let kept = self.poll_fork(cx, |fork, clone_id, waker| {
+                    fork.poll_clone_filtered(clone_id, waker, &keep)
+                });
+                match ready!(kept) {
+                    Some(Some(item)) => {
+                        self.count_item();
+                        return Poll::Ready(Some(item));
+                    }
+                    Some(None) => {}
+                    None => return Poll::Ready(None),
+                }
+            }
+        })
+    }
+
+    /// Waits for the next `N` items and returns them as an array.
+    ///
+    /// Returns `None` when the stream ends before `N` items were received. The
+    /// items of such a partial chunk are lost; use [`StreamExt::chunks`] to
+    /// also receive the last, partial chunk.
+    ///
+    /// ```rust
+    /// use clone_stream::ForkStream;
+    /// use futures::{executor::block_on, stream};
+    ///
+    /// let mut clone = stream::iter(0..5).fork();
+    /// assert_eq!(block_on(clone.next_chunk::<3>()), Some([0, 1, 2]));
+    /// assert_eq!(block_on(clone.next_chunk::<3>()), None);
+    /// ```
+    pub async fn next_chunk<const N: usize>(&mut self) -> Option<[BaseStream::Item; N]> {
+        let mut chunk = Vec::with_capacity(N);
+        while chunk.len() < N {
+            chunk.push(self.next().await?);
+        }
+        chunk.try_into().ok()
+    }
+
+    /// Sends every remaining item of this clone into `sink`, waiting whenever
+    /// the sink is not ready, and closes the sink at the end.
+    ///
+    /// Other clones keep consuming the base stream independently.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error of the sink.
+    ///
+    /// ```rust
+    /// use clone_stream::ForkStream;
+    /// use futures::{StreamExt, channel::mpsc, executor::block_on, stream};
+    ///
+    /// let (sender, receiver) = mpsc::unbounded();
+    /// block_on(stream::iter(0..3).fork().forward_to(sender)).unwrap();
+    /// assert_eq!(block_on(receiver.collect::<Vec<_>>()), vec![0, 1, 2]);
+    /// ```
+    pub async fn forward_to<Output>(self, sink: Output) -> Result<(), Output::Error>
+    where
+        Output: Sink<BaseStream::Item>,
+    {
+        self.map(Ok).forward(sink).await
+    }
+
+    /// Drains this clone into `out`, appending every remaining item.
+    ///
+    /// Unlike [`StreamExt::collect`], this reuses the allocation of `out`.
+    ///
+    /// ```rust
+    /// use clone_stream::ForkStream;
+    /// use futures::{executor::block_on, stream};
+    ///
+    /// let mut items = Vec::with_capacity(3);
+    /// block_on(stream::iter(0..3).fork().collect_into(&mut items));
+    /// assert_eq!(items, vec![0, 1, 2]);
+    /// ```
+    pub async fn collect_into(mut self, out: &mut Vec<BaseStream::Item>) {
+        while let Some(item) = self.next().await {
+            out.push(item);
+        }
+    }
+
+    /// Forwards the items of this clone into a new
+    /// [`tokio::sync::broadcast`] channel with room for `capacity` items.
+    ///
+    /// Returns the [`BroadcastBridge`] that pumps the items, which has to be
+    /// spawned or awaited, and a receiver of the channel.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is zero.
+    ///
+    /// ```rust
+    /// use clone_stream::ForkStream;
+    /// use futures::stream;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let (bridge, mut receiver) = stream::iter(0..3).fork().into_broadcast(4);
+    /// bridge.await;
+    /// assert_eq!(receiver.recv().await, Ok(0));
+    /// # }
+    /// ```
+    #[cfg(feature = "broadcast")]
+    pub fn into_broadcast(
+        self,
+        capacity: usize,
+    ) -> (
+        BroadcastBridge<BaseStream>,
+        tokio::sync::broadcast::Receiver<BaseStream::Item>,
+    ) {
+        BroadcastBridge::new(self, capacity)
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl<BaseStream> CloneStream<BaseStream>
+where
+    BaseStream: Stream<Item = Bytes>,
+{
+    /// Returns the total length of the byte chunks queued for this clone.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the internal fork lock is poisoned.
+    #[must_use]
+    pub fn buffered_bytes(&self) -> usize {
+        self.fork
+            .lock()
+            .expect("Fork lock poisoned during buffered_bytes")
+            .unseen_items(self.id)
+            .map(Bytes::len)
+            .sum()
     }
 }
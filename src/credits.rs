@@ -0,0 +1,54 @@
+use std::{collections::BTreeMap, task::Waker};
+
+/// Number of items each clone may still receive.
+///
+/// Set up by
+/// [`ForkStream::fork_with_credits`](crate::ForkStream::fork_with_credits).
+#[derive(Default)]
+pub(crate) struct Credits {
+    balances: BTreeMap<usize, usize>,
+    /// Wakers of clones that were polled without credits.
+    waiting: BTreeMap<usize, Waker>,
+}
+
+impl Credits {
+    /// Uses one credit of `clone_id`, or remembers `waker` to wake once more
+    /// credits are granted if it has none left.
+    pub(crate) fn try_spend(&mut self, clone_id: usize, waker: &Waker) -> bool {
+        match self.balances.get_mut(&clone_id) {
+            Some(balance) if *balance > 0 => {
+                *balance -= 1;
+                true
+            }
+            _ => {
+                self.waiting.insert(clone_id, waker.clone());
+                false
+            }
+        }
+    }
+
+    /// Gives back a credit that was spent on a poll that returned nothing.
+    pub(crate) fn refund(&mut self, clone_id: usize) {
+        *self.balances.entry(clone_id).or_default() += 1;
+    }
+
+    pub(crate) fn grant(&mut self, clone_id: usize, credits: usize) {
+        if credits == 0 {
+            return;
+        }
+        let balance = self.balances.entry(clone_id).or_default();
+        *balance = balance.saturating_add(credits);
+        if let Some(waker) = self.waiting.remove(&clone_id) {
+            waker.wake();
+        }
+    }
+
+    pub(crate) fn balance(&self, clone_id: usize) -> usize {
+        self.balances.get(&clone_id).copied().unwrap_or_default()
+    }
+
+    pub(crate) fn forget(&mut self, clone_id: usize) {
+        self.balances.remove(&clone_id);
+        self.waiting.remove(&clone_id);
+    }
+}
@@ -0,0 +1,68 @@
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use futures::{Stream, StreamExt, ready};
+
+use crate::CloneStream;
+
+/// A clone that skips items equal to the item it yielded just before.
+///
+/// Created with [`ForkStream::fork_dedup`](crate::ForkStream::fork_dedup).
+/// Deduplication happens per clone, so clones that started at different
+/// positions may skip different items.
+pub struct DedupCloneStream<BaseStream>
+where
+    BaseStream: Stream<Item: Clone>,
+{
+    clone: CloneStream<BaseStream>,
+    /// Item this clone yielded last.
+    previous: Option<BaseStream::Item>,
+}
+
+// The previous item is never pinned.
+impl<BaseStream> Unpin for DedupCloneStream<BaseStream> where BaseStream: Stream<Item: Clone> {}
+
+impl<BaseStream> DedupCloneStream<BaseStream>
+where
+    BaseStream: Stream<Item: Clone>,
+{
+    pub(crate) fn new(clone: CloneStream<BaseStream>) -> Self {
+        Self {
+            clone,
+            previous: None,
+        }
+    }
+}
+
+impl<BaseStream> Clone for DedupCloneStream<BaseStream>
+where
+    BaseStream: Stream<Item: Clone>,
+{
+    /// Creates a new clone that yields its first item even if it equals the
+    /// item this clone yielded last.
+    fn clone(&self) -> Self {
+        Self::new(self.clone.clone())
+    }
+}
+
+impl<BaseStream> Stream for DedupCloneStream<BaseStream>
+where
+    BaseStream: Stream<Item: Clone + PartialEq>,
+{
+    type Item = BaseStream::Item;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            match ready!(self.clone.poll_next_unpin(cx)) {
+                Some(item) if self.previous.as_ref() == Some(&item) => {}
+                Some(item) => {
+                    self.previous = Some(item.clone());
+                    return Poll::Ready(Some(item));
+                }
+                None => return Poll::Ready(None),
+            }
+        }
+    }
+}
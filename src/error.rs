@@ -17,6 +17,14 @@ pub enum CloneStreamError {
     CloneAlreadyActive {
         clone_id: usize,
     },
+    /// The base stream has already ended
+    BaseStreamEnded,
+    /// Another clone would push the estimated memory of queued items over
+    /// the limit
+    MemoryLimitExceeded {
+        estimated_bytes: usize,
+        max_bytes: usize,
+    },
 }
 
 impl fmt::Display for CloneStreamError {
@@ -27,14 +35,34 @@ impl fmt::Display for CloneStreamError {
                 current_count,
             } => write!(
                 f,
-                "Maximum number of clones exceeded: {current_count} >= {max_allowed}"
+                "Maximum number of clones exceeded: {current_count} >= {max_allowed}. Drop unused \
+                 clones or increase `max_clone_count` in `ForkConfig`"
             ),
             CloneStreamError::InvalidCloneId { clone_id } => {
-                write!(f, "Invalid clone ID: {clone_id}")
+                write!(
+                    f,
+                    "Invalid clone ID: {clone_id}. The clone was never registered with this fork"
+                )
             }
             CloneStreamError::CloneAlreadyActive { clone_id } => {
-                write!(f, "Clone {clone_id} is already active")
+                write!(
+                    f,
+                    "Clone {clone_id} is already active. Its state was restored twice, which is a \
+                     bug in clone-stream"
+                )
+            }
+            CloneStreamError::BaseStreamEnded => {
+                write!(f, "The base stream has already ended, so no items can be added")
             }
+            CloneStreamError::MemoryLimitExceeded {
+                estimated_bytes,
+                max_bytes,
+            } => write!(
+                f,
+                "Another clone would raise the estimated memory of queued items to \
+                 {estimated_bytes} bytes, over the limit of {max_bytes}. Let lagging clones catch \
+                 up or increase `max_total_memory_bytes` in `ForkConfig`"
+            ),
         }
     }
 }
@@ -42,3 +70,109 @@ impl fmt::Display for CloneStreamError {
 impl std::error::Error for CloneStreamError {}
 
 pub type Result<T> = std::result::Result<T, CloneStreamError>;
+
+/// Reasons why [`CloneStream::try_recv`](crate::CloneStream::try_recv) did not
+/// return an item.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TryRecvError {
+    /// No item is available right now, but more may arrive later.
+    Empty,
+    /// The base stream has ended and this clone has received every item.
+    Disconnected,
+}
+
+impl fmt::Display for TryRecvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TryRecvError::Empty => write!(f, "No item available yet"),
+            TryRecvError::Disconnected => write!(f, "Base stream has ended"),
+        }
+    }
+}
+
+impl std::error::Error for TryRecvError {}
+
+/// Error yielded by a clone created with
+/// [`ForkStream::fork_lossy_reporting`](crate::ForkStream::fork_lossy_reporting)
+/// that fell behind and missed items evicted from the full queue.
+///
+/// Holds the number of missed items. The clone resumes with the oldest item
+/// that is still queued.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Lagged(pub u64);
+
+impl fmt::Display for Lagged {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Clone lagged behind and missed {} items. Poll it more often or increase \
+             `max_queue_size` in `ForkConfig`",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for Lagged {}
+
+/// Problems found by [`ForkConfig::validate`](crate::ForkConfig::validate).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigError {
+    /// `max_clone_count` is zero, so not even the first clone can be created.
+    ZeroCloneCount,
+    /// `max_queue_size` is zero, so clones miss every item polled by another
+    /// clone.
+    ZeroQueueSize,
+    /// `initial_queue_capacity` exceeds `max_queue_size`, so part of the
+    /// preallocated storage can never be used.
+    InitialCapacityExceedsMax {
+        initial_capacity: usize,
+        max_queue_size: usize,
+    },
+    /// `low_water` is not below `high_water`, so polling the base stream
+    /// never resumes after it stopped.
+    LowWaterNotBelowHighWater { low_water: usize, high_water: usize },
+    /// `high_water` exceeds `max_queue_size`, so the queue never grows large
+    /// enough to stop polling the base stream.
+    HighWaterExceedsMaxQueueSize {
+        high_water: usize,
+        max_queue_size: usize,
+    },
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::ZeroCloneCount => {
+                write!(f, "`max_clone_count` is zero, so no clone can be created")
+            }
+            ConfigError::ZeroQueueSize => write!(
+                f,
+                "`max_queue_size` is zero, so clones miss every item polled by another clone"
+            ),
+            ConfigError::InitialCapacityExceedsMax {
+                initial_capacity,
+                max_queue_size,
+            } => write!(
+                f,
+                "`initial_queue_capacity` {initial_capacity} exceeds `max_queue_size` \
+                 {max_queue_size}"
+            ),
+            ConfigError::LowWaterNotBelowHighWater {
+                low_water,
+                high_water,
+            } => write!(
+                f,
+                "`low_water` {low_water} is not below `high_water` {high_water}"
+            ),
+            ConfigError::HighWaterExceedsMaxQueueSize {
+                high_water,
+                max_queue_size,
+            } => write!(
+                f,
+                "`high_water` {high_water} exceeds `max_queue_size` {max_queue_size}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
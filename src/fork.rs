@@ -1,15 +1,32 @@
-use core::ops::Deref;
 use std::{
-    iter,
+    any::Any,
+    collections::{BTreeMap, VecDeque},
+    mem,
     pin::Pin,
-    sync::Arc,
-    task::{Poll, Wake, Waker},
+    sync::{Arc, Mutex},
+    task::{Context, Poll, Wake, Waker, ready},
+    time::{Duration, Instant},
 };
 
-use futures::Stream;
+use futures::{Stream, StreamExt};
 use log::{debug, trace, warn};
 
-use crate::{registry::CloneRegistry, ring_queue::RingQueue};
+use crate::{
+    ack::Acks,
+    clock::{Clock, SystemClock},
+    credits::Credits,
+    error::{CloneStreamError, ConfigError, Result},
+    metrics::PollCounters,
+    registry::CloneRegistry,
+    replay::Replay,
+    ring_queue::{QueuePosition, RingQueue, is_behind},
+    scheduler::Scheduler,
+    sequence::SequenceTracker,
+    shard::Router,
+    states::{CloneState, Delivery},
+    trace::{PollEvent, PollEventKind, PollOutcome, Readiness},
+    waker_set::WakerSet,
+};
 
 /// Maximum number of clones that can be registered simultaneously.
 const MAX_CLONE_COUNT: usize = 65536;
@@ -17,12 +34,246 @@ const MAX_CLONE_COUNT: usize = 65536;
 /// Maximum number of items that can be queued simultaneously.
 const MAX_QUEUE_SIZE: usize = 1024 * 1024;
 
+/// Time without new items after which the base stream is considered idle.
+const IDLE_AFTER: Duration = Duration::from_secs(1);
+
+/// Settings of a fork, passed to
+/// [`ForkStream::fork_with_config`](crate::ForkStream::fork_with_config).
+///
+/// Every option that is a plain value is a field of this struct. Options
+/// that are closures or shared objects, such as
+/// [`CloneStream::with_eviction_filter`](crate::CloneStream::with_eviction_filter),
+/// are attached to the forked stream with its `with_*` methods instead, so
+/// every option combines with every other.
 #[derive(Debug, Clone, Copy)]
+#[allow(clippy::struct_excessive_bools)]
 pub struct ForkConfig {
     /// Maximum number of clones allowed.
     pub max_clone_count: usize,
     /// Maximum queue size before panic.
     pub max_queue_size: usize,
+    /// Number of queued items to allocate storage for up front.
+    ///
+    /// The queue grows beyond this as needed. Items are only evicted once
+    /// [`ForkConfig::max_queue_size`] is reached.
+    pub initial_queue_capacity: usize,
+    /// Record every base-stream poll and queue operation, retrievable with
+    /// [`CloneStream::poll_trace`](crate::CloneStream::poll_trace).
+    ///
+    /// The trace grows without bound, so only enable this while debugging.
+    pub record_poll_order: bool,
+    /// When the base stream wakes up, only wake the clones that are still
+    /// waiting on it at that moment.
+    ///
+    /// By default, the base stream wakes every clone that was waiting when it
+    /// was last polled, even if some of them have been woken or satisfied from
+    /// the queue since.
+    pub selective_wakeups: bool,
+    /// Reclaim queued items that no clone needs anymore as soon as a clone is
+    /// dropped.
+    ///
+    /// Reclaiming visits every queued item for every clone. When disabled,
+    /// drops only mark the queue for cleanup and the next poll of any clone
+    /// reclaims the items once for all drops since, which is cheaper when
+    /// many short-lived clones come and go.
+    pub eager_drop_cleanup: bool,
+    /// How long after its last item the base stream is considered idle by
+    /// [`CloneStream::readiness`](crate::CloneStream::readiness).
+    pub idle_after: Duration,
+    /// How long an item stays queued before it is evicted, even for clones
+    /// that have not received it yet.
+    ///
+    /// Such clones move on to the next queued item, and
+    /// [`CloneStream::n_expired_items`](crate::CloneStream::n_expired_items)
+    /// counts what they missed. Time is read from the clock of the fork.
+    pub item_ttl: Option<Duration>,
+    /// Number of queued items beyond which the base stream wakes the clone
+    /// that has received the fewest items first, so it can drain the queue.
+    ///
+    /// Below it, clones are woken in no particular order. Does not apply with
+    /// [`ForkConfig::selective_wakeups`].
+    pub memory_pressure_threshold: Option<usize>,
+    /// Number of queued items a clone may fall behind before a warning
+    /// naming the clone and its lag is logged.
+    ///
+    /// The warning is logged when a clone crosses the threshold, not again
+    /// for every further item. Checking visits every queued item for every
+    /// clone on each new item.
+    pub slow_clone_warn_threshold: Option<usize>,
+    /// Seed for the order in which
+    /// [`CloneStream::poll_all_ready`](crate::CloneStream::poll_all_ready)
+    /// polls clones, which decides the clone that drives the base stream.
+    ///
+    /// Without a seed, clones are polled in the order they are passed in.
+    /// The same seed reproduces the same interleaving, which helps to test
+    /// fairness.
+    pub scheduler_seed: Option<u64>,
+    /// Estimated number of bytes the queued items may take up for all
+    /// clones together before creating another clone fails.
+    ///
+    /// A new clone receives every queued item, so creating it adds the
+    /// whole queue to the estimate. Items count as [`size_of`] their type,
+    /// unless estimated with
+    /// [`CloneStream::with_item_size`](crate::CloneStream::with_item_size).
+    pub max_total_memory_bytes: Option<usize>,
+    /// Number of times in a row the base stream may return pending before a
+    /// warning is logged.
+    ///
+    /// A base stream that returns pending without ever waking the waker it
+    /// was given leaves its clones waiting forever. Such a stream is usually
+    /// only polled again when other tasks happen to poll the clones, so many
+    /// pending results in a row point at it. The warning is logged once per
+    /// run of pending results.
+    pub max_consecutive_pending: Option<usize>,
+    /// Number of items queued for the slowest clone at which the fork stops
+    /// polling the base stream.
+    ///
+    /// Clones that need a new item stay pending while the base stream keeps
+    /// its items, so a bounded channel as base stream fills up and slows down
+    /// its producer. Polling resumes once the slowest clone has caught up to
+    /// [`ForkConfig::low_water`]. Not to be confused with
+    /// [`CloneStream::buffer_high_water_mark`](crate::CloneStream::buffer_high_water_mark),
+    /// which only reports the largest queue.
+    pub high_water: Option<usize>,
+    /// Number of items queued for the slowest clone at or below which the
+    /// fork resumes polling the base stream after reaching
+    /// [`ForkConfig::high_water`].
+    pub low_water: usize,
+    /// Order in which each clone yields the items queued for it.
+    pub backlog_order: BacklogOrder,
+    /// How the queue finds the next queued item.
+    pub queue_backend: QueueBackend,
+    /// Poll the base stream before the queue, so the polling clone receives
+    /// a fresh item as soon as the base stream has one.
+    ///
+    /// Queued items the clone has not received yet are skipped for it when
+    /// the base stream is ready, and delivered as usual while it is pending.
+    /// Other clones still receive every item in order.
+    pub min_latency: bool,
+    /// Queue items for clones that are not polled when the base stream
+    /// produces them.
+    ///
+    /// By default, a clone only receives the items produced while it waits
+    /// on the base stream or still has queued items to receive. Items
+    /// produced while it has caught up and is not polled are skipped for it.
+    /// With this option, a clone receives every item produced after it was
+    /// created, so it cannot miss items because of when its task happens to
+    /// run. Items then stay queued for a clone that is never polled until
+    /// [`ForkConfig::max_queue_size`] is reached.
+    ///
+    /// Plain [`ForkStream::fork`](crate::ForkStream::fork) keeps the default.
+    /// The constructors that compute something once for all clones, such as
+    /// batches, running sums, shard routing or a replay history, always set
+    /// this option instead, because they promise every clone the complete
+    /// result. Without it, which batches or shard items a clone receives
+    /// would depend on when its task happens to run. Each of them links
+    /// here.
+    pub deliver_to_idle_clones: bool,
+    /// Only queue items for a clone from its first poll on, with
+    /// [`ForkConfig::deliver_to_idle_clones`].
+    ///
+    /// A clone that is never polled then holds no items, and receives every
+    /// item produced from its first poll on.
+    pub subscribe_on_first_poll: bool,
+}
+
+impl ForkConfig {
+    /// Returns the default configuration with
+    /// [`ForkConfig::deliver_to_idle_clones`] set.
+    ///
+    /// ```rust
+    /// use clone_stream::ForkConfig;
+    ///
+    /// assert!(ForkConfig::delivering_to_idle_clones().deliver_to_idle_clones);
+    /// ```
+    #[must_use]
+    pub fn delivering_to_idle_clones() -> Self {
+        Self {
+            deliver_to_idle_clones: true,
+            ..Self::default()
+        }
+    }
+
+    /// Checks the configuration and reports every problem at once.
+    ///
+    /// # Errors
+    ///
+    /// Returns all [`ConfigError`]s found, in the order of the fields they
+    /// concern.
+    ///
+    /// ```rust
+    /// use clone_stream::{ConfigError, ForkConfig};
+    ///
+    /// let config = ForkConfig {
+    ///     max_clone_count: 0,
+    ///     ..ForkConfig::default()
+    /// };
+    /// assert_eq!(config.validate(), Err(vec![ConfigError::ZeroCloneCount]));
+    /// ```
+    pub fn validate(&self) -> std::result::Result<(), Vec<ConfigError>> {
+        let mut errors = Vec::new();
+        if self.max_clone_count == 0 {
+            errors.push(ConfigError::ZeroCloneCount);
+        }
+        if self.max_queue_size == 0 {
+            errors.push(ConfigError::ZeroQueueSize);
+        }
+        if self.initial_queue_capacity > self.max_queue_size {
+            errors.push(ConfigError::InitialCapacityExceedsMax {
+                initial_capacity: self.initial_queue_capacity,
+                max_queue_size: self.max_queue_size,
+            });
+        }
+        if let Some(high_water) = self.high_water {
+            if self.low_water >= high_water {
+                errors.push(ConfigError::LowWaterNotBelowHighWater {
+                    low_water: self.low_water,
+                    high_water,
+                });
+            }
+            if high_water > self.max_queue_size {
+                errors.push(ConfigError::HighWaterExceedsMaxQueueSize {
+                    high_water,
+                    max_queue_size: self.max_queue_size,
+                });
+            }
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+/// Order in which a clone yields the items queued for it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BacklogOrder {
+    /// Oldest item first, the order the base stream produced them in.
+    #[default]
+    OldestFirst,
+    /// Newest item first. The clone still receives every item, but yields
+    /// its queued backlog from newest to oldest before it moves on to items
+    /// queued since.
+    NewestFirst,
+}
+
+/// How the queue of a fork finds the next queued item.
+///
+/// Items that every clone has received are removed, which can leave holes in
+/// the middle of the queue when clones skip or filter items.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum QueueBackend {
+    /// Keeps an ordered set of the occupied slots next to the items. Finding
+    /// the next item takes logarithmic time, no matter how many holes lie in
+    /// between.
+    #[default]
+    Sparse,
+    /// Only keeps the items in contiguous slots and scans them for the next
+    /// one. Faster and smaller when clones receive items in order, since the
+    /// next item is then in the next slot, but slow to skip long runs of
+    /// holes.
+    Dense,
 }
 
 impl Default for ForkConfig {
@@ -30,6 +281,24 @@ impl Default for ForkConfig {
         Self {
             max_clone_count: MAX_CLONE_COUNT,
             max_queue_size: MAX_QUEUE_SIZE,
+            initial_queue_capacity: 0,
+            record_poll_order: false,
+            selective_wakeups: false,
+            eager_drop_cleanup: true,
+            idle_after: IDLE_AFTER,
+            item_ttl: None,
+            memory_pressure_threshold: None,
+            slow_clone_warn_threshold: None,
+            scheduler_seed: None,
+            max_total_memory_bytes: None,
+            max_consecutive_pending: None,
+            high_water: None,
+            low_water: 0,
+            backlog_order: BacklogOrder::OldestFirst,
+            queue_backend: QueueBackend::Sparse,
+            min_latency: false,
+            deliver_to_idle_clones: false,
+            subscribe_on_first_poll: false,
         }
     }
 }
@@ -38,9 +307,91 @@ pub(crate) struct Fork<BaseStream>
 where
     BaseStream: Stream<Item: Clone>,
 {
-    pub(crate) base_stream: Pin<Box<BaseStream>>,
+    /// The base stream, until the fork is shut down.
+    pub(crate) base_stream: Option<Pin<Box<BaseStream>>>,
     pub(crate) item_buffer: RingQueue<Option<BaseStream::Item>>,
     pub(crate) clone_registry: CloneRegistry,
+    pub(crate) poll_trace: Option<Vec<PollEvent>>,
+    waiting_clones: Option<Arc<WaitingClones>>,
+    pub(crate) context: Option<Arc<dyn Any + Send + Sync>>,
+    /// Whether the last poll of the base stream returned `None`.
+    pub(crate) base_exhausted: bool,
+    /// Whether clones were dropped since the queue was last cleaned up.
+    cleanup_pending: bool,
+    pub(crate) config: ForkConfig,
+    pub(crate) poll_counters: Arc<PollCounters>,
+    /// Wakers of tasks waiting for items to be queued.
+    enqueue_wakers: WakerSet,
+    /// When the base stream last produced an item.
+    pub(crate) last_base_item_at: Option<Instant>,
+    /// Clone whose poll of the base stream most recently returned ready.
+    pub(crate) base_stream_driver: Option<usize>,
+    /// Largest number of items queued at once.
+    pub(crate) high_water: usize,
+    /// Number of times any clone polled the base stream.
+    pub(crate) base_polls: u64,
+    /// Number of items the base stream produced.
+    base_items: u64,
+    /// Number of times the base stream returned pending since it was last
+    /// ready.
+    consecutive_pending: usize,
+    /// Whether polling the base stream stopped until the slowest clone
+    /// catches up, see [`ForkConfig::high_water`].
+    pub(crate) backpressured: bool,
+    pub(crate) clock: Arc<dyn Clock>,
+    /// Decides which queued items may be evicted when the queue is full.
+    pub(crate) eviction_filter: Option<EvictionFilter<BaseStream::Item>>,
+    /// Sequence numbers carried by the items of the base stream.
+    pub(crate) sequence_tracker: Option<SequenceTracker<BaseStream::Item>>,
+    /// Items each clone may still receive, if clones have to ask for them.
+    pub(crate) credits: Option<Credits>,
+    /// Recent items replayed to clones created later.
+    pub(crate) replay: Option<Replay<BaseStream::Item>>,
+    /// Queued items that clones still have to acknowledge.
+    pub(crate) acks: Acks,
+    /// When each queued item was queued, oldest first, if items expire.
+    enqueued_at: VecDeque<(QueuePosition, Instant)>,
+    /// Number of items each clone missed because they expired.
+    expired: BTreeMap<usize, u64>,
+    /// Number of pending [`CloneStream::wait_until_buffered`] futures of each
+    /// clone, which keep it from skipping items while it is not polled.
+    ///
+    /// [`CloneStream::wait_until_buffered`]: crate::CloneStream::wait_until_buffered
+    buffer_waits: BTreeMap<usize, usize>,
+    /// Order in which clones polled together are polled, if seeded.
+    pub(crate) scheduler: Option<Scheduler>,
+    /// Estimates the number of bytes of an item, if not [`size_of`] its type.
+    pub(crate) item_size: Option<ItemSize<BaseStream::Item>>,
+    /// Runs on the base stream after every poll of it.
+    pub(crate) flush: Option<Flush<BaseStream>>,
+    /// Routes every item to the clone of a single shard, if sharded.
+    pub(crate) router: Option<Router<BaseStream::Item>>,
+}
+
+pub(crate) type EvictionFilter<Item> = Box<dyn Fn(&Item) -> bool + Send + Sync>;
+
+pub(crate) type ItemSize<Item> = Box<dyn Fn(&Item) -> usize + Send + Sync>;
+
+pub(crate) type Flush<BaseStream> = Box<dyn FnMut(Pin<&mut BaseStream>) + Send>;
+
+/// Why a polled clone did not receive an item.
+#[derive(Debug, Clone, Copy)]
+enum Pending {
+    BaseStream,
+    Backpressured,
+    Credits,
+    Ack,
+}
+
+impl Pending {
+    fn outcome<Item>(self) -> PollOutcome<Item> {
+        match self {
+            Pending::BaseStream => PollOutcome::PendingBaseStream,
+            Pending::Backpressured => PollOutcome::Backpressured,
+            Pending::Credits => PollOutcome::PendingCredits,
+            Pending::Ack => PollOutcome::PendingAck,
+        }
+    }
 }
 
 impl<BaseStream> Fork<BaseStream>
@@ -52,10 +403,495 @@ where
     }
 
     pub(crate) fn with_config(base_stream: BaseStream, config: ForkConfig) -> Self {
+        let poll_counters = Arc::new(PollCounters::default());
         Self {
-            base_stream: Box::pin(base_stream),
+            base_stream: Some(Box::pin(base_stream)),
             clone_registry: CloneRegistry::new(config.max_clone_count),
-            item_buffer: RingQueue::new(config.max_queue_size),
+            item_buffer: RingQueue::with_initial_capacity(
+                config.max_queue_size,
+                config.initial_queue_capacity,
+                config.queue_backend,
+            ),
+            poll_trace: config.record_poll_order.then(Vec::new),
+            waiting_clones: config
+                .selective_wakeups
+                .then(|| Arc::new(WaitingClones::new(poll_counters.clone()))),
+            context: None,
+            base_exhausted: false,
+            cleanup_pending: false,
+            config,
+            poll_counters,
+            enqueue_wakers: WakerSet::default(),
+            last_base_item_at: None,
+            base_stream_driver: None,
+            high_water: 0,
+            base_polls: 0,
+            base_items: 0,
+            consecutive_pending: 0,
+            backpressured: false,
+            clock: Arc::new(SystemClock),
+            eviction_filter: None,
+            sequence_tracker: None,
+            credits: None,
+            replay: None,
+            acks: Acks::default(),
+            enqueued_at: VecDeque::new(),
+            expired: BTreeMap::new(),
+            buffer_waits: BTreeMap::new(),
+            scheduler: config.scheduler_seed.map(Scheduler::new),
+            item_size: None,
+            flush: None,
+            router: None,
+        }
+    }
+
+    pub(crate) fn record(&mut self, clone_id: usize, kind: PollEventKind) {
+        if let Some(trace) = &mut self.poll_trace {
+            trace.push(PollEvent { clone_id, kind });
+        }
+    }
+
+    /// Registers a new clone.
+    ///
+    /// A clone registered after the base stream ended, when nothing is queued
+    /// anymore, sees that end first instead of polling the base stream again.
+    pub(crate) fn register_clone(&mut self) -> Result<usize> {
+        self.check_memory_for_new_clone()?;
+        let initial_state = if self.base_exhausted && self.item_buffer.is_empty() {
+            CloneState::BaseStreamExhausted
+        } else if self.config.subscribe_on_first_poll {
+            CloneState::Unsubscribed
+        } else {
+            CloneState::default()
+        };
+        let clone_id = self.clone_registry.register_with_state(initial_state)?;
+        if let Some(replay) = &mut self.replay {
+            self.poll_counters
+                .record_item_clones(replay.start(clone_id));
+        }
+        Ok(clone_id)
+    }
+
+    /// Fails if the queued items a new clone would receive push the estimated
+    /// memory over [`ForkConfig::max_total_memory_bytes`].
+    fn check_memory_for_new_clone(&self) -> Result<()> {
+        let Some(max_bytes) = self.config.max_total_memory_bytes else {
+            return Ok(());
+        };
+        let inherited_bytes: usize = (&self.item_buffer)
+            .into_iter()
+            .filter_map(|(_, item)| item.as_ref())
+            .map(|item| self.item_bytes(item))
+            .sum();
+        let estimated_bytes = self.estimated_total_memory() + inherited_bytes;
+        if estimated_bytes > max_bytes {
+            return Err(CloneStreamError::MemoryLimitExceeded {
+                estimated_bytes,
+                max_bytes,
+            });
+        }
+        Ok(())
+    }
+
+    /// Estimates the bytes of the queued items every clone has not received
+    /// yet, counting an item once for each clone that still needs it.
+    pub(crate) fn estimated_total_memory(&self) -> usize {
+        self.clone_registry
+            .iter_active_with_ids()
+            .flat_map(|(clone_id, _)| self.unseen_items(clone_id))
+            .map(|item| self.item_bytes(item))
+            .sum()
+    }
+
+    fn item_bytes(&self, item: &BaseStream::Item) -> usize {
+        self.item_size
+            .as_ref()
+            .map_or(size_of::<BaseStream::Item>(), |item_size| item_size(item))
+    }
+
+    /// Moves a clone past every queued item, activating it again if it was
+    /// closed. After the base stream ended, the clone sees that end first.
+    pub(crate) fn resubscribe(&mut self, clone_id: usize) {
+        self.clone_registry.take(clone_id);
+        self.forget_clone(clone_id);
+        self.clone_registry
+            .restore(clone_id, self.subscribed_state())
+            .expect("Failed to restore clone state - the clone was just taken");
+    }
+
+    /// Returns the state of a clone that receives only the items produced
+    /// from now on. After the base stream ended, the clone sees that end
+    /// first.
+    pub(crate) fn subscribed_state(&self) -> CloneState {
+        if self.base_exhausted {
+            CloneState::BaseStreamExhausted
+        } else {
+            match self.item_buffer.newest_position() {
+                Some(last_seen) => CloneState::BaseStreamReadyWithQueueHistory { last_seen },
+                None => CloneState::BaseStreamReady,
+            }
+        }
+    }
+
+    /// Moves the given clones back to the position of the one among them that
+    /// has seen the fewest queued items, and wakes those that were waiting.
+    pub(crate) fn align_to_slowest(&mut self, clone_ids: &[usize]) {
+        let positions: Vec<_> = clone_ids
+            .iter()
+            .filter_map(|&clone_id| self.clone_registry.get_clone_state(clone_id))
+            .map(CloneState::seen_through)
+            .collect();
+        let Some(slowest) = positions.into_iter().reduce(|slowest, position| {
+            if is_behind(position, slowest) {
+                position
+            } else {
+                slowest
+            }
+        }) else {
+            return;
+        };
+        for &clone_id in clone_ids {
+            let Some(state) = self.clone_registry.take(clone_id) else {
+                continue;
+            };
+            if let Some(waker) = state.waker() {
+                waker.wake();
+            }
+            if let Some(waiting_clones) = &self.waiting_clones {
+                waiting_clones.remove(clone_id);
+            }
+            self.clone_registry
+                .restore(clone_id, CloneState::seen_through_position(slowest))
+                .expect("Failed to restore clone state - the clone was just taken");
+        }
+    }
+
+    /// Returns the base stream, unless the fork was shut down.
+    pub(crate) fn base(&self) -> Option<&BaseStream> {
+        self.base_stream.as_deref()
+    }
+
+    pub(crate) fn base_size_hint(&self) -> (usize, Option<usize>) {
+        self.base().map_or((0, Some(0)), Stream::size_hint)
+    }
+
+    /// Drops the base stream and every queued item, so all clones end on
+    /// their next poll, and wakes the clones that are waiting.
+    pub(crate) fn shutdown(&mut self) {
+        debug!("Shutting down the fork.");
+        self.base_stream = None;
+        self.base_exhausted = true;
+        self.item_buffer.clear();
+        self.enqueued_at.clear();
+        self.wake_waiting_clones();
+        self.enqueue_wakers.wake_all();
+    }
+
+    pub(crate) fn is_shut_down(&self) -> bool {
+        self.base_stream.is_none()
+    }
+
+    /// Polls the base stream once on behalf of `clone_id`.
+    pub(crate) fn poll_base(
+        &mut self,
+        clone_id: usize,
+        waker: &Waker,
+    ) -> Poll<Option<BaseStream::Item>> {
+        let base_waker = self.waker(clone_id, waker);
+        if self.apply_backpressure() {
+            trace!("Clone {clone_id} waits for the slowest clone to catch up.");
+            return Poll::Pending;
+        }
+        self.poll_base_with(clone_id, &base_waker)
+    }
+
+    /// Polls the base stream with `base_waker`, even while it is paused until
+    /// the slowest clone catches up.
+    fn poll_base_with(
+        &mut self,
+        clone_id: usize,
+        base_waker: &Waker,
+    ) -> Poll<Option<BaseStream::Item>> {
+        let Some(base_stream) = &mut self.base_stream else {
+            trace!("Base stream was shut down");
+            return Poll::Ready(None);
+        };
+        let poll = base_stream.poll_next_unpin(&mut Context::from_waker(base_waker));
+        self.base_polls += 1;
+        if let Some(flush) = &mut self.flush {
+            flush(base_stream.as_mut());
+        }
+        match poll {
+            Poll::Ready(item) => {
+                trace!("Base stream ready with item");
+                let kind = if item.is_some() {
+                    PollEventKind::BaseItem
+                } else {
+                    PollEventKind::BaseEnded
+                };
+                self.record(clone_id, kind);
+                self.base_stream_driver = Some(clone_id);
+                self.consecutive_pending = 0;
+                self.base_exhausted = item.is_none();
+                if item.is_none() {
+                    self.wake_waiting_clones();
+                }
+                if let Some(item) = &item {
+                    self.observe_base_item(item);
+                }
+                Poll::Ready(item)
+            }
+            Poll::Pending => {
+                trace!("Base stream pending");
+                self.record(clone_id, PollEventKind::BasePending);
+                self.consecutive_pending += 1;
+                if let Some(max_pending) = self.config.max_consecutive_pending
+                    && self.consecutive_pending == max_pending + 1
+                {
+                    warn!(
+                        "Base stream returned pending {} times in a row. It may not wake the \
+                         waker it is polled with.",
+                        self.consecutive_pending
+                    );
+                }
+                Poll::Pending
+            }
+        }
+    }
+
+    /// Stops polling the base stream when the slowest clone lags
+    /// [`ForkConfig::high_water`] items behind, and resumes it when the lag
+    /// dropped to [`ForkConfig::low_water`]. Returns whether polling is
+    /// stopped.
+    ///
+    /// Items stay queued until the slowest clone has them, so the length of
+    /// the queue is the lag of the slowest clone.
+    fn apply_backpressure(&mut self) -> bool {
+        let Some(high_water) = self.config.high_water else {
+            return false;
+        };
+        let lag = self.item_buffer.len();
+        if !self.backpressured && lag >= high_water {
+            debug!("Slowest clone lags {lag} items behind, pausing the base stream.");
+            self.backpressured = true;
+        }
+        self.release_backpressure();
+        self.backpressured
+    }
+
+    /// Resumes polling the base stream once the slowest clone caught up to
+    /// [`ForkConfig::low_water`], and wakes the clones waiting for it.
+    fn release_backpressure(&mut self) {
+        if self.backpressured && self.item_buffer.len() <= self.config.low_water {
+            debug!("Slowest clone caught up, resuming the base stream.");
+            self.backpressured = false;
+            self.wake_waiting_clones();
+        }
+    }
+
+    /// Updates the bookkeeping for an item produced by the base stream.
+    fn observe_base_item(&mut self, item: &BaseStream::Item) {
+        self.base_items += 1;
+        self.last_base_item_at = Some(self.clock.now());
+        if let Some(tracker) = &mut self.sequence_tracker {
+            tracker.observe(item);
+        }
+        if let Some(replay) = &mut self.replay {
+            self.poll_counters.record_item_clones(1);
+            replay.record(item);
+        }
+    }
+
+    /// Queues `item` for every clone as if the base stream produced it.
+    #[cfg(feature = "test-util")]
+    pub(crate) fn inject(&mut self, clone_id: usize, item: BaseStream::Item) -> Result<()> {
+        if self.base_exhausted {
+            return Err(CloneStreamError::BaseStreamEnded);
+        }
+        self.observe_base_item(&item);
+        self.poll_counters.record_item_clones(1);
+        self.enqueue(clone_id, Some(item));
+        self.wake_waiting_clones();
+        Ok(())
+    }
+
+    /// Wakes every other clone waiting on the base stream, so it sees the end
+    /// even if the base stream does not wake it.
+    fn wake_waiting_clones(&self) {
+        match &self.waiting_clones {
+            Some(waiting_clones) => waiting_clones.wake_by_ref(),
+            None => self
+                .clone_registry
+                .collect_wakers_needing_base_item()
+                .wake_all(),
+        }
+    }
+
+    /// Polls the base stream until it ends, queueing every item for all
+    /// clones including `clone_id`. Keeps polling while the base stream is
+    /// paused for backpressure, since every item has to be queued anyway.
+    pub(crate) fn poll_buffer_to_end(&mut self, clone_id: usize, waker: &Waker) -> Poll<()> {
+        let base_waker = self.waker(clone_id, waker);
+        while !self.base_exhausted {
+            let item = ready!(self.poll_base_with(clone_id, &base_waker));
+            let ended = item.is_none();
+            self.enqueue(clone_id, item);
+            if ended {
+                break;
+            }
+        }
+        Poll::Ready(())
+    }
+
+    /// Returns how many items `clone_id` will still receive from the replayed
+    /// and the queued items, not counting the end of the base stream.
+    pub(crate) fn remaining_items(&self, clone_id: usize) -> usize {
+        let replayed = self
+            .replay
+            .as_ref()
+            .map_or(0, |replay| replay.remaining(clone_id));
+        replayed + self.unseen_items(clone_id).count()
+    }
+
+    /// Returns how many items of the base stream `clone_id` is past: the
+    /// items produced so far minus those still queued for it.
+    pub(crate) fn epoch(&self, clone_id: usize) -> u64 {
+        self.base_items - self.unseen_items(clone_id).count() as u64
+    }
+
+    /// Returns the queued items that `clone_id` has not received yet, not
+    /// including the end of the base stream.
+    pub(crate) fn unseen_items(&self, clone_id: usize) -> impl Iterator<Item = &BaseStream::Item> {
+        (&self.item_buffer)
+            .into_iter()
+            .filter(move |(item_index, _)| self.should_clone_see_item(clone_id, *item_index))
+            .filter_map(|(_, item)| item.as_ref())
+    }
+
+    /// Queues an item from the base stream.
+    ///
+    /// Other clones that do not want fresh items skip it, see
+    /// [`ForkConfig::deliver_to_idle_clones`].
+    pub(crate) fn enqueue(&mut self, clone_id: usize, item: Option<BaseStream::Item>) {
+        let idle_clones: Vec<usize> = self
+            .clone_registry
+            .iter_active_with_ids()
+            .map(|(other_id, _)| other_id)
+            .filter(|&other_id| other_id != clone_id && self.is_idle(other_id))
+            .collect();
+        match &self.eviction_filter {
+            Some(may_evict) => self
+                .item_buffer
+                .push_evicting(item, |queued| queued.as_ref().is_none_or(may_evict)),
+            None => self.item_buffer.push(item),
+        }
+        self.high_water = self.high_water.max(self.item_buffer.len());
+        if self.config.item_ttl.is_some()
+            && let Some(position) = self.item_buffer.newest_position()
+            && self
+                .item_buffer
+                .get(position.index)
+                .is_some_and(Option::is_some)
+        {
+            self.enqueued_at.push_back((position, self.clock.now()));
+        }
+        if let Some(index) = self.item_buffer.newest {
+            self.record(clone_id, PollEventKind::Enqueued { index });
+        }
+        if let Some(position) = self.item_buffer.newest_position() {
+            for idle_id in idle_clones {
+                trace!("Clone {idle_id} is idle, skipping the new item");
+                self.clone_registry.take(idle_id);
+                self.clone_registry
+                    .restore(idle_id, CloneState::seen_through_position(Some(position)))
+                    .expect("Failed to restore clone state - the clone was just taken");
+            }
+        }
+        self.enqueue_wakers.wake_all();
+        self.warn_about_slow_clones();
+    }
+
+    /// Warns about clones that just fell more than
+    /// [`ForkConfig::slow_clone_warn_threshold`] items behind.
+    fn warn_about_slow_clones(&self) {
+        let Some(threshold) = self.config.slow_clone_warn_threshold else {
+            return;
+        };
+        for (clone_id, _) in self.clone_registry.iter_active_with_ids() {
+            let lag = self.remaining_queued_items(clone_id);
+            if lag == threshold + 1 {
+                warn!(
+                    "Clone {clone_id} lags {lag} items behind, over the threshold of {threshold}."
+                );
+            }
+        }
+    }
+
+    /// Reclaims queued items no clone needs anymore on the next poll.
+    pub(crate) fn defer_cleanup(&mut self) {
+        self.cleanup_pending = true;
+    }
+
+    /// Evicts the queued items older than [`ForkConfig::item_ttl`] and counts
+    /// them as missed for the clones that had not received them.
+    fn evict_expired(&mut self) {
+        let Some(ttl) = self.config.item_ttl else {
+            return;
+        };
+        let now = self.clock.now();
+        let mut newest_expired = None;
+        while let Some(&(position, enqueued_at)) = self.enqueued_at.front()
+            && now.saturating_duration_since(enqueued_at) >= ttl
+        {
+            self.enqueued_at.pop_front();
+            if let Some(position) = self.item_buffer.locate(position) {
+                newest_expired = Some(position);
+            }
+        }
+        let Some(newest_expired) = newest_expired else {
+            return;
+        };
+        for (item_index, _) in &self.item_buffer {
+            let is_expired = self
+                .item_buffer
+                .position(item_index)
+                .is_some_and(|position| !newest_expired.is_before(position));
+            if !is_expired {
+                break;
+            }
+            for (clone_id, state) in self.clone_registry.iter_active_with_ids() {
+                if state.is_unseen(&self.item_buffer, item_index) {
+                    *self.expired.entry(clone_id).or_default() += 1;
+                }
+            }
+        }
+        debug!("Evicting queued items up to {newest_expired:?}, they expired.");
+        self.item_buffer.drain_range(newest_expired.index);
+    }
+
+    /// Number of items `clone_id` missed because they expired.
+    pub(crate) fn n_expired_items(&self, clone_id: usize) -> u64 {
+        self.expired.get(&clone_id).copied().unwrap_or_default()
+    }
+
+    /// Wakes `waker` the next time an item is queued.
+    pub(crate) fn wake_on_enqueue(&mut self, waker: &Waker) {
+        self.enqueue_wakers.insert(waker);
+    }
+
+    /// Queues new items for `clone_id` while it waits for them to be
+    /// buffered, even if it is not polled.
+    pub(crate) fn start_buffer_wait(&mut self, clone_id: usize) {
+        *self.buffer_waits.entry(clone_id).or_default() += 1;
+    }
+
+    /// Undoes [`Self::start_buffer_wait`].
+    pub(crate) fn end_buffer_wait(&mut self, clone_id: usize) {
+        if let Some(waits) = self.buffer_waits.get_mut(&clone_id) {
+            *waits -= 1;
+            if *waits == 0 {
+                self.buffer_waits.remove(&clone_id);
+            }
         }
     }
 
@@ -64,73 +900,413 @@ where
         clone_id: usize,
         clone_waker: &Waker,
     ) -> Poll<Option<BaseStream::Item>> {
-        let mut current_state = self.clone_registry.take(clone_id).unwrap();
+        self.poll_clone_outcome(clone_id, clone_waker).into_poll()
+    }
+
+    /// Polls a clone and tells why it is pending, if it is.
+    pub(crate) fn poll_clone_outcome(
+        &mut self,
+        clone_id: usize,
+        clone_waker: &Waker,
+    ) -> PollOutcome<BaseStream::Item> {
+        match self.poll_delivery(clone_id, clone_waker) {
+            Ok(Delivery::Owned(item)) => PollOutcome::Item(item),
+            Ok(Delivery::Queued(index)) => {
+                self.poll_counters.record_item_clones(1);
+                PollOutcome::Item(self.item_buffer.get(index).cloned().flatten())
+            }
+            Err(pending) => pending.outcome(),
+        }
+    }
+
+    /// Polls a clone for an item it acknowledges later. Also returns the
+    /// sequence number of the item if it was kept in the queue until then.
+    pub(crate) fn poll_clone_acked(
+        &mut self,
+        clone_id: usize,
+        clone_waker: &Waker,
+    ) -> Poll<Option<(BaseStream::Item, Option<u64>)>> {
+        self.acks.begin_receiving(clone_id);
+        let poll_result = self.poll_clone(clone_id, clone_waker);
+        let sequence = self.acks.finish_receiving();
+        poll_result.map(|item| item.map(|item| (item, sequence)))
+    }
+
+    /// Keeps the queued item at `index` until `clone_id` acknowledges it, if
+    /// the clone is receiving an item to acknowledge. The end of the base
+    /// stream is never kept.
+    pub(crate) fn keep_for_ack(&mut self, clone_id: usize, index: usize) {
+        if self.acks.is_receiving(clone_id)
+            && self.item_buffer.get(index).is_some_and(Option::is_some)
+            && let Some(position) = self.item_buffer.position(index)
+        {
+            self.acks.hold(position.sequence);
+        }
+    }
+
+    /// Checks whether a clone has not acknowledged the queued item at `index`
+    /// yet.
+    pub(crate) fn is_held_for_ack(&self, index: usize) -> bool {
+        self.item_buffer
+            .position(index)
+            .is_some_and(|position| self.acks.is_held(position.sequence))
+    }
+
+    /// Acknowledges the queued item with `sequence` once, and removes it from
+    /// the queue when no clone needs it anymore.
+    pub(crate) fn ack(&mut self, sequence: u64) {
+        if self.acks.release(sequence) {
+            trace!("Item {sequence} was acknowledged by every clone that received it.");
+            self.cleanup_unneeded_queue_items();
+            self.release_backpressure();
+        }
+    }
+
+    /// Polls a clone and passes a reference to its next item to `inspect`,
+    /// so items that stay queued for other clones are not cloned.
+    pub(crate) fn poll_clone_with<Output>(
+        &mut self,
+        clone_id: usize,
+        clone_waker: &Waker,
+        inspect: impl FnOnce(&BaseStream::Item) -> Output,
+    ) -> Poll<Option<Output>> {
+        Poll::Ready(match self.poll_delivery(clone_id, clone_waker) {
+            Ok(Delivery::Owned(item)) => item.as_ref().map(inspect),
+            Ok(Delivery::Queued(index)) => self
+                .item_buffer
+                .get(index)
+                .and_then(Option::as_ref)
+                .map(inspect),
+            Err(_) => return Poll::Pending,
+        })
+    }
+
+    /// Polls a clone for its next item if `keep` accepts it, or `Some(None)`
+    /// if not. Only accepted items still queued for other clones are cloned.
+    #[allow(clippy::option_option)]
+    pub(crate) fn poll_clone_filtered(
+        &mut self,
+        clone_id: usize,
+        clone_waker: &Waker,
+        keep: impl FnOnce(&BaseStream::Item) -> bool,
+    ) -> Poll<Option<Option<BaseStream::Item>>> {
+        Poll::Ready(match self.poll_delivery(clone_id, clone_waker) {
+            Ok(Delivery::Owned(item)) => item.map(|item| keep(&item).then_some(item)),
+            Ok(Delivery::Queued(index)) => self
+                .item_buffer
+                .get(index)
+                .and_then(Option::as_ref)
+                .map(|item| {
+                    keep(item).then(|| {
+                        self.poll_counters.record_item_clones(1);
+                        item.clone()
+                    })
+                }),
+            Err(_) => return Poll::Pending,
+        })
+    }
+
+    /// Polls a clone for its next item. Decides why the clone is pending
+    /// while the fork is still locked, so the reason cannot change before it
+    /// is reported.
+    fn poll_delivery(
+        &mut self,
+        clone_id: usize,
+        clone_waker: &Waker,
+    ) -> std::result::Result<Delivery<BaseStream::Item>, Pending> {
+        if self.cleanup_pending {
+            trace!("Cleaning up queue after deferred clone drops.");
+            self.cleanup_unneeded_queue_items();
+            self.cleanup_pending = false;
+        }
+        self.evict_expired();
+        if self.is_shut_down() {
+            trace!("Clone {clone_id} was polled after the fork was shut down.");
+            return Ok(Delivery::Owned(None));
+        }
+        let Some(mut current_state) = self.clone_registry.take(clone_id) else {
+            warn!("Clone {clone_id} was polled but is not registered, treating it as ended.");
+            return Ok(Delivery::Owned(None));
+        };
+        if let Some(credits) = &mut self.credits
+            && !credits.try_spend(clone_id, clone_waker)
+        {
+            trace!("Clone {clone_id} has no credits left.");
+            self.clone_registry
+                .restore(clone_id, current_state)
+                .expect("Failed to restore clone state - the clone was just taken");
+            return Err(Pending::Credits);
+        }
+        if let Some(item) = self
+            .replay
+            .as_mut()
+            .and_then(|replay| replay.next(clone_id))
+        {
+            trace!("Replaying a recent item to clone {clone_id}.");
+            self.clone_registry
+                .restore(clone_id, current_state)
+                .expect("Failed to restore clone state - the clone was just taken");
+            return Ok(Delivery::Owned(Some(item)));
+        }
         debug!("State of clone {clone_id} is {current_state:?}.");
+        let was_waiting = current_state.waker().is_some();
 
         let poll_result = current_state.step(clone_id, clone_waker, self);
 
+        self.poll_counters.record_poll();
+        if let Some(credits) = &mut self.credits
+            && poll_result.is_pending()
+        {
+            credits.refund(clone_id);
+        }
+        if was_waiting && poll_result.is_pending() {
+            self.poll_counters.record_spurious_poll();
+        }
+
         debug!("Clone {clone_id} transitioned to {current_state:?}.");
+        if let Some(waiting_clones) = &self.waiting_clones
+            && current_state.waker().is_none()
+        {
+            waiting_clones.remove(clone_id);
+        }
         self.clone_registry
             .restore(clone_id, current_state)
             .expect("Failed to restore clone state - this should never happen as we just took it");
-        poll_result
+        match poll_result {
+            Poll::Ready(delivery) => {
+                self.release_backpressure();
+                Ok(delivery)
+            }
+            Poll::Pending => Err(self.pending_reason()),
+        }
     }
 
-    pub(crate) fn waker(&self, extra_waker: &Waker) -> Waker {
-        let clone_wakers = self.clone_registry.collect_wakers_needing_base_item();
+    /// Tells why a clone that found no item for it is pending.
+    fn pending_reason(&self) -> Pending {
+        if !self.backpressured {
+            Pending::BaseStream
+        } else if self
+            .item_buffer
+            .oldest_index()
+            .is_some_and(|index| self.is_held_for_ack(index))
+        {
+            Pending::Ack
+        } else {
+            Pending::Backpressured
+        }
+    }
+
+    pub(crate) fn waker(&self, clone_id: usize, extra_waker: &Waker) -> Waker {
+        if let Some(waiting_clones) = &self.waiting_clones {
+            waiting_clones.insert(clone_id, extra_waker.clone());
+            return Waker::from(waiting_clones.clone());
+        }
+
+        let mut wakers = if self.is_under_memory_pressure() {
+            trace!("Queue is over the memory pressure threshold, waking slowest clones first");
+            self.clone_registry.collect_wakers_slowest_first()
+        } else {
+            self.clone_registry.collect_wakers_needing_base_item()
+        };
         trace!(
             "There are {} clone wakers needing base item. Adding one more",
-            clone_wakers.len()
+            wakers.len()
         );
-        let waker_count = clone_wakers.len() + 1;
+        wakers.insert(extra_waker);
 
-        // Avoid Arc allocation for single waker
-        if waker_count == 1 {
-            extra_waker.clone()
-        } else {
-            let all_wakers = clone_wakers
-                .into_iter()
-                .chain(iter::once(extra_waker.clone()))
-                .collect();
-            Waker::from(Arc::new(MultiWaker { wakers: all_wakers }))
+        // Avoid Arc allocation for single waker, unless its wakeup is counted
+        match wakers.single() {
+            Some(waker) if !cfg!(feature = "metrics") => waker.clone(),
+            _ => Waker::from(Arc::new(MultiWaker {
+                wakers,
+                poll_counters: self.poll_counters.clone(),
+            })),
         }
     }
 
+    fn is_under_memory_pressure(&self) -> bool {
+        self.config
+            .memory_pressure_threshold
+            .is_some_and(|threshold| self.item_buffer.len() > threshold)
+    }
+
     pub(crate) fn remaining_queued_items(&self, clone_id: usize) -> usize {
+        let replayed = self
+            .replay
+            .as_ref()
+            .map_or(0, |replay| replay.remaining(clone_id));
+        replayed
+            + (&self.item_buffer)
+                .into_iter()
+                .map(|(item_index, _)| item_index)
+                .filter(|&item_index| self.should_clone_see_item(clone_id, item_index))
+                .count()
+    }
+
+    pub(crate) fn readiness(&self, clone_id: usize) -> Readiness {
+        if self.has_unseen_items(clone_id) {
+            Readiness::Ready
+        } else if self.last_base_item_at.is_some_and(|produced_at| {
+            self.clock.now().saturating_duration_since(produced_at) < self.config.idle_after
+        }) {
+            Readiness::SourceActive
+        } else {
+            Readiness::Idle
+        }
+    }
+
+    pub(crate) fn has_unseen_items(&self, clone_id: usize) -> bool {
+        if self
+            .replay
+            .as_ref()
+            .is_some_and(|replay| replay.remaining(clone_id) > 0)
+        {
+            return true;
+        }
+        self.clone_registry
+            .get_clone_state(clone_id)
+            .and_then(|state| state.next_queued_index(&self.item_buffer, self.config.backlog_order))
+            .is_some()
+    }
+
+    /// Checks whether two clones have seen the same queued items.
+    pub(crate) fn same_position(&self, clone_id: usize, other_clone_id: usize) -> bool {
+        let position = |clone_id| {
+            self.clone_registry.get_clone_state(clone_id).map(|state| {
+                let sequence =
+                    |position: Option<QueuePosition>| position.map(|position| position.sequence);
+                (sequence(state.seen_through()), sequence(state.last_seen()))
+            })
+        };
+        match (position(clone_id), position(other_clone_id)) {
+            (Some(position), Some(other_position)) => position == other_position,
+            _ => false,
+        }
+    }
+
+    /// Checks whether any active clone has not received or not acknowledged
+    /// the queued item at `item_index` yet.
+    fn is_needed(&self, item_index: usize) -> bool {
+        self.is_held_for_ack(item_index)
+            || self
+                .clone_registry
+                .iter_active_with_ids()
+                .any(|(clone_id, _)| self.should_clone_see_item(clone_id, item_index))
+    }
+
+    /// Returns the index of the oldest queued item some active clone has not
+    /// received yet.
+    pub(crate) fn oldest_needed_index(&self) -> Option<usize> {
         (&self.item_buffer)
             .into_iter()
             .map(|(item_index, _)| item_index)
-            .filter(|&item_index| self.should_clone_see_item(clone_id, item_index))
-            .count()
+            .find(|&item_index| self.is_needed(item_index))
     }
 
     pub(crate) fn should_clone_see_item(&self, clone_id: usize, queue_item_index: usize) -> bool {
-        if let Some(state) = self.clone_registry.get_clone_state(clone_id) {
-            match state {
-                crate::states::CloneState::AwaitingFirstItem
-                | crate::states::CloneState::AwaitingBaseStream { .. } => true,
-                crate::states::CloneState::AwaitingBaseStreamWithQueueHistory {
-                    last_seen_index,
-                    ..
-                } => self
-                    .item_buffer
-                    .is_newer_than(queue_item_index, *last_seen_index),
-                crate::states::CloneState::ProcessingQueue {
-                    last_seen_queue_index: unseen_index,
-                } => !self
-                    .item_buffer
-                    .is_newer_than(queue_item_index, *unseen_index),
-                crate::states::CloneState::BaseStreamReady
-                | crate::states::CloneState::BaseStreamReadyWithQueueHistory => false,
-            }
-        } else {
-            false
-        }
+        self.clone_registry
+            .get_clone_state(clone_id)
+            .is_some_and(|state| state.is_unseen(&self.item_buffer, queue_item_index))
+            && self.is_routed_to(clone_id, queue_item_index)
+    }
+
+    /// Checks whether the queued item at `index` is routed to `clone_id`,
+    /// which is always the case unless the stream is sharded.
+    pub(crate) fn is_routed_to(&self, clone_id: usize, index: usize) -> bool {
+        self.router.as_ref().is_none_or(|router| {
+            router.is_routed_to(
+                clone_id,
+                self.item_buffer.get(index).and_then(Option::as_ref),
+            )
+        })
+    }
+
+    /// Checks whether a clone other than `clone_id` wants an item fresh from
+    /// the base stream.
+    pub(crate) fn is_wanted_by_other_clones(
+        &self,
+        clone_id: usize,
+        item: Option<&BaseStream::Item>,
+    ) -> bool {
+        self.clone_registry
+            .iter_active_with_ids()
+            .any(|(other_id, state)| {
+                other_id != clone_id
+                    && !matches!(state, CloneState::Unsubscribed)
+                    && !self.is_idle(other_id)
+                    && self
+                        .router
+                        .as_ref()
+                        .is_none_or(|router| router.is_routed_to(other_id, item))
+            })
+    }
+
+    /// Checks whether a clone skips the items the base stream produces now,
+    /// because it has caught up and is neither waiting on the base stream nor
+    /// for items to be buffered.
+    ///
+    /// Never the case with [`ForkConfig::deliver_to_idle_clones`].
+    fn is_idle(&self, clone_id: usize) -> bool {
+        !self.config.deliver_to_idle_clones
+            && !self.buffer_waits.contains_key(&clone_id)
+            && self
+                .clone_registry
+                .get_clone_state(clone_id)
+                .is_some_and(|state| {
+                    state.waker().is_none()
+                        && !matches!(
+                            state,
+                            CloneState::BaseStreamExhausted | CloneState::Unsubscribed
+                        )
+                })
+            && !self.has_unseen_items(clone_id)
     }
 
     pub(crate) fn unregister(&mut self, clone_id: usize) {
         self.clone_registry.unregister(clone_id);
-        self.cleanup_unneeded_queue_items();
+        self.forget_demand(clone_id);
+        self.forget_clone(clone_id);
+    }
+
+    /// Stops a clone from receiving items, but keeps its ID reserved until
+    /// [`Self::release`], so it is not handed to a new clone while the closed
+    /// clone still exists.
+    pub(crate) fn close(&mut self, clone_id: usize) {
+        self.clone_registry.deactivate(clone_id);
+        self.forget_demand(clone_id);
+        self.forget_clone(clone_id);
+    }
+
+    /// Makes the ID of a closed clone available for new clones.
+    pub(crate) fn release(&mut self, clone_id: usize) {
+        self.clone_registry.release(clone_id);
+    }
+
+    /// Drops the credits, replayed items and expiry count of a removed clone.
+    fn forget_demand(&mut self, clone_id: usize) {
+        if let Some(credits) = &mut self.credits {
+            credits.forget(clone_id);
+        }
+        if let Some(replay) = &mut self.replay {
+            replay.forget(clone_id);
+        }
+        self.expired.remove(&clone_id);
+    }
+
+    /// Drops the waker and the queued items that only the removed clone
+    /// still needed.
+    fn forget_clone(&mut self, clone_id: usize) {
+        if let Some(waiting_clones) = &self.waiting_clones {
+            waiting_clones.remove(clone_id);
+        }
+        // Clones waiting on a paused base stream are only woken once the
+        // items of the removed clone are gone.
+        if self.config.eager_drop_cleanup || self.backpressured {
+            self.cleanup_unneeded_queue_items();
+            self.release_backpressure();
+        } else {
+            self.cleanup_pending = true;
+        }
     }
 
     fn cleanup_unneeded_queue_items(&mut self) {
@@ -139,41 +1315,89 @@ where
             return;
         }
 
-        let items_to_remove: Vec<usize> = (&self.item_buffer)
+        let unneeded: Vec<(usize, bool)> = (&self.item_buffer)
             .into_iter()
-            .filter_map(|(item_index, _)| {
-                let is_needed = self
-                    .clone_registry
-                    .iter_active_with_ids()
-                    .any(|(clone_id, _)| self.should_clone_see_item(clone_id, item_index));
-                (!is_needed).then_some(item_index)
-            })
+            .map(|(item_index, _)| (item_index, !self.is_needed(item_index)))
             .collect();
 
-        for item_index in items_to_remove {
-            self.item_buffer.remove(item_index);
+        // The oldest items that every clone has seen are drained in one pass.
+        let drained = unneeded
+            .iter()
+            .take_while(|(_, is_unneeded)| *is_unneeded)
+            .count();
+        if let Some(&(up_to_index, _)) = drained.checked_sub(1).map(|last| &unneeded[last]) {
+            self.item_buffer.drain_range(up_to_index);
+        }
+        for &(item_index, is_unneeded) in &unneeded[drained..] {
+            if is_unneeded {
+                self.item_buffer.remove(item_index);
+            }
         }
     }
 }
 
-impl<BaseStream> Deref for Fork<BaseStream>
-where
-    BaseStream: Stream<Item: Clone>,
-{
-    type Target = BaseStream;
+pub(crate) struct MultiWaker {
+    wakers: WakerSet,
+    poll_counters: Arc<PollCounters>,
+}
 
-    fn deref(&self) -> &Self::Target {
-        &self.base_stream
+impl Wake for MultiWaker {
+    fn wake(self: Arc<Self>) {
+        warn!("New data arrived in source stream, waking up sleeping clones.");
+        for waker in &self.wakers {
+            self.poll_counters.record_wakeup();
+            waker.wake_by_ref();
+        }
     }
 }
 
-pub(crate) struct MultiWaker {
-    wakers: Vec<Waker>,
+/// Wakers of the clones currently waiting on the base stream.
+///
+/// Used as the waker of the base stream when
+/// [`ForkConfig::selective_wakeups`] is enabled. Waking it wakes and forgets
+/// all clones waiting at that moment; they register again when they poll.
+pub(crate) struct WaitingClones {
+    wakers: Mutex<BTreeMap<usize, Waker>>,
+    poll_counters: Arc<PollCounters>,
 }
 
-impl Wake for MultiWaker {
+impl WaitingClones {
+    fn new(poll_counters: Arc<PollCounters>) -> Self {
+        Self {
+            wakers: Mutex::default(),
+            poll_counters,
+        }
+    }
+
+    fn insert(&self, clone_id: usize, waker: Waker) {
+        self.wakers
+            .lock()
+            .expect("Waiting clones lock poisoned")
+            .insert(clone_id, waker);
+    }
+
+    fn remove(&self, clone_id: usize) {
+        self.wakers
+            .lock()
+            .expect("Waiting clones lock poisoned")
+            .remove(&clone_id);
+    }
+}
+
+impl Wake for WaitingClones {
     fn wake(self: Arc<Self>) {
-        warn!("New data arrived in source stream, waking up sleeping clones.");
-        self.wakers.iter().for_each(Waker::wake_by_ref);
+        self.wake_by_ref();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        let wakers: WakerSet =
+            mem::take(&mut *self.wakers.lock().expect("Waiting clones lock poisoned"))
+                .into_values()
+                .collect();
+        trace!("Waking {} distinct waiting clone wakers.", wakers.len());
+        for waker in wakers {
+            self.poll_counters.record_wakeup();
+            waker.wake();
+        }
     }
 }
@@ -0,0 +1,81 @@
+use futures::{Stream, StreamExt, future};
+
+use crate::CloneStream;
+
+/// Clones of the same fork that are consumed together.
+///
+/// Created with [`CloneStream::group`].
+pub struct ForkGroup<BaseStream>
+where
+    BaseStream: Stream<Item: Clone>,
+{
+    clones: Vec<CloneStream<BaseStream>>,
+}
+
+/// Number of items queued for each clone of a [`ForkGroup`], returned by
+/// [`ForkGroup::stats`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GroupStats {
+    /// Items queued for each clone, in the order of the group.
+    pub queued_items: Vec<usize>,
+}
+
+impl<BaseStream> ForkGroup<BaseStream>
+where
+    BaseStream: Stream<Item: Clone>,
+{
+    pub(crate) fn new(clones: Vec<CloneStream<BaseStream>>) -> Self {
+        Self { clones }
+    }
+
+    /// Consumes every clone of the group concurrently until it ends, and
+    /// returns the items of each clone in the order of the group.
+    pub async fn drain_all(&mut self) -> Vec<Vec<BaseStream::Item>> {
+        future::join_all(
+            self.clones
+                .iter_mut()
+                .map(|clone| clone.by_ref().collect::<Vec<_>>()),
+        )
+        .await
+    }
+
+    /// Closes every clone of the group, like [`CloneStream::close`].
+    pub fn close_all(&mut self) {
+        self.clones.iter_mut().for_each(CloneStream::close);
+    }
+
+    /// Returns how many items are queued for each clone.
+    #[must_use]
+    pub fn stats(&self) -> GroupStats {
+        GroupStats {
+            queued_items: self
+                .clones
+                .iter()
+                .map(CloneStream::n_queued_items)
+                .collect(),
+        }
+    }
+
+    /// Returns the clones of the group.
+    pub fn clones_mut(&mut self) -> &mut [CloneStream<BaseStream>] {
+        &mut self.clones
+    }
+
+    /// Splits the group into its clones.
+    #[must_use]
+    pub fn into_clones(self) -> Vec<CloneStream<BaseStream>> {
+        self.clones
+    }
+
+    /// Returns the number of clones in the group.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.clones.len()
+    }
+
+    /// Checks whether the group has no clones.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.clones.is_empty()
+    }
+}
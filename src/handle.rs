@@ -0,0 +1,47 @@
+use std::sync::{Arc, Mutex};
+
+use futures::Stream;
+
+use crate::{CloneStream, fork::Fork};
+
+/// Access to a forked stream that is not itself a consumer.
+///
+/// A handle is obtained with [`CloneStream::handle`]. Unlike a
+/// [`CloneStream`], it is not registered as a clone, so it never holds back
+/// items in the queue. It keeps the base stream alive and can create new
+/// consumers on demand with [`SharedHandle::subscribe`].
+pub struct SharedHandle<BaseStream>
+where
+    BaseStream: Stream<Item: Clone>,
+{
+    fork: Arc<Mutex<Fork<BaseStream>>>,
+}
+
+impl<BaseStream> SharedHandle<BaseStream>
+where
+    BaseStream: Stream<Item: Clone>,
+{
+    pub(crate) fn new(fork: Arc<Mutex<Fork<BaseStream>>>) -> Self {
+        Self { fork }
+    }
+
+    /// Registers a new clone of the stream.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the maximum number of clones has been exceeded for this
+    /// stream.
+    #[must_use]
+    pub fn subscribe(&self) -> CloneStream<BaseStream> {
+        CloneStream::register(&self.fork)
+    }
+}
+
+impl<BaseStream> Clone for SharedHandle<BaseStream>
+where
+    BaseStream: Stream<Item: Clone>,
+{
+    fn clone(&self) -> Self {
+        Self::new(self.fork.clone())
+    }
+}
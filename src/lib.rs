@@ -22,19 +22,81 @@
 //! // Both clones receive all items independently
 //! # }
 //! ```
+//!
+//! # Delivery
+//!
+//! A clone receives the items the base stream produces while it waits for
+//! one, and items queued while it still had queued items to receive. A clone
+//! that has caught up and is not polled skips the items produced in the
+//! meantime. Set [`ForkConfig::deliver_to_idle_clones`] to queue every item
+//! produced after a clone was created for that clone, up to
+//! [`ForkConfig::max_queue_size`].
+mod abortable;
+mod ack;
+#[cfg(feature = "test-util")]
+mod audit;
+mod barrier;
+#[cfg(feature = "broadcast")]
+mod broadcast;
 pub mod clean_log;
+mod clock;
 mod clone;
+mod credits;
+mod dedup;
 mod error;
 mod fork;
+mod group;
+mod handle;
+mod lossy;
+mod metrics;
+mod reentrancy;
 mod registry;
+mod replay;
 pub mod ring_queue;
+mod scheduler;
+mod sequence;
+mod shard;
 mod states;
+mod trace;
+mod try_clone;
+mod waker_set;
+#[cfg(feature = "watch")]
+mod watch;
+
+use std::{iter, pin::Pin, sync::Arc};
 
-pub use clone::CloneStream;
-pub use error::{CloneStreamError, Result};
+pub use abortable::AbortableCloneStream;
+pub use ack::AckToken;
+#[cfg(feature = "test-util")]
+pub use audit::DeliveryAuditor;
+pub use barrier::Barrier;
+#[cfg(feature = "broadcast")]
+pub use broadcast::{BroadcastBridge, ForkReceiver};
+pub use clock::{Clock, SystemClock};
+pub use clone::{BoxedCloneStream, CloneStream};
+use credits::Credits;
+pub use dedup::DedupCloneStream;
+pub use error::{CloneStreamError, ConfigError, Lagged, Result, TryRecvError};
 use fork::Fork;
-pub use fork::ForkConfig;
-use futures::Stream;
+pub use fork::{BacklogOrder, ForkConfig, QueueBackend};
+use futures::{
+    Stream, StreamExt,
+    future::{self, Either},
+    stream,
+};
+pub use group::{ForkGroup, GroupStats};
+pub use handle::SharedHandle;
+pub use lossy::LossyCloneStream;
+#[cfg(feature = "metrics")]
+pub use metrics::PollMetrics;
+use replay::Replay;
+use sequence::SequenceTracker;
+use shard::Router;
+pub use shard::Shard;
+pub use trace::{PollEvent, PollEventKind, PollOutcome, Readiness};
+pub use try_clone::TryCloneStream;
+#[cfg(feature = "watch")]
+pub use watch::ForkWatch;
 
 /// Extension trait to make any [`Stream`] cloneable.
 pub trait ForkStream: Stream<Item: Clone> + Sized {
@@ -70,9 +132,506 @@ pub trait ForkStream: Stream<Item: Clone> + Sized {
         let config = ForkConfig {
             max_clone_count,
             max_queue_size,
+            ..ForkConfig::default()
         };
         CloneStream::from(Fork::with_config(self, config))
     }
+
+    /// Creates a cloneable stream with a custom [`ForkConfig`].
+    ///
+    /// ```rust
+    /// use clone_stream::{ForkConfig, ForkStream};
+    /// use futures::stream;
+    ///
+    /// let config = ForkConfig {
+    ///     record_poll_order: true,
+    ///     ..ForkConfig::default()
+    /// };
+    /// let stream = stream::iter(0..3).fork_with_config(config);
+    /// ```
+    fn fork_with_config(self, config: ForkConfig) -> CloneStream<Self> {
+        CloneStream::from(Fork::with_config(self, config))
+    }
+
+    /// Creates a cloneable stream with a custom [`ForkConfig`], after checking
+    /// it with [`ForkConfig::validate`].
+    ///
+    /// # Errors
+    ///
+    /// Returns every problem with `config` and drops the stream when the
+    /// configuration is invalid.
+    ///
+    /// ```rust
+    /// use clone_stream::{ConfigError, ForkConfig, ForkStream};
+    /// use futures::stream;
+    ///
+    /// let config = ForkConfig {
+    ///     max_queue_size: 0,
+    ///     ..ForkConfig::default()
+    /// };
+    /// let result = stream::iter(0..3).try_fork_with_config(config);
+    /// assert_eq!(result.err(), Some(vec![ConfigError::ZeroQueueSize]));
+    /// ```
+    fn try_fork_with_config(
+        self,
+        config: ForkConfig,
+    ) -> std::result::Result<CloneStream<Self>, Vec<ConfigError>> {
+        config.validate()?;
+        Ok(self.fork_with_config(config))
+    }
+
+    /// Boxes the stream and creates a cloneable stream whose type does not
+    /// depend on the type of this stream.
+    ///
+    /// ```rust
+    /// use clone_stream::{BoxedCloneStream, ForkStream};
+    /// use futures::stream;
+    ///
+    /// fn numbers() -> BoxedCloneStream<i32> {
+    ///     stream::iter(0..3).fork_boxed()
+    /// }
+    /// ```
+    fn fork_boxed(self) -> BoxedCloneStream<Self::Item>
+    where
+        Self: Send + 'static,
+    {
+        let boxed: Pin<Box<dyn Stream<Item = Self::Item> + Send>> = Box::pin(self);
+        boxed.fork()
+    }
+
+    /// Groups every `n` items into a [`Vec`] and creates a cloneable stream
+    /// of those batches.
+    ///
+    /// The grouping happens once before the items are shared, so every clone
+    /// receives the same batches. A partial batch is yielded when the stream
+    /// ends. Forks with [`ForkConfig::deliver_to_idle_clones`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is zero.
+    ///
+    /// ```rust
+    /// use clone_stream::ForkStream;
+    /// use futures::{StreamExt, executor::block_on, stream};
+    ///
+    /// let batches = stream::iter(0..5).fork_chunks(2);
+    /// assert_eq!(
+    ///     block_on(batches.collect::<Vec<_>>()),
+    ///     vec![vec![0, 1], vec![2, 3], vec![4]]
+    /// );
+    /// ```
+    fn fork_chunks(self, n: usize) -> CloneStream<stream::Chunks<Self>> {
+        self.chunks(n)
+            .fork_with_config(ForkConfig::delivering_to_idle_clones())
+    }
+
+    /// Folds the items into a shared accumulator and creates a cloneable
+    /// stream of the outputs of `step`.
+    ///
+    /// `step` runs exactly once per item of this stream, no matter how many
+    /// clones exist, so every clone sees the same accumulated view. Running a
+    /// scan on each clone instead would keep separate state per clone. Forks
+    /// with [`ForkConfig::deliver_to_idle_clones`].
+    ///
+    /// ```rust
+    /// use clone_stream::ForkStream;
+    /// use futures::{StreamExt, executor::block_on, stream};
+    ///
+    /// let sums = stream::iter(1..=3).fork_scan(0, |sum, item| {
+    ///     *sum += item;
+    ///     *sum
+    /// });
+    /// assert_eq!(block_on(sums.collect::<Vec<_>>()), vec![1, 3, 6]);
+    /// ```
+    fn fork_scan<State, Output, Step>(
+        self,
+        init: State,
+        mut step: Step,
+    ) -> CloneStream<impl Stream<Item = Output>>
+    where
+        Output: Clone,
+        Step: FnMut(&mut State, Self::Item) -> Output,
+    {
+        let mut state = init;
+        self.map(move |item| step(&mut state, item))
+            .fork_with_config(ForkConfig::delivering_to_idle_clones())
+    }
+
+    /// Applies a fallible mapping once per item and creates a cloneable
+    /// stream of the results.
+    ///
+    /// Because `map` runs on the shared path, every clone sees the same `Ok`
+    /// or `Err` for a given item. An `Err` does not end the stream; consumers
+    /// decide themselves whether to stop on the first error. Forks with
+    /// [`ForkConfig::deliver_to_idle_clones`].
+    ///
+    /// ```rust
+    /// use clone_stream::ForkStream;
+    /// use futures::{StreamExt, executor::block_on, stream};
+    ///
+    /// let parsed = stream::iter(["1", "x", "3"]).fork_try_map(|text| text.parse::<u8>());
+    /// let results = block_on(parsed.collect::<Vec<_>>());
+    /// assert_eq!(results[0], Ok(1));
+    /// assert!(results[1].is_err());
+    /// assert_eq!(results[2], Ok(3));
+    /// ```
+    fn fork_try_map<Output, Error, Map>(
+        self,
+        map: Map,
+    ) -> CloneStream<impl Stream<Item = std::result::Result<Output, Error>>>
+    where
+        Output: Clone,
+        Error: Clone,
+        Map: Fn(&Self::Item) -> std::result::Result<Output, Error>,
+    {
+        self.map(move |item| map(&item))
+            .fork_with_config(ForkConfig::delivering_to_idle_clones())
+    }
+
+    /// Creates a cloneable stream that yields one summary after the last
+    /// item.
+    ///
+    /// Items are wrapped in [`Either::Left`]. When this stream ends,
+    /// `summarize` receives the number of items it yielded and its result is
+    /// broadcast as [`Either::Right`] before `None`. The summary is built once
+    /// and every clone receives it exactly once. Forks with
+    /// [`ForkConfig::deliver_to_idle_clones`].
+    ///
+    /// ```rust
+    /// use clone_stream::ForkStream;
+    /// use futures::{StreamExt, executor::block_on, future::Either, stream};
+    ///
+    /// let summarized = stream::iter(["a", "b"]).fork_with_summary(|count| count);
+    /// let items = block_on(summarized.collect::<Vec<_>>());
+    /// assert!(matches!(
+    ///     items[..],
+    ///     [Either::Left("a"), Either::Left("b"), Either::Right(2)]
+    /// ));
+    /// ```
+    fn fork_with_summary<Summary, Summarize>(
+        self,
+        summarize: Summarize,
+    ) -> CloneStream<impl Stream<Item = Either<Self::Item, Summary>>>
+    where
+        Summary: Clone,
+        Summarize: FnOnce(usize) -> Summary,
+    {
+        self.map(Some)
+            .chain(stream::once(future::ready(None)))
+            .scan((0, Some(summarize)), |(count, summarize), item| {
+                future::ready(match item {
+                    Some(item) => {
+                        *count += 1;
+                        Some(Either::Left(item))
+                    }
+                    None => summarize
+                        .take()
+                        .map(|summarize| Either::Right(summarize(*count))),
+                })
+            })
+            .fork_with_config(ForkConfig::delivering_to_idle_clones())
+    }
+
+    /// Creates a cloneable stream whose clones skip consecutive equal items.
+    ///
+    /// Useful for state streams that re-emit unchanged values. Every clone
+    /// receives every item and compares it with the item it yielded last, so
+    /// clones that started at different positions may skip different items.
+    /// Forks with [`ForkConfig::deliver_to_idle_clones`].
+    ///
+    /// ```rust
+    /// use clone_stream::ForkStream;
+    /// use futures::{StreamExt, executor::block_on, stream};
+    ///
+    /// let stream = stream::iter([1, 1, 2, 1]).fork_dedup();
+    /// let items: Vec<_> = block_on(stream.collect());
+    /// assert_eq!(items, vec![1, 2, 1]);
+    /// ```
+    fn fork_dedup(self) -> DedupCloneStream<Self>
+    where
+        Self::Item: PartialEq,
+    {
+        DedupCloneStream::new(self.fork_with_config(ForkConfig::delivering_to_idle_clones()))
+    }
+
+    /// Creates a cloneable stream whose clones yield their queued backlog
+    /// newest first.
+    ///
+    /// Items are still shared between clones and every clone receives every
+    /// item, but a clone that fell behind yields the items queued for it from
+    /// newest to oldest. See [`BacklogOrder::NewestFirst`]. Forks with
+    /// [`ForkConfig::deliver_to_idle_clones`].
+    ///
+    /// ```rust
+    /// use clone_stream::ForkStream;
+    /// use futures::{StreamExt, executor::block_on, stream};
+    ///
+    /// let mut fast = stream::iter(1..=3).fork_lifo();
+    /// let slow = fast.clone();
+    /// block_on(async {
+    ///     for _ in 0..3 {
+    ///         fast.next().await;
+    ///     }
+    ///     assert_eq!(slow.collect::<Vec<_>>().await, vec![3, 2, 1]);
+    /// });
+    /// ```
+    fn fork_lifo(self) -> CloneStream<Self> {
+        let config = ForkConfig {
+            backlog_order: BacklogOrder::NewestFirst,
+            ..ForkConfig::delivering_to_idle_clones()
+        };
+        self.fork_with_config(config)
+    }
+
+    /// Creates a cloneable stream of items that carry their own sequence
+    /// numbers, such as network packets.
+    ///
+    /// `sequence_of` extracts the number of each item. Numbers the base
+    /// stream skips are reported by [`CloneStream::missing_sequences`],
+    /// independent of items evicted from the queue. Numbers are expected to
+    /// increase; a skipped number that arrives late is no longer missing.
+    ///
+    /// ```rust
+    /// use clone_stream::ForkStream;
+    /// use futures::{StreamExt, executor::block_on, stream};
+    ///
+    /// let mut stream = stream::iter([1, 2, 5]).fork_sequenced(|item| *item);
+    /// block_on(stream.by_ref().count());
+    /// assert_eq!(stream.last_sequence(), Some(5));
+    /// assert_eq!(stream.missing_sequences(), vec![3..5]);
+    /// ```
+    fn fork_sequenced<Sequence>(self, sequence_of: Sequence) -> CloneStream<Self>
+    where
+        Sequence: Fn(&Self::Item) -> u64 + Send + Sync + 'static,
+    {
+        let mut fork = Fork::new(self);
+        fork.sequence_tracker = Some(SequenceTracker::new(sequence_of));
+        CloneStream::from(fork)
+    }
+
+    /// Creates a cloneable stream that replays the last `replay` items to
+    /// clones created later.
+    ///
+    /// A new clone first receives the most recent items, even when every
+    /// existing clone consumed them already, and then every item produced
+    /// after it was created. Forks with [`ForkConfig::deliver_to_idle_clones`].
+    ///
+    /// ```rust
+    /// use clone_stream::ForkStream;
+    /// use futures::{StreamExt, executor::block_on, stream};
+    ///
+    /// let mut first = stream::iter(0..5).fork_with_replay(2);
+    /// assert_eq!(block_on(first.by_ref().take(4).count()), 4);
+    /// let late = first.clone();
+    /// assert_eq!(block_on(late.collect::<Vec<_>>()), vec![2, 3, 4]);
+    /// ```
+    fn fork_with_replay(self, replay: usize) -> CloneStream<Self> {
+        let mut fork = Fork::with_config(self, ForkConfig::delivering_to_idle_clones());
+        fork.replay = Some(Replay::new(replay));
+        CloneStream::from(fork)
+    }
+
+    /// Creates a cloneable stream whose clones receive items only on demand.
+    ///
+    /// Each clone starts without credits and receives one item per credit
+    /// granted with [`CloneStream::grant_credits`], like demand signalling in
+    /// reactive streams. The base stream is not polled on behalf of a clone
+    /// without credits, and items stay queued for it until it has credits.
+    /// Forks with [`ForkConfig::deliver_to_idle_clones`].
+    ///
+    /// ```rust
+    /// use clone_stream::ForkStream;
+    /// use futures::{FutureExt, StreamExt, stream};
+    ///
+    /// let mut stream = stream::iter(0..3).fork_with_credits();
+    /// assert_eq!(stream.next().now_or_never(), None);
+    /// stream.grant_credits(1);
+    /// assert_eq!(stream.next().now_or_never(), Some(Some(0)));
+    /// ```
+    fn fork_with_credits(self) -> CloneStream<Self> {
+        let mut fork = Fork::with_config(self, ForkConfig::delivering_to_idle_clones());
+        fork.credits = Some(Credits::default());
+        CloneStream::from(fork)
+    }
+
+    /// Creates a cloneable stream that reads the current time from `clock`.
+    ///
+    /// Time-based behaviour such as [`CloneStream::readiness`] then follows
+    /// `clock` instead of the system clock, so tests can advance time without
+    /// sleeping.
+    ///
+    /// ```rust
+    /// use clone_stream::{ForkStream, SystemClock};
+    /// use futures::stream;
+    ///
+    /// let stream = stream::iter(0..3).fork_with_clock(SystemClock);
+    /// ```
+    fn fork_with_clock<Source>(self, clock: Source) -> CloneStream<Self>
+    where
+        Source: Clock + 'static,
+    {
+        let mut fork = Fork::new(self);
+        fork.clock = Arc::new(clock);
+        CloneStream::from(fork)
+    }
+
+    /// Creates a cloneable stream and returns exactly `n` clones of it.
+    ///
+    /// Unlike calling [`ForkStream::fork`] and cloning the result `n` times,
+    /// no extra clone stays registered. When `n` is zero, the stream is
+    /// dropped and an empty vector is returned.
+    ///
+    /// # Panics
+    ///
+    /// When `n` exceeds the default clone limit.
+    ///
+    /// ```rust
+    /// use clone_stream::ForkStream;
+    /// use futures::stream;
+    ///
+    /// let clones = stream::iter(0..3).fork_with_initial_clones(4);
+    /// assert_eq!(clones.len(), 4);
+    /// ```
+    fn fork_with_initial_clones(self, n: usize) -> Vec<CloneStream<Self>> {
+        if n == 0 {
+            return Vec::new();
+        }
+        let first = self.fork();
+        let others: Vec<_> = (1..n).map(|_| first.clone()).collect();
+        iter::once(first).chain(others).collect()
+    }
+
+    /// Creates a cloneable version of a fallible stream whose clones end after
+    /// the first error.
+    ///
+    /// Every clone receives every item up to the first error, so all
+    /// consumers observe the failure. Forks with
+    /// [`ForkConfig::deliver_to_idle_clones`].
+    ///
+    /// ```rust
+    /// use clone_stream::ForkStream;
+    /// use futures::{StreamExt, executor::block_on, stream};
+    ///
+    /// let stream = stream::iter([Ok(1), Err("failed"), Ok(2)]).fork_try();
+    /// let items: Vec<_> = block_on(stream.collect());
+    /// assert_eq!(items, vec![Ok(1), Err("failed")]);
+    /// ```
+    fn fork_try<Item, Error>(self) -> TryCloneStream<Self>
+    where
+        Self: Stream<Item = std::result::Result<Item, Error>>,
+    {
+        TryCloneStream::new(self.fork_with_config(ForkConfig::delivering_to_idle_clones()))
+    }
+
+    /// Creates a cloneable stream whose clones report the items they missed.
+    ///
+    /// When the queue is full, the oldest queued item is evicted even if a
+    /// slow clone has not received it yet. Clones created by this method then
+    /// yield `Err(Lagged(n))` with the number `n` of missed items, followed
+    /// by the next item they can still receive.
+    ///
+    /// ```rust
+    /// use clone_stream::{ForkConfig, ForkStream, Lagged};
+    /// use futures::{StreamExt, executor::block_on, stream};
+    ///
+    /// let config = ForkConfig {
+    ///     max_queue_size: 1,
+    ///     ..ForkConfig::delivering_to_idle_clones()
+    /// };
+    /// let mut slow = stream::iter(0..4).fork_lossy_reporting(config);
+    /// let mut fast = slow.clone();
+    /// block_on(async {
+    ///     assert_eq!(slow.next().await, Some(Ok(0)));
+    ///     for _ in 0..4 {
+    ///         fast.next().await;
+    ///     }
+    ///     assert_eq!(slow.next().await, Some(Err(Lagged(2))));
+    ///     assert_eq!(slow.next().await, Some(Ok(3)));
+    /// });
+    /// ```
+    fn fork_lossy_reporting(self, config: ForkConfig) -> LossyCloneStream<Self> {
+        LossyCloneStream::new(self.enumerate().fork_with_config(config))
+    }
+
+    /// Creates a cloneable stream of byte chunks.
+    ///
+    /// Cloning [`Bytes`](bytes::Bytes) only increments a reference count, so
+    /// every clone receives the same chunks without copying their contents.
+    /// See also [`CloneStream::buffered_bytes`]. Forks with
+    /// [`ForkConfig::deliver_to_idle_clones`].
+    ///
+    /// ```rust
+    /// use bytes::Bytes;
+    /// use clone_stream::ForkStream;
+    /// use futures::{StreamExt, executor::block_on, stream};
+    ///
+    /// let first = stream::iter([Bytes::from_static(b"chunk")]).fork_bytes();
+    /// let second = first.clone();
+    /// let (first, second) = block_on(futures::future::join(
+    ///     first.collect::<Vec<_>>(),
+    ///     second.collect::<Vec<_>>(),
+    /// ));
+    /// assert_eq!(first[0].as_ptr(), second[0].as_ptr());
+    /// ```
+    #[cfg(feature = "bytes")]
+    fn fork_bytes(self) -> CloneStream<Self>
+    where
+        Self: Stream<Item = bytes::Bytes>,
+    {
+        self.fork_with_config(ForkConfig::delivering_to_idle_clones())
+    }
+
+    /// Splits this stream into `shards` consumers that each receive only the
+    /// items routed to them.
+    ///
+    /// Every item goes to exactly one shard: the one at index
+    /// `route(&item) % shards`. Items are only queued for the shard they are
+    /// routed to, so a slow shard does not keep the items of other shards
+    /// queued. Forks with [`ForkConfig::deliver_to_idle_clones`]. When
+    /// `shards` is zero, the stream is dropped and an empty vector is
+    /// returned.
+    ///
+    /// # Panics
+    ///
+    /// When `shards` exceeds the default clone limit.
+    ///
+    /// ```rust
+    /// use clone_stream::ForkStream;
+    /// use futures::{StreamExt, executor::block_on, stream};
+    ///
+    /// let mut shards = stream::iter(0..4).fork_sharded(2, |x| *x);
+    /// let odd = shards.pop().unwrap();
+    /// let even = shards.pop().unwrap();
+    /// let (even, odd) = block_on(futures::future::join(
+    ///     even.collect::<Vec<_>>(),
+    ///     odd.collect::<Vec<_>>(),
+    /// ));
+    /// assert_eq!(even, vec![0, 2]);
+    /// assert_eq!(odd, vec![1, 3]);
+    /// ```
+    fn fork_sharded<Route>(self, shards: usize, route: Route) -> Vec<Shard<Self, Route>>
+    where
+        Route: Fn(&Self::Item) -> usize + Send + Sync + 'static,
+    {
+        let route = Arc::new(route);
+        let clones = self.fork_with_initial_clones(shards);
+        if let Some(first) = clones.first() {
+            let mut fork = first
+                .fork
+                .lock()
+                .expect("Fork lock poisoned during fork_sharded");
+            fork.config.deliver_to_idle_clones = true;
+            fork.router = Some(Router::new(
+                route.clone(),
+                clones.iter().map(|clone| clone.id).collect(),
+            ));
+        }
+        clones
+            .into_iter()
+            .enumerate()
+            .map(|(index, clone)| Shard::new(clone, route.clone(), index, shards))
+            .collect()
+    }
 }
 
 impl<BaseStream> ForkStream for BaseStream where BaseStream: Stream<Item: Clone> {}
@@ -0,0 +1,80 @@
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use futures::{Stream, StreamExt, ready, stream::Enumerate};
+
+use crate::{CloneStream, Lagged};
+
+/// A clone that reports items it missed because the queue was full.
+///
+/// Created with
+/// [`ForkStream::fork_lossy_reporting`](crate::ForkStream::fork_lossy_reporting).
+/// When items this clone still needed were evicted from the queue, it yields
+/// [`Lagged`] with the number of missed items before resuming with the next
+/// item it can still receive.
+pub struct LossyCloneStream<BaseStream>
+where
+    BaseStream: Stream<Item: Clone>,
+{
+    clone: CloneStream<Enumerate<BaseStream>>,
+    /// Index in the base stream of the item this clone expects next, once it
+    /// has received an item.
+    expected_index: Option<usize>,
+    /// Item that was held back to report the items missed before it.
+    held_back: Option<BaseStream::Item>,
+}
+
+// The held back item is never pinned.
+impl<BaseStream> Unpin for LossyCloneStream<BaseStream> where BaseStream: Stream<Item: Clone> {}
+
+impl<BaseStream> LossyCloneStream<BaseStream>
+where
+    BaseStream: Stream<Item: Clone>,
+{
+    pub(crate) fn new(clone: CloneStream<Enumerate<BaseStream>>) -> Self {
+        Self {
+            clone,
+            expected_index: None,
+            held_back: None,
+        }
+    }
+}
+
+impl<BaseStream> Clone for LossyCloneStream<BaseStream>
+where
+    BaseStream: Stream<Item: Clone>,
+{
+    /// Creates a new clone. It starts at the oldest queued item and only
+    /// reports items it misses from then on.
+    fn clone(&self) -> Self {
+        Self::new(self.clone.clone())
+    }
+}
+
+impl<BaseStream> Stream for LossyCloneStream<BaseStream>
+where
+    BaseStream: Stream<Item: Clone>,
+{
+    type Item = Result<BaseStream::Item, Lagged>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if let Some(item) = self.held_back.take() {
+            return Poll::Ready(Some(Ok(item)));
+        }
+        let Some((index, item)) = ready!(self.clone.poll_next_unpin(cx)) else {
+            return Poll::Ready(None);
+        };
+        let missed = self
+            .expected_index
+            .map_or(0, |expected| index.saturating_sub(expected));
+        self.expected_index = Some(index + 1);
+        if missed == 0 {
+            Poll::Ready(Some(Ok(item)))
+        } else {
+            self.held_back = Some(item);
+            Poll::Ready(Some(Err(Lagged(missed as u64))))
+        }
+    }
+}
@@ -0,0 +1,103 @@
+//! Counters of how efficiently clones are polled and woken.
+//!
+//! Counting only happens with the `metrics` feature. Without it, the counters
+//! are empty and recording is a no-op. Item clones are only counted in debug
+//! builds.
+
+#[cfg(feature = "metrics")]
+use std::{
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
+
+/// Time waiting for the fork lock after which the wait counts as slow.
+#[cfg(feature = "metrics")]
+const SLOW_LOCK_WAIT: Duration = Duration::from_millis(1);
+
+/// Poll counters of a fork, returned by
+/// [`CloneStream::poll_metrics`](crate::CloneStream::poll_metrics).
+#[cfg(feature = "metrics")]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PollMetrics {
+    /// Number of polls of any clone.
+    pub polls: u64,
+    /// Number of polls of a clone that was already waiting on the base stream
+    /// and stayed pending without any change of its state.
+    pub spurious_polls: u64,
+    /// Number of clone wakers woken by the base stream.
+    pub wakeups: u64,
+    /// Number of polls that had to wait because another clone held the fork
+    /// lock.
+    pub lock_waits: u64,
+    /// Number of lock waits that took longer than a millisecond. Many of them
+    /// point at too many clones contending for the same fork.
+    pub slow_lock_waits: u64,
+}
+
+/// Counters shared by a fork and the wakers it hands to the base stream.
+#[derive(Debug, Default)]
+pub(crate) struct PollCounters {
+    #[cfg(feature = "metrics")]
+    polls: AtomicU64,
+    #[cfg(feature = "metrics")]
+    spurious_polls: AtomicU64,
+    #[cfg(feature = "metrics")]
+    wakeups: AtomicU64,
+    #[cfg(feature = "metrics")]
+    lock_waits: AtomicU64,
+    #[cfg(feature = "metrics")]
+    slow_lock_waits: AtomicU64,
+    #[cfg(all(debug_assertions, feature = "metrics"))]
+    item_clones: AtomicU64,
+}
+
+#[cfg_attr(not(feature = "metrics"), allow(clippy::unused_self))]
+impl PollCounters {
+    pub(crate) fn record_poll(&self) {
+        #[cfg(feature = "metrics")]
+        self.polls.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_spurious_poll(&self) {
+        #[cfg(feature = "metrics")]
+        self.spurious_polls.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_wakeup(&self) {
+        #[cfg(feature = "metrics")]
+        self.wakeups.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Records that the fork cloned `count` items.
+    #[cfg_attr(not(debug_assertions), allow(clippy::unused_self))]
+    pub(crate) fn record_item_clones(&self, count: usize) {
+        #[cfg(all(debug_assertions, feature = "metrics"))]
+        self.item_clones.fetch_add(count as u64, Ordering::Relaxed);
+        #[cfg(not(all(debug_assertions, feature = "metrics")))]
+        let _ = count;
+    }
+
+    #[cfg(all(debug_assertions, feature = "metrics"))]
+    pub(crate) fn item_clones(&self) -> u64 {
+        self.item_clones.load(Ordering::Relaxed)
+    }
+
+    #[cfg(feature = "metrics")]
+    pub(crate) fn record_lock_wait(&self, waited: Duration) {
+        self.lock_waits.fetch_add(1, Ordering::Relaxed);
+        if waited > SLOW_LOCK_WAIT {
+            self.slow_lock_waits.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    #[cfg(feature = "metrics")]
+    pub(crate) fn snapshot(&self) -> PollMetrics {
+        PollMetrics {
+            polls: self.polls.load(Ordering::Relaxed),
+            spurious_polls: self.spurious_polls.load(Ordering::Relaxed),
+            wakeups: self.wakeups.load(Ordering::Relaxed),
+            lock_waits: self.lock_waits.load(Ordering::Relaxed),
+            slow_lock_waits: self.slow_lock_waits.load(Ordering::Relaxed),
+        }
+    }
+}
@@ -0,0 +1,41 @@
+//! Detection of clones that are polled from inside the base stream of their
+//! own fork.
+//!
+//! The base stream is polled while the fork lock is held, so such a clone
+//! would deadlock on the same lock.
+
+use std::cell::RefCell;
+
+thread_local! {
+    /// Addresses of the forks that are being polled on this thread.
+    static POLLING_FORKS: RefCell<Vec<usize>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Marks a fork as being polled on the current thread until it is dropped.
+pub(crate) struct PollingGuard {
+    fork_address: usize,
+}
+
+impl PollingGuard {
+    /// Returns `None` when the fork is already being polled on this thread.
+    pub(crate) fn enter(fork_address: usize) -> Option<Self> {
+        POLLING_FORKS.with_borrow_mut(|forks| {
+            if forks.contains(&fork_address) {
+                None
+            } else {
+                forks.push(fork_address);
+                Some(Self { fork_address })
+            }
+        })
+    }
+}
+
+impl Drop for PollingGuard {
+    fn drop(&mut self) {
+        POLLING_FORKS.with_borrow_mut(|forks| {
+            if let Some(position) = forks.iter().rposition(|&a| a == self.fork_address) {
+                forks.swap_remove(position);
+            }
+        });
+    }
+}
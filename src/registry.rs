@@ -1,10 +1,12 @@
-use std::task::Waker;
+use std::cmp::Ordering;
 
 use log::{trace, warn};
 
 use crate::{
     error::{CloneStreamError, Result},
+    ring_queue::is_behind,
     states::CloneState,
+    waker_set::WakerSet,
 };
 
 #[derive(Debug)]
@@ -24,6 +26,10 @@ impl CloneRegistry {
     }
 
     pub(crate) fn register(&mut self) -> Result<usize> {
+        self.register_with_state(CloneState::default())
+    }
+
+    pub(crate) fn register_with_state(&mut self, initial_state: CloneState) -> Result<usize> {
         if self.count() >= self.max_clone_count {
             return Err(CloneStreamError::MaxClonesExceeded {
                 current_count: self.count(),
@@ -33,27 +39,44 @@ impl CloneRegistry {
 
         if let Some(reused_id) = self.available_indices.pop() {
             trace!("Registering clone {reused_id} (reused index).");
-            self.clones[reused_id] = Some(CloneState::default());
+            self.clones[reused_id] = Some(initial_state);
             Ok(reused_id)
         } else {
             let clone_id = self.clones.len();
             trace!("Registering clone {clone_id} (new index).");
-            self.clones.push(Some(CloneState::default()));
+            self.clones.push(Some(initial_state));
             Ok(clone_id)
         }
     }
 
     pub(crate) fn unregister(&mut self, clone_id: usize) {
         trace!("Unregistering clone {clone_id}.");
+        if self.deactivate(clone_id) {
+            self.available_indices.push(clone_id);
+        }
+        trace!("Unregister of clone {clone_id} complete.");
+    }
 
+    /// Removes the state of a clone without making its ID available again.
+    /// Returns whether the clone was registered.
+    pub(crate) fn deactivate(&mut self, clone_id: usize) -> bool {
         if !self.exists(clone_id) {
             warn!("Attempted to unregister clone {clone_id} that was not registered");
-            return;
+            return false;
         }
-
         self.clones[clone_id] = None;
-        self.available_indices.push(clone_id);
-        trace!("Unregister of clone {clone_id} complete.");
+        true
+    }
+
+    /// Makes the ID of a deactivated clone available for new clones, unless
+    /// it is in use or available already.
+    pub(crate) fn release(&mut self, clone_id: usize) {
+        if clone_id < self.clones.len()
+            && !self.exists(clone_id)
+            && !self.available_indices.contains(&clone_id)
+        {
+            self.available_indices.push(clone_id);
+        }
     }
 
     pub(crate) fn take(&mut self, clone_id: usize) -> Option<CloneState> {
@@ -84,6 +107,10 @@ impl CloneRegistry {
         self.clones.iter().filter(|s| s.is_some()).count()
     }
 
+    pub(crate) fn remaining(&self) -> usize {
+        self.max_clone_count.saturating_sub(self.count())
+    }
+
     pub(crate) fn iter_active_with_ids(&self) -> impl Iterator<Item = (usize, &CloneState)> {
         self.clones
             .iter()
@@ -97,21 +124,31 @@ impl CloneRegistry {
             .filter_map(|state_opt| state_opt.as_ref())
     }
 
-    pub(crate) fn collect_wakers_needing_base_item(&self) -> Vec<Waker> {
-        trace!("Collecting wakers for clones needing base item.");
-        self.iter_active()
-            .filter(|state| state.should_still_see_base_item())
-            .filter_map(CloneState::waker)
-            .collect()
+    /// Collects the wakers of clones waiting on the base stream. A task that
+    /// polls several clones is collected once, so it is woken once.
+    pub(crate) fn collect_wakers_needing_base_item(&self) -> WakerSet {
+        trace!("Collecting wakers for clones waiting on the base stream.");
+        self.iter_active().filter_map(CloneState::waker).collect()
     }
 
-    pub(crate) fn has_other_clones_waiting(&self, exclude_clone_id: usize) -> bool {
-        self.clones.iter().enumerate().any(|(clone_id, state_opt)| {
-            clone_id != exclude_clone_id
-                && state_opt
-                    .as_ref()
-                    .is_some_and(CloneState::should_still_see_base_item)
-        })
+    /// Collects the wakers of clones waiting on the base stream like
+    /// [`Self::collect_wakers_needing_base_item`], ordered from the clone that
+    /// has seen the fewest queued items to the one that has seen the most.
+    pub(crate) fn collect_wakers_slowest_first(&self) -> WakerSet {
+        let mut waiting: Vec<_> = self
+            .iter_active()
+            .filter_map(|state| Some((state.seen_through(), state.waker()?)))
+            .collect();
+        waiting.sort_by(|(position, _), (other_position, _)| {
+            if is_behind(*position, *other_position) {
+                Ordering::Less
+            } else if is_behind(*other_position, *position) {
+                Ordering::Greater
+            } else {
+                Ordering::Equal
+            }
+        });
+        waiting.into_iter().map(|(_, waker)| waker).collect()
     }
 
     pub(crate) fn get_clone_state(&self, clone_id: usize) -> Option<&CloneState> {
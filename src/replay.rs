@@ -0,0 +1,65 @@
+use std::collections::{BTreeMap, VecDeque};
+
+/// Most recent items of the base stream, kept for clones created later.
+///
+/// Set up by
+/// [`ForkStream::fork_with_replay`](crate::ForkStream::fork_with_replay).
+pub(crate) struct Replay<Item> {
+    capacity: usize,
+    /// Items the base stream produced last, oldest first.
+    recent: VecDeque<Item>,
+    /// Replayed items each new clone has not received yet, oldest first.
+    pending: BTreeMap<usize, VecDeque<Item>>,
+}
+
+impl<Item> Replay<Item>
+where
+    Item: Clone,
+{
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            recent: VecDeque::with_capacity(capacity),
+            pending: BTreeMap::new(),
+        }
+    }
+
+    /// Records an item of the base stream, forgetting the oldest one when
+    /// more than `capacity` items are kept.
+    pub(crate) fn record(&mut self, item: &Item) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.recent.len() == self.capacity {
+            self.recent.pop_front();
+        }
+        self.recent.push_back(item.clone());
+    }
+
+    /// Makes a new clone receive the recent items first, and returns how
+    /// many items were cloned for it.
+    pub(crate) fn start(&mut self, clone_id: usize) -> usize {
+        if !self.recent.is_empty() {
+            self.pending.insert(clone_id, self.recent.clone());
+        }
+        self.recent.len()
+    }
+
+    /// Takes the next replayed item for `clone_id`, if any is left.
+    pub(crate) fn next(&mut self, clone_id: usize) -> Option<Item> {
+        let pending = self.pending.get_mut(&clone_id)?;
+        let item = pending.pop_front();
+        if pending.is_empty() {
+            self.pending.remove(&clone_id);
+        }
+        item
+    }
+
+    pub(crate) fn remaining(&self, clone_id: usize) -> usize {
+        self.pending.get(&clone_id).map_or(0, VecDeque::len)
+    }
+
+    pub(crate) fn forget(&mut self, clone_id: usize) {
+        self.pending.remove(&clone_id);
+    }
+}
@@ -1,16 +1,64 @@
-use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+
+use log::{debug, trace, warn};
+
+use crate::QueueBackend;
+
+/// Smallest span of slots worth compacting.
+const COMPACT_MIN_SPAN: usize = 64;
+/// Compact when fewer than one in this many slots of the span hold an item.
+const COMPACT_SPARSENESS: usize = 4;
+
+/// Position of a consumer in a [`RingQueue`]: the newest item it has seen.
+///
+/// Ring slots are reused once the ring wraps around, so the sequence number of
+/// the item is kept as well. It identifies the item even after its slot has
+/// been taken by a newer one.
+///
+/// Sequence numbers are `u64` on every target and wrap around after
+/// `u64::MAX`. They are compared with serial number arithmetic, which orders
+/// items correctly as long as fewer than 2^63 items were pushed between them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct QueuePosition {
+    pub(crate) index: usize,
+    pub(crate) sequence: u64,
+}
+
+impl QueuePosition {
+    /// Checks whether this item was pushed before `other`.
+    pub(crate) fn is_before(self, other: QueuePosition) -> bool {
+        is_later_sequence(other.sequence, self.sequence)
+    }
+}
 
-use log::trace;
+/// Checks whether a consumer at position `behind` has seen fewer items than
+/// one at `ahead`. `None` is the position of a consumer that has not seen any
+/// item.
+pub(crate) fn is_behind(behind: Option<QueuePosition>, ahead: Option<QueuePosition>) -> bool {
+    match (behind, ahead) {
+        (None, Some(_)) => true,
+        (Some(behind), Some(ahead)) => behind.is_before(ahead),
+        (_, None) => false,
+    }
+}
 
 /// A ring buffer queue that wraps around at a maximum capacity.
+///
+/// Every pushed item is tagged with a sequence number that keeps increasing,
+/// independent of the slot it is stored in.
 #[derive(Debug)]
 pub(crate) struct RingQueue<T>
 where
     T: Clone,
 {
-    pub(crate) items: BTreeMap<usize, T>,
+    /// Item and its sequence number per ring slot. Grows on demand up to
+    /// `capacity` slots.
+    slots: Vec<Option<(u64, T)>>,
+    /// Which slots hold an item, to find neighbours in ring order.
+    occupied: Occupancy,
     pub(crate) oldest: Option<usize>,
     pub(crate) newest: Option<usize>,
+    next_sequence: u64,
     capacity: usize,
 }
 
@@ -18,11 +66,24 @@ impl<T> RingQueue<T>
 where
     T: Clone,
 {
+    #[cfg(test)]
     pub fn new(capacity: usize) -> Self {
+        Self::with_initial_capacity(capacity, 0, QueueBackend::default())
+    }
+
+    /// Creates a queue that evicts beyond `capacity` items but allocates
+    /// storage for `initial_capacity` items up front.
+    pub(crate) fn with_initial_capacity(
+        capacity: usize,
+        initial_capacity: usize,
+        backend: QueueBackend,
+    ) -> Self {
         Self {
-            items: BTreeMap::new(),
+            slots: Vec::with_capacity(initial_capacity.min(capacity)),
+            occupied: Occupancy::new(backend),
             oldest: None,
             newest: None,
+            next_sequence: 0,
             capacity,
         }
     }
@@ -31,70 +92,189 @@ where
         if self.capacity == 0 {
             return;
         }
+        if self.is_sparse() {
+            self.compact();
+        }
 
-        // If queue is at capacity, remove oldest item first
-        if self.items.len() >= self.capacity
-            && let Some(oldest) = self.oldest
-        {
-            self.items.remove(&oldest);
-            self.oldest = self.next_ring_index(oldest);
+        let next_index = self.newest.map_or(0, |newest| (newest + 1) % self.capacity);
+
+        // The slot after the newest item is only occupied when the ring has
+        // wrapped around onto the oldest item. Holes left by removals in the
+        // middle of the queue are skipped over, so they never get overwritten.
+        if self.take_slot(next_index).is_some() {
+            self.oldest = self.next_ring_index(next_index);
         }
 
-        if let Some(newest) = self.newest {
-            let next_index = (newest + 1) % self.capacity;
-            self.items.insert(next_index, item);
-            self.newest = Some(next_index);
+        if next_index >= self.slots.len() {
+            self.slots.resize_with(next_index + 1, || None);
+        }
+        self.slots[next_index] = Some((self.next_sequence, item));
+        self.occupied.insert(next_index);
+        self.next_sequence = self.next_sequence.wrapping_add(1);
+        self.newest = Some(next_index);
+        if self.oldest.is_none() {
+            self.oldest = Some(next_index);
+        }
+    }
 
-            // Update oldest if this is the first item after being empty
-            if self.oldest.is_none() {
-                self.oldest = Some(next_index);
+    /// Pushes an item like [`Self::push`], but when the queue is full, evicts
+    /// the oldest item for which `is_evictable` returns `true` instead of the
+    /// oldest item. When no queued item is evictable, the oldest one is
+    /// evicted anyway.
+    pub(crate) fn push_evicting(&mut self, item: T, is_evictable: impl Fn(&T) -> bool) {
+        let is_full = self
+            .newest
+            .is_some_and(|newest| self.is_occupied((newest + 1) % self.capacity));
+        if is_full {
+            let evictable = self
+                .into_iter()
+                .find(|(_, queued)| is_evictable(queued))
+                .map(|(index, _)| index);
+            match evictable {
+                Some(index) => self.evict_shifting_older(index),
+                None => warn!("Every queued item is pinned, evicting the oldest one anyway."),
             }
+        }
+        self.push(item);
+    }
+
+    /// Evicts the item at `index` and moves every older item one slot newer,
+    /// which frees the slot of the oldest item while keeping the ring order.
+    /// Consumers keep finding moved items by their sequence number.
+    fn evict_shifting_older(&mut self, index: usize) {
+        let Some(oldest) = self.oldest else {
+            return;
+        };
+        self.take_slot(index);
+        let mut to = index;
+        while to != oldest {
+            let from = (to + self.capacity - 1) % self.capacity;
+            if let Some(moved) = self.take_slot(from) {
+                self.slots[to] = Some(moved);
+                self.occupied.insert(to);
+            }
+            to = from;
+        }
+        if self.is_empty() {
+            self.oldest = None;
+            self.newest = None;
         } else {
-            // First item
-            self.newest = Some(0);
-            self.oldest = Some(0);
-            self.items.insert(0, item);
+            self.oldest = self.next_ring_index(oldest);
         }
     }
 
+    /// Removes the item at `index`, which may lie anywhere between the oldest
+    /// and newest item. Removing an item in the middle leaves a hole that
+    /// iteration and [`Self::find_next_newer_index`] skip.
     pub(crate) fn remove(&mut self, index: usize) -> Option<T> {
         if self.capacity == 0 {
             return None;
         }
-        let removed = self.items.remove(&index);
+        let (_, removed) = self.take_slot(index)?;
+        if self.is_empty() {
+            self.oldest = None;
+            self.newest = None;
+            return Some(removed);
+        }
         if Some(index) == self.oldest {
             self.oldest = self.next_ring_index(index);
         }
         if Some(index) == self.newest {
-            if self.oldest == Some(index) {
-                self.newest = None;
-            } else {
-                self.newest = self.prev_ring_index(index);
-            }
+            self.newest = self.prev_ring_index(index);
         }
-        removed
+        Some(removed)
     }
 
-    pub fn pop_oldest(&mut self) -> Option<T> {
-        if self.capacity == 0 {
-            return None;
+    /// Removes every item from the oldest one up to and including the one at
+    /// `up_to_index`, in ring order, and moves the oldest item only once.
+    pub(crate) fn drain_range(&mut self, up_to_index: usize) {
+        let Some(up_to) = self.position(up_to_index) else {
+            return;
+        };
+        let drained: Vec<usize> = self
+            .into_iter()
+            .map(|(index, _)| index)
+            .take_while(|&index| {
+                self.position(index)
+                    .is_some_and(|position| !up_to.is_before(position))
+            })
+            .collect();
+        for index in drained {
+            self.take_slot(index);
         }
-        if let Some(oldest) = self.oldest
-            && let Some(item) = self.items.remove(&oldest)
-        {
-            if self.items.is_empty() {
-                self.oldest = None;
-                self.newest = None;
-            } else {
-                self.oldest = self.next_ring_index(oldest);
-            }
-            return Some(item);
+        if self.is_empty() {
+            self.oldest = None;
+            self.newest = None;
+        } else {
+            self.oldest = self.next_ring_index(up_to_index);
         }
-        None
+    }
+
+    /// Number of slots from the oldest to the newest item in ring order,
+    /// including the holes left by removals.
+    pub(crate) fn span(&self) -> usize {
+        match (self.oldest_index(), self.newest) {
+            (Some(oldest), Some(newest)) => (newest + self.capacity - oldest) % self.capacity + 1,
+            _ => 0,
+        }
+    }
+
+    /// Checks whether most slots between the oldest and newest item are
+    /// holes.
+    fn is_sparse(&self) -> bool {
+        let span = self.span();
+        span >= COMPACT_MIN_SPAN && self.len() * COMPACT_SPARSENESS < span
+    }
+
+    /// Moves the items to the first slots, in ring order, and frees the
+    /// storage of the slots after them.
+    ///
+    /// Items keep their sequence numbers, so consumers find moved items like
+    /// after [`Self::push_evicting`].
+    pub(crate) fn compact(&mut self) {
+        debug!(
+            "Compacting {} queued items spread over {} slots.",
+            self.len(),
+            self.span()
+        );
+        let indices: Vec<usize> = self.into_iter().map(|(index, _)| index).collect();
+        let mut compacted = Vec::with_capacity(indices.len());
+        for index in indices {
+            compacted.extend(self.take_slot(index).map(Some));
+        }
+        self.occupied.fill(compacted.len());
+        self.oldest = (!compacted.is_empty()).then_some(0);
+        self.newest = compacted.len().checked_sub(1);
+        self.slots = compacted;
+    }
+
+    /// Returns where the item at `position` is now, after it may have been
+    /// moved to another slot.
+    pub(crate) fn locate(&self, position: QueuePosition) -> Option<QueuePosition> {
+        if self.position(position.index) == Some(position) {
+            return Some(position);
+        }
+        self.into_iter()
+            .filter_map(|(index, _)| self.position(index))
+            .find(|moved| moved.sequence == position.sequence)
+    }
+
+    fn take_slot(&mut self, index: usize) -> Option<(u64, T)> {
+        let taken = self.slots.get_mut(index)?.take()?;
+        self.occupied.remove(index);
+        Some(taken)
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.occupied.len()
     }
 
     pub fn is_empty(&self) -> bool {
-        self.items.is_empty()
+        self.len() == 0
+    }
+
+    fn is_occupied(&self, index: usize) -> bool {
+        self.slots.get(index).is_some_and(Option::is_some)
     }
 
     pub fn oldest_index(&self) -> Option<usize> {
@@ -102,13 +282,76 @@ where
     }
 
     pub(crate) fn clear(&mut self) {
-        self.items.clear();
+        self.slots.clear();
+        self.occupied.fill(0);
         self.oldest = None;
         self.newest = None;
     }
 
     pub fn get(&self, index: usize) -> Option<&T> {
-        self.items.get(&index)
+        self.slots.get(index)?.as_ref().map(|(_, item)| item)
+    }
+
+    pub(crate) fn position(&self, index: usize) -> Option<QueuePosition> {
+        self.slots
+            .get(index)?
+            .as_ref()
+            .map(|&(sequence, _)| QueuePosition { index, sequence })
+    }
+
+    pub(crate) fn newest_position(&self) -> Option<QueuePosition> {
+        self.position(self.newest?)
+    }
+
+    /// Checks whether the item at `index` was pushed after `last_seen`. Every
+    /// item is unseen for a consumer that has not seen any queued item yet.
+    pub(crate) fn is_unseen(&self, index: usize, last_seen: Option<QueuePosition>) -> bool {
+        match (self.position(index), last_seen) {
+            (Some(position), Some(last_seen)) => {
+                is_later_sequence(position.sequence, last_seen.sequence)
+            }
+            (Some(_), None) => true,
+            (None, _) => false,
+        }
+    }
+
+    /// Returns the index of the oldest item pushed after `last_seen`.
+    pub(crate) fn next_unseen_index(&self, last_seen: Option<QueuePosition>) -> Option<usize> {
+        match last_seen {
+            None => self.oldest_index(),
+            Some(last_seen) if self.position(last_seen.index) == Some(last_seen) => {
+                self.find_next_newer_index(last_seen.index)
+            }
+            // The last seen item has left the queue, look it up by sequence.
+            Some(last_seen) => self
+                .into_iter()
+                .map(|(index, _)| index)
+                .find(|&index| self.is_unseen(index, Some(last_seen))),
+        }
+    }
+
+    /// Returns the index of the newest item pushed after `last_seen`.
+    pub(crate) fn newest_unseen_index(&self, last_seen: Option<QueuePosition>) -> Option<usize> {
+        self.newest
+            .filter(|&newest| self.is_unseen(newest, last_seen))
+    }
+
+    /// Returns the index of the newest item pushed after `last_seen` but
+    /// before `before`.
+    pub(crate) fn newest_unseen_index_before(
+        &self,
+        last_seen: Option<QueuePosition>,
+        before: QueuePosition,
+    ) -> Option<usize> {
+        self.into_iter()
+            .map(|(index, _)| index)
+            .filter(|&index| {
+                self.is_unseen(index, last_seen)
+                    && self
+                        .position(index)
+                        .is_some_and(|position| position.is_before(before))
+            })
+            .last()
     }
 
     /// Checks if an index is within the valid range of the ring
@@ -131,8 +374,13 @@ where
             if oldest <= newest {
                 if to >= from { Some(to - from) } else { None }
             } else {
-                // Wraparound case
-                let distance = (to + self.capacity - from) % self.capacity;
+                // Wraparound case, computed without overflowing for capacities
+                // close to `usize::MAX`.
+                let distance = if to >= from {
+                    to - from
+                } else {
+                    self.capacity - from + to
+                };
                 Some(distance)
             }
         } else {
@@ -141,19 +389,36 @@ where
     }
 
     fn next_ring_index(&self, from: usize) -> Option<usize> {
-        self.items
-            .range((from + 1)..)
-            .chain(self.items.range(..from))
-            .next()
-            .map(|(k, _)| *k)
+        // Items are usually stored in consecutive slots, so walking the queue
+        // in ring order rarely needs to search for the next occupied slot.
+        let consecutive = (from + 1) % self.capacity;
+        if consecutive != from && self.is_occupied(consecutive) {
+            return Some(consecutive);
+        }
+        match &self.occupied {
+            Occupancy::Sparse(occupied) => occupied
+                .range((from + 1)..)
+                .chain(occupied.range(..from))
+                .next()
+                .copied(),
+            Occupancy::Dense(_) => ((from + 1)..self.slots.len())
+                .chain(0..from.min(self.slots.len()))
+                .find(|&index| self.is_occupied(index)),
+        }
     }
 
     fn prev_ring_index(&self, from: usize) -> Option<usize> {
-        self.items
-            .range(..from)
-            .chain(self.items.range((from + 1)..))
-            .next_back()
-            .map(|(k, _)| *k)
+        match &self.occupied {
+            Occupancy::Sparse(occupied) => occupied
+                .range(..from)
+                .chain(occupied.range((from + 1)..))
+                .next_back()
+                .copied(),
+            Occupancy::Dense(_) => (0..from.min(self.slots.len()))
+                .chain((from + 1)..self.slots.len())
+                .rev()
+                .find(|&index| self.is_occupied(index)),
+        }
     }
 
     pub(crate) fn is_newer_than(&self, maybe_newer: usize, current: usize) -> bool {
@@ -166,13 +431,21 @@ where
     pub(crate) fn find_next_newer_index(&self, current_index: usize) -> Option<usize> {
         let (oldest, newest) = (self.oldest?, self.newest?);
         trace!("Finding next newer index after {current_index}, oldest={oldest}, newest={newest}");
-        trace!("Current queue has length {:?}", self.items.len());
+        // Items are stored in ring order, so the next occupied slot after an
+        // occupied one is the next newer item.
+        if self.is_occupied(current_index) {
+            return if current_index == newest {
+                None
+            } else {
+                self.next_ring_index(current_index)
+            };
+        }
+        trace!("Current queue has length {:?}", self.len());
         // Check consecutive index first
         let next_consecutive = (current_index + 1) % self.capacity;
 
         trace!("Next consecutive index is {next_consecutive}");
-        if self.items.contains_key(&next_consecutive)
-            && self.is_newer_than(next_consecutive, current_index)
+        if self.is_occupied(next_consecutive) && self.is_newer_than(next_consecutive, current_index)
         {
             return Some(next_consecutive);
         }
@@ -191,10 +464,71 @@ where
     fn ring_indices_from(&self, start: usize) -> impl Iterator<Item = usize> + '_ {
         (0..self.capacity)
             .map(move |offset| (start + offset) % self.capacity)
-            .filter(|&idx| self.items.contains_key(&idx))
+            .filter(|&idx| self.is_occupied(idx))
+    }
+}
+
+/// Bookkeeping of the occupied slots of a [`RingQueue`], depending on its
+/// [`QueueBackend`].
+#[derive(Debug)]
+enum Occupancy {
+    /// Indices of the occupied slots.
+    Sparse(BTreeSet<usize>),
+    /// Number of occupied slots. The slots themselves are scanned.
+    Dense(usize),
+}
+
+impl Occupancy {
+    fn new(backend: QueueBackend) -> Self {
+        match backend {
+            QueueBackend::Sparse => Occupancy::Sparse(BTreeSet::new()),
+            QueueBackend::Dense => Occupancy::Dense(0),
+        }
+    }
+
+    /// Marks the empty slot at `index` as occupied.
+    fn insert(&mut self, index: usize) {
+        match self {
+            Occupancy::Sparse(occupied) => {
+                occupied.insert(index);
+            }
+            Occupancy::Dense(len) => *len += 1,
+        }
+    }
+
+    /// Marks the occupied slot at `index` as empty.
+    fn remove(&mut self, index: usize) {
+        match self {
+            Occupancy::Sparse(occupied) => {
+                occupied.remove(&index);
+            }
+            Occupancy::Dense(len) => *len -= 1,
+        }
+    }
+
+    /// Marks the first `len` slots as the only occupied ones.
+    fn fill(&mut self, len: usize) {
+        match self {
+            Occupancy::Sparse(occupied) => *occupied = (0..len).collect(),
+            Occupancy::Dense(occupied) => *occupied = len,
+        }
+    }
+
+    fn len(&self) -> usize {
+        match self {
+            Occupancy::Sparse(occupied) => occupied.len(),
+            Occupancy::Dense(len) => *len,
+        }
     }
 }
 
+/// Checks whether `sequence` was assigned after `other`, allowing for
+/// wraparound of the sequence counter.
+fn is_later_sequence(sequence: u64, other: u64) -> bool {
+    let distance = sequence.wrapping_sub(other);
+    distance != 0 && distance < 1 << 63
+}
+
 pub struct RingQueueIter<'a, T>
 where
     T: Clone,
@@ -212,7 +546,7 @@ where
         Self {
             queue,
             current_index: queue.oldest,
-            remaining_items: queue.items.len(),
+            remaining_items: queue.len(),
         }
     }
 }
@@ -229,7 +563,7 @@ where
         }
 
         if let Some(index) = self.current_index
-            && let Some(item) = self.queue.items.get(&index)
+            && let Some(item) = self.queue.get(index)
         {
             self.remaining_items -= 1;
 
@@ -276,10 +610,14 @@ mod tests {
     #[test]
     fn test_wraparound_eviction() {
         let mut queue = RingQueue::new(3);
-        
+
         queue.extend(["a", "b", "c", "d"]);
-        
-        assert_eq!(queue.oldest, Some(1), "Oldest should advance after eviction");
+
+        assert_eq!(
+            queue.oldest,
+            Some(1),
+            "Oldest should advance after eviction"
+        );
         assert_eq!(queue.newest, Some(0), "Newest should wrap to index 0");
         assert_eq!(queue.get(0), Some(&"d"), "New item at wrapped index");
     }
@@ -287,43 +625,295 @@ mod tests {
     #[test]
     fn test_ring_iteration_order() {
         let mut queue = RingQueue::new(3);
-        
+
         queue.extend(["a", "b", "c", "d"]);
-        
+
         let items: Vec<_> = queue.into_iter().map(|(_, item)| *item).collect();
-        assert_eq!(items, vec!["b", "c", "d"], "Should iterate from oldest to newest");
+        assert_eq!(
+            items,
+            vec!["b", "c", "d"],
+            "Should iterate from oldest to newest"
+        );
     }
 
     #[test]
     fn test_find_next_newer_index() {
         let mut queue = RingQueue::new(4);
-        
+
         queue.extend(["a", "b", "c", "d", "e"]);
-        
-        assert_eq!(queue.find_next_newer_index(1), Some(2), "Should find next newer after oldest");
-        assert_eq!(queue.find_next_newer_index(2), Some(3), "Should find next in sequence");
-        assert_eq!(queue.find_next_newer_index(3), Some(0), "Should wrap to newest");
+
+        assert_eq!(
+            queue.find_next_newer_index(1),
+            Some(2),
+            "Should find next newer after oldest"
+        );
+        assert_eq!(
+            queue.find_next_newer_index(2),
+            Some(3),
+            "Should find next in sequence"
+        );
+        assert_eq!(
+            queue.find_next_newer_index(3),
+            Some(0),
+            "Should wrap to newest"
+        );
     }
 
     #[test]
     fn test_is_newer_than_with_wraparound() {
         let mut queue = RingQueue::new(4);
-        
+
         queue.extend(["a", "b", "c", "d", "e"]);
-        
-        assert!(queue.is_newer_than(0, 3), "Wrapped newest should be newer than previous");
-        assert!(queue.is_newer_than(2, 1), "Index 2 should be newer than oldest index 1");
-        assert!(queue.is_newer_than(3, 2), "Index 3 should be newer than index 2");
+
+        assert!(
+            queue.is_newer_than(0, 3),
+            "Wrapped newest should be newer than previous"
+        );
+        assert!(
+            queue.is_newer_than(2, 1),
+            "Index 2 should be newer than oldest index 1"
+        );
+        assert!(
+            queue.is_newer_than(3, 2),
+            "Index 3 should be newer than index 2"
+        );
     }
 
     #[test]
     fn test_ring_distance() {
         let mut queue = RingQueue::new(4);
-        
+
         queue.extend(["a", "b", "c", "d", "e"]);
-        
+
         assert_eq!(queue.ring_distance(1, 2), Some(1), "Adjacent distance");
         assert_eq!(queue.ring_distance(3, 0), Some(1), "Wraparound distance");
         assert_eq!(queue.ring_distance(0, 1), Some(1), "Full circle distance");
     }
+
+    #[test]
+    fn test_drain_range_keeps_newer_items() {
+        let mut queue = RingQueue::new(16);
+
+        queue.extend(0..10);
+        queue.drain_range(5);
+
+        let items: Vec<_> = queue.into_iter().map(|(_, item)| *item).collect();
+        assert_eq!(
+            items,
+            (6..10).collect::<Vec<_>>(),
+            "Items up to index 5 drained"
+        );
+        assert_eq!(queue.oldest, Some(6));
+        assert_eq!(queue.newest, Some(9));
+        assert_eq!(queue.len(), 4);
+    }
+
+    #[test]
+    fn test_drain_range_across_wraparound() {
+        let mut queue = RingQueue::new(4);
+
+        queue.extend(["a", "b", "c", "d", "e", "f"]);
+        queue.drain_range(0);
+
+        let items: Vec<_> = queue.into_iter().map(|(_, item)| *item).collect();
+        assert_eq!(items, vec!["f"]);
+        assert_eq!(queue.oldest, Some(1));
+    }
+
+    #[test]
+    fn test_remove_middle_leaves_skippable_hole() {
+        let mut queue = RingQueue::new(8);
+
+        queue.extend(["a", "b", "c", "d", "e"]);
+
+        assert_eq!(queue.remove(2), Some("c"));
+        assert_eq!(queue.oldest, Some(0), "Oldest unaffected by middle removal");
+        assert_eq!(queue.newest, Some(4), "Newest unaffected by middle removal");
+
+        let items: Vec<_> = queue.into_iter().map(|(_, item)| *item).collect();
+        assert_eq!(
+            items,
+            vec!["a", "b", "d", "e"],
+            "Iteration should skip the hole"
+        );
+        assert_eq!(
+            queue.find_next_newer_index(1),
+            Some(3),
+            "Should skip the hole"
+        );
+        assert_eq!(queue.get(2), None, "No stale data in the hole");
+    }
+
+    #[test]
+    fn test_push_after_middle_removal_does_not_overwrite() {
+        let mut queue = RingQueue::new(3);
+
+        queue.extend(["a", "b", "c"]);
+        queue.remove(1);
+        queue.push("d");
+
+        let items: Vec<_> = queue.into_iter().map(|(_, item)| *item).collect();
+        assert_eq!(
+            items,
+            vec!["c", "d"],
+            "Oldest item should make room for the new one"
+        );
+        assert_eq!(queue.oldest, Some(2));
+        assert_eq!(queue.newest, Some(0));
+    }
+
+    #[test]
+    fn test_next_unseen_index_after_slot_reuse() {
+        let mut queue = RingQueue::new(1);
+
+        queue.push("a");
+        let seen_a = queue.newest_position();
+        queue.push("b");
+
+        assert_eq!(queue.newest, Some(0), "Item b reuses the slot of a");
+        assert!(queue.is_unseen(0, seen_a), "Item b is newer than a");
+        assert_eq!(queue.next_unseen_index(seen_a), Some(0));
+        assert_eq!(queue.next_unseen_index(queue.newest_position()), None);
+    }
+
+    #[test]
+    fn test_initial_capacity_preallocates_without_changing_eviction() {
+        let mut queue = RingQueue::with_initial_capacity(4, 4, QueueBackend::Sparse);
+        let allocated = queue.slots.capacity();
+        assert!(allocated >= 4, "Storage should be allocated up front");
+
+        queue.extend(["a", "b", "c", "d", "e"]);
+
+        assert_eq!(queue.slots.capacity(), allocated, "No reallocation needed");
+        let items: Vec<_> = queue.into_iter().map(|(_, item)| *item).collect();
+        assert_eq!(
+            items,
+            vec!["b", "c", "d", "e"],
+            "Eviction still at capacity"
+        );
+    }
+
+    #[test]
+    fn test_push_evicting_skips_pinned_items() {
+        let mut queue = RingQueue::new(3);
+        queue.extend(["keep", "drop", "also keep"]);
+        let seen_keep = queue.position(0);
+
+        queue.push_evicting("new", |item| *item == "drop");
+
+        let items: Vec<_> = (&queue).into_iter().map(|(_, item)| *item).collect();
+        assert_eq!(items, vec!["keep", "also keep", "new"]);
+        assert_eq!(
+            queue
+                .next_unseen_index(seen_keep)
+                .and_then(|index| queue.get(index)),
+            Some(&"also keep"),
+            "Moved items are still found after the last seen one"
+        );
+
+        queue.push_evicting("newest", |_| false);
+        let items: Vec<_> = (&queue).into_iter().map(|(_, item)| *item).collect();
+        assert_eq!(
+            items,
+            vec!["also keep", "new", "newest"],
+            "Oldest is evicted when everything is pinned"
+        );
+    }
+
+    #[test]
+    fn test_compact_keeps_order_and_reduces_span() {
+        let mut queue = RingQueue::new(1024);
+        queue.extend(0..100);
+        let kept: Vec<usize> = (0..100).step_by(10).collect();
+        for index in 0..100 {
+            if !kept.contains(&index) {
+                queue.remove(index);
+            }
+        }
+        let moved = queue.position(90).unwrap();
+        assert_eq!(queue.span(), 91);
+
+        queue.compact();
+
+        assert_eq!(queue.span(), 10);
+        assert_eq!(
+            queue.into_iter().map(|(_, item)| *item).collect::<Vec<_>>(),
+            kept
+        );
+        assert_eq!(queue.locate(moved).map(|position| position.index), Some(9));
+        assert!(!queue.is_unseen(9, Some(moved)));
+    }
+
+    #[test]
+    fn test_push_compacts_sparse_queue() {
+        let mut queue = RingQueue::new(1024);
+        queue.extend(0..100);
+        for index in 1..99 {
+            queue.remove(index);
+        }
+
+        queue.push(100);
+
+        assert_eq!(queue.span(), 3);
+        assert_eq!(
+            queue.into_iter().map(|(_, item)| *item).collect::<Vec<_>>(),
+            vec![0, 99, 100]
+        );
+    }
+
+    #[test]
+    fn test_backends_agree_after_removals_and_wraparound() {
+        let contents = |backend| {
+            let mut queue = RingQueue::with_initial_capacity(8, 0, backend);
+            queue.extend(0..10);
+            queue.remove(4);
+            queue.remove(6);
+            queue.extend(10..12);
+            let items: Vec<_> = queue.into_iter().collect::<Vec<_>>();
+            let newer = queue.find_next_newer_index(3);
+            (
+                items
+                    .into_iter()
+                    .map(|(index, item)| (index, *item))
+                    .collect::<Vec<_>>(),
+                newer,
+                queue.oldest,
+                queue.newest,
+            )
+        };
+
+        assert_eq!(
+            contents(QueueBackend::Sparse),
+            contents(QueueBackend::Dense)
+        );
+    }
+
+    #[test]
+    fn test_sequence_wraparound_keeps_order() {
+        let mut queue = RingQueue::new(4);
+        queue.next_sequence = u64::MAX - 1;
+
+        queue.push("a");
+        let seen_a = queue.newest_position();
+        queue.push("b");
+        let seen_b = queue.newest_position();
+        queue.push("c");
+
+        assert_eq!(seen_b.map(|position| position.sequence), Some(u64::MAX));
+        assert_eq!(queue.position(2).map(|position| position.sequence), Some(0));
+        assert!(
+            queue.is_unseen(2, seen_b),
+            "c is newer than b across the wrap"
+        );
+        assert!(!queue.is_unseen(0, seen_b), "a is older than b");
+        assert_eq!(queue.next_unseen_index(seen_a), Some(1));
+        assert_eq!(queue.next_unseen_index(seen_b), Some(2));
+
+        queue.remove(1);
+        assert_eq!(
+            queue.next_unseen_index(seen_b),
+            Some(2),
+            "Lookup by sequence also works across the wrap"
+        );
+    }
 }
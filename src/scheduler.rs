@@ -0,0 +1,36 @@
+/// Chooses the order in which [`CloneStream::poll_all_ready`] polls clones,
+/// and with it which clone drives the base stream.
+///
+/// Set up by [`ForkConfig::scheduler_seed`]. The same seed always produces the
+/// same sequence of orders.
+///
+/// [`CloneStream::poll_all_ready`]: crate::CloneStream::poll_all_ready
+/// [`ForkConfig::scheduler_seed`]: crate::ForkConfig::scheduler_seed
+pub(crate) struct Scheduler {
+    state: u64,
+}
+
+impl Scheduler {
+    pub(crate) fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// Returns a pseudo-random permutation of `0..n`.
+    pub(crate) fn poll_order(&mut self, n: usize) -> Vec<usize> {
+        let mut order: Vec<usize> = (0..n).collect();
+        for last in (1..n).rev() {
+            let chosen = (self.next_random() % (last as u64 + 1)) as usize;
+            order.swap(last, chosen);
+        }
+        order
+    }
+
+    /// Advances the `SplitMix64` generator.
+    fn next_random(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut mixed = self.state;
+        mixed = (mixed ^ (mixed >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        mixed = (mixed ^ (mixed >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        mixed ^ (mixed >> 31)
+    }
+}
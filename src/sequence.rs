@@ -0,0 +1,56 @@
+use std::ops::Range;
+
+/// Tracks the sequence numbers that a base stream carries in its items.
+///
+/// Set up by
+/// [`ForkStream::fork_sequenced`](crate::ForkStream::fork_sequenced).
+pub(crate) struct SequenceTracker<Item> {
+    sequence_of: Box<dyn Fn(&Item) -> u64 + Send + Sync>,
+    /// Highest sequence number produced so far.
+    pub(crate) last: Option<u64>,
+    /// Sequence numbers skipped by the base stream, oldest first.
+    pub(crate) missing: Vec<Range<u64>>,
+}
+
+impl<Item> SequenceTracker<Item> {
+    pub(crate) fn new(sequence_of: impl Fn(&Item) -> u64 + Send + Sync + 'static) -> Self {
+        Self {
+            sequence_of: Box::new(sequence_of),
+            last: None,
+            missing: Vec::new(),
+        }
+    }
+
+    /// Records the sequence number of an item produced by the base stream.
+    ///
+    /// A number beyond the next expected one marks the numbers in between as
+    /// missing. An older number that arrives late is no longer missing.
+    pub(crate) fn observe(&mut self, item: &Item) {
+        let sequence = (self.sequence_of)(item);
+        match self.last {
+            Some(last) if sequence <= last => self.fill(sequence),
+            Some(last) => {
+                if sequence > last + 1 {
+                    self.missing.push(last + 1..sequence);
+                }
+                self.last = Some(sequence);
+            }
+            None => self.last = Some(sequence),
+        }
+    }
+
+    fn fill(&mut self, sequence: u64) {
+        let Some(position) = self.missing.iter().position(|gap| gap.contains(&sequence)) else {
+            return;
+        };
+        let gap = self.missing.remove(position);
+        let after = sequence + 1..gap.end;
+        if !after.is_empty() {
+            self.missing.insert(position, after);
+        }
+        let before = gap.start..sequence;
+        if !before.is_empty() {
+            self.missing.insert(position, before);
+        }
+    }
+}
@@ -0,0 +1,103 @@
+use std::{
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use futures::{Stream, StreamExt, ready};
+
+use crate::CloneStream;
+
+/// A clone that only yields the items routed to its shard.
+///
+/// Shards are created with
+/// [`ForkStream::fork_sharded`](crate::ForkStream::fork_sharded). Every item of
+/// the base stream reaches exactly one shard: the one with index `route(&item)
+/// % shards`. Items are only queued for the shard they are routed to, so a
+/// slow shard does not hold back the items of the others.
+pub struct Shard<BaseStream, Route>
+where
+    BaseStream: Stream<Item: Clone>,
+{
+    clone: CloneStream<BaseStream>,
+    route: Arc<Route>,
+    index: usize,
+    shards: usize,
+}
+
+impl<BaseStream, Route> Shard<BaseStream, Route>
+where
+    BaseStream: Stream<Item: Clone>,
+    Route: Fn(&BaseStream::Item) -> usize,
+{
+    pub(crate) fn new(
+        clone: CloneStream<BaseStream>,
+        route: Arc<Route>,
+        index: usize,
+        shards: usize,
+    ) -> Self {
+        Self {
+            clone,
+            route,
+            index,
+            shards,
+        }
+    }
+
+    /// Returns the index of this shard.
+    #[must_use]
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns the number of items queued for this shard.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the internal fork lock is poisoned.
+    #[must_use]
+    pub fn n_queued_items(&self) -> usize {
+        self.clone.n_queued_items()
+    }
+}
+
+impl<BaseStream, Route> Stream for Shard<BaseStream, Route>
+where
+    BaseStream: Stream<Item: Clone>,
+    Route: Fn(&BaseStream::Item) -> usize,
+{
+    type Item = BaseStream::Item;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            match ready!(self.clone.poll_next_unpin(cx)) {
+                Some(item) if (self.route)(&item) % self.shards != self.index => {}
+                item => return Poll::Ready(item),
+            }
+        }
+    }
+}
+
+/// Routes every item of a sharded fork to the clone of a single shard.
+pub(crate) struct Router<Item> {
+    route: Arc<dyn Fn(&Item) -> usize + Send + Sync>,
+    /// ID of the clone of each shard, by shard index.
+    clone_ids: Vec<usize>,
+}
+
+impl<Item> Router<Item> {
+    pub(crate) fn new(
+        route: Arc<dyn Fn(&Item) -> usize + Send + Sync>,
+        clone_ids: Vec<usize>,
+    ) -> Self {
+        Self { route, clone_ids }
+    }
+
+    /// Checks whether `item` is routed to the shard of `clone_id`. The end of
+    /// the base stream reaches every shard.
+    pub(crate) fn is_routed_to(&self, clone_id: usize, item: Option<&Item>) -> bool {
+        item.is_none_or(|item| {
+            self.clone_ids[(self.route)(item) % self.clone_ids.len()] == clone_id
+        })
+    }
+}
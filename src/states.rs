@@ -1,22 +1,49 @@
 use std::{
     fmt::Debug,
-    task::{Context, Poll, Waker},
+    task::{Poll, Waker, ready},
 };
 
-use futures::{Stream, StreamExt};
+use futures::Stream;
 use log::{debug, trace};
 
-use crate::Fork;
+use crate::{
+    Fork,
+    fork::BacklogOrder,
+    ring_queue::{QueuePosition, RingQueue},
+    trace::PollEventKind,
+};
+
+/// Item handed to a clone by [`CloneState::step`].
+pub(crate) enum Delivery<Item> {
+    /// No other clone needs the item anymore, so it was moved out of the
+    /// queue or never queued.
+    Owned(Option<Item>),
+    /// The item stays queued at this index for other clones. The clone
+    /// borrows or clones it from there.
+    Queued(usize),
+}
 
 /// Represents the state of a clone in the stream cloning state machine.
 ///
 /// Each clone maintains its own state to track its position relative to the
 /// base stream and the shared queue. The state determines how the clone should
 /// behave when polled.
-#[derive(Clone, Debug)]
+///
+/// States without queue history have not seen any of the items that are
+/// currently queued. States with queue history have seen every queued item up
+/// to and including their last seen position.
+#[derive(Clone, Debug, Default)]
 pub(crate) enum CloneState {
+    #[default]
     AwaitingFirstItem,
     BaseStreamReady,
+    /// Registered after the base stream ended. Yields that end once before
+    /// polling the base stream again.
+    BaseStreamExhausted,
+    /// Not polled yet in a fork with
+    /// [`ForkConfig::subscribe_on_first_poll`](crate::ForkConfig::subscribe_on_first_poll).
+    /// Has no interest in any item until then.
+    Unsubscribed,
 
     AwaitingBaseStream {
         waker: Waker,
@@ -24,35 +51,138 @@ pub(crate) enum CloneState {
 
     AwaitingBaseStreamWithQueueHistory {
         waker: Waker,
-        last_seen_index: usize,
+        last_seen: QueuePosition,
     },
 
-    BaseStreamReadyWithQueueHistory,
+    BaseStreamReadyWithQueueHistory {
+        last_seen: QueuePosition,
+    },
     ProcessingQueue {
-        last_seen_queue_index: usize,
+        last_seen: QueuePosition,
+    },
+    /// Delivering the queued backlog newest item first. Has seen every
+    /// queued item up to and including `floor`, and every item from
+    /// `oldest_delivered` up to and including `newest_delivered`.
+    ProcessingQueueNewestFirst {
+        floor: Option<QueuePosition>,
+        oldest_delivered: QueuePosition,
+        newest_delivered: QueuePosition,
     },
-}
-
-impl Default for CloneState {
-    fn default() -> Self {
-        Self::AwaitingFirstItem
-    }
 }
 
 use CloneState::{
-    AwaitingBaseStream, AwaitingBaseStreamWithQueueHistory, AwaitingFirstItem, BaseStreamReady,
-    BaseStreamReadyWithQueueHistory, ProcessingQueue,
+    AwaitingBaseStream, AwaitingBaseStreamWithQueueHistory, AwaitingFirstItem, BaseStreamExhausted,
+    BaseStreamReady, BaseStreamReadyWithQueueHistory, ProcessingQueue, ProcessingQueueNewestFirst,
+    Unsubscribed,
 };
 
 impl CloneState {
-    pub(crate) fn should_still_see_base_item(&self) -> bool {
-        trace!("Checking if clone in state {self:?} should still see base item");
+    /// Position of the newest queued item this clone has seen, or `None` if
+    /// it has not seen any of the queued items.
+    pub(crate) fn last_seen(&self) -> Option<QueuePosition> {
+        match self {
+            AwaitingFirstItem
+            | BaseStreamReady
+            | BaseStreamExhausted
+            | Unsubscribed
+            | AwaitingBaseStream { .. } => None,
+            AwaitingBaseStreamWithQueueHistory { last_seen, .. }
+            | BaseStreamReadyWithQueueHistory { last_seen }
+            | ProcessingQueue { last_seen } => Some(*last_seen),
+            ProcessingQueueNewestFirst {
+                newest_delivered, ..
+            } => Some(*newest_delivered),
+        }
+    }
+
+    /// Position after which this clone has not seen any queued item, or
+    /// `None` if it has not seen any of the queued items.
+    pub(crate) fn seen_through(&self) -> Option<QueuePosition> {
+        match self {
+            ProcessingQueueNewestFirst { floor, .. } => *floor,
+            _ => self.last_seen(),
+        }
+    }
+
+    /// Returns the state of a clone that has seen every queued item up to and
+    /// including `last_seen`, and none after it.
+    pub(crate) fn seen_through_position(last_seen: Option<QueuePosition>) -> CloneState {
+        match last_seen {
+            Some(last_seen) => BaseStreamReadyWithQueueHistory { last_seen },
+            None => AwaitingFirstItem,
+        }
+    }
+
+    /// Checks whether this clone has not seen the queued item at `index`.
+    pub(crate) fn is_unseen<Item>(&self, queue: &RingQueue<Item>, index: usize) -> bool
+    where
+        Item: Clone,
+    {
+        match self {
+            ProcessingQueueNewestFirst {
+                floor,
+                oldest_delivered,
+                newest_delivered,
+            } => {
+                queue.is_unseen(index, Some(*newest_delivered))
+                    || (queue.is_unseen(index, *floor)
+                        && queue
+                            .position(index)
+                            .is_some_and(|position| position.is_before(*oldest_delivered)))
+            }
+            Unsubscribed => false,
+            _ => queue.is_unseen(index, self.last_seen()),
+        }
+    }
+
+    /// Returns the index of the queued item this clone receives next.
+    ///
+    /// With [`BacklogOrder::NewestFirst`], the newest unseen item is delivered
+    /// first and the older ones follow newest to oldest, before any item
+    /// queued since.
+    pub(crate) fn next_queued_index<Item>(
+        &self,
+        queue: &RingQueue<Item>,
+        order: BacklogOrder,
+    ) -> Option<usize>
+    where
+        Item: Clone,
+    {
+        match self {
+            ProcessingQueueNewestFirst {
+                floor,
+                oldest_delivered,
+                newest_delivered,
+            } => queue
+                .newest_unseen_index_before(*floor, *oldest_delivered)
+                .or_else(|| queue.newest_unseen_index(Some(*newest_delivered))),
+            _ if order == BacklogOrder::NewestFirst => queue.newest_unseen_index(self.last_seen()),
+            _ => queue.next_unseen_index(self.last_seen()),
+        }
+    }
 
+    /// Returns the state after delivering the queued item at `position`.
+    fn after_delivering(&self, position: QueuePosition, order: BacklogOrder) -> CloneState {
+        if order == BacklogOrder::OldestFirst {
+            return ProcessingQueue {
+                last_seen: position,
+            };
+        }
         match self {
-            AwaitingBaseStream { .. }
-            | AwaitingBaseStreamWithQueueHistory { .. }
-            | BaseStreamReady => true,
-            AwaitingFirstItem | BaseStreamReadyWithQueueHistory | ProcessingQueue { .. } => false,
+            ProcessingQueueNewestFirst {
+                floor,
+                oldest_delivered,
+                newest_delivered,
+            } if position.is_before(*oldest_delivered) => ProcessingQueueNewestFirst {
+                floor: *floor,
+                oldest_delivered: position,
+                newest_delivered: *newest_delivered,
+            },
+            _ => ProcessingQueueNewestFirst {
+                floor: self.last_seen(),
+                oldest_delivered: position,
+                newest_delivered: position,
+            },
         }
     }
 
@@ -63,18 +193,21 @@ impl CloneState {
             }
             AwaitingFirstItem
             | BaseStreamReady
-            | BaseStreamReadyWithQueueHistory
-            | ProcessingQueue { .. } => None,
+            | BaseStreamExhausted
+            | Unsubscribed
+            | BaseStreamReadyWithQueueHistory { .. }
+            | ProcessingQueue { .. }
+            | ProcessingQueueNewestFirst { .. } => None,
         }
     }
 
     #[inline]
-    fn transition_on_poll<Item>(
+    fn transition_on_poll<Output>(
         &mut self,
-        poll_result: Poll<Option<Item>>,
+        poll_result: Poll<Output>,
         ready_state: CloneState,
         pending_state: CloneState,
-    ) -> Poll<Option<Item>> {
+    ) -> Poll<Output> {
         match poll_result {
             Poll::Ready(item) => {
                 *self = ready_state;
@@ -89,145 +222,129 @@ impl CloneState {
 }
 
 impl CloneState {
+    /// Advances the clone by one poll.
+    ///
+    /// Queued items the clone has not seen yet are always delivered before
+    /// the base stream is polled, so every clone receives items in the order
+    /// the base stream produced them, unless the fork delivers backlogs
+    /// newest first.
     #[inline]
     pub(crate) fn step<BaseStream>(
         &mut self,
         clone_id: usize,
         waker: &Waker,
         fork: &mut Fork<BaseStream>,
-    ) -> Poll<Option<BaseStream::Item>>
+    ) -> Poll<Delivery<BaseStream::Item>>
     where
         BaseStream: Stream<Item: Clone>,
     {
-        match self {
-            AwaitingFirstItem | BaseStreamReady => self.transition_on_poll(
-                poll_base_with_queue_check(clone_id, waker, fork),
-                BaseStreamReady,
-                next_pending_state(waker, fork),
-            ),
-            AwaitingBaseStream { .. } => {
-                if fork.item_buffer.is_empty() {
-                    debug!("Clone {clone_id}: Queue still empty, polling base stream");
-                    self.transition_on_poll(
-                        poll_base_with_queue_check(clone_id, waker, fork),
-                        BaseStreamReady,
-                        AwaitingBaseStream {
-                            waker: waker.clone(),
-                        },
-                    )
-                } else {
-                    debug!("Clone {clone_id}: Queue now has items, processing oldest");
-                    let (oldest_queue_index, item) =
-                        pop_or_clone_oldest_unseen_queue_item(fork, clone_id);
-                    *self = ProcessingQueue {
-                        last_seen_queue_index: oldest_queue_index,
-                    };
-                    Poll::Ready(item)
-                }
-            }
-            AwaitingBaseStreamWithQueueHistory {
-                last_seen_index, ..
-            } => {
-                let last_seen_index = *last_seen_index;
-                if let Some((newer_index, item)) = process_newer_queue_item(fork, last_seen_index) {
-                    *self = ProcessingQueue {
-                        last_seen_queue_index: newer_index,
-                    };
-                    Poll::Ready(item)
-                } else {
-                    self.transition_on_poll(
-                        poll_base_stream(clone_id, waker, fork),
-                        BaseStreamReadyWithQueueHistory,
-                        AwaitingBaseStreamWithQueueHistory {
-                            waker: waker.clone(),
-                            last_seen_index,
-                        },
-                    )
-                }
-            }
-            BaseStreamReadyWithQueueHistory => {
-                let pending_state = if let Some(oldest_index) = fork.item_buffer.oldest_index() {
-                    AwaitingBaseStreamWithQueueHistory {
-                        waker: waker.clone(),
-                        last_seen_index: oldest_index,
-                    }
-                } else {
-                    AwaitingBaseStream {
-                        waker: waker.clone(),
-                    }
-                };
+        if matches!(self, Unsubscribed) {
+            debug!("Clone {clone_id}: Subscribing on first poll");
+            *self = fork.subscribed_state();
+        }
+        if fork.router.is_some() {
+            self.skip_routed_elsewhere(clone_id, fork);
+        }
+        let last_seen = self.last_seen();
+        let order = fork.config.backlog_order;
 
-                self.transition_on_poll(
-                    poll_base_stream(clone_id, waker, fork),
-                    BaseStreamReadyWithQueueHistory,
-                    pending_state,
-                )
-            }
-            ProcessingQueue {
-                last_seen_queue_index,
-            } => {
-                let last_seen_queue_index = *last_seen_queue_index;
-                trace!(
-                    "Clone {clone_id}: previously a queue item was ready, checking if there is a newer one at {last_seen_queue_index}"
-                );
-                if let Some((newer_index, item)) =
-                    process_newer_queue_item(fork, last_seen_queue_index)
-                {
-                    trace!("Clone {clone_id}: Found newer item at {newer_index}");
-                    *self = ProcessingQueue {
-                        last_seen_queue_index: newer_index,
-                    };
-                    Poll::Ready(item)
-                } else {
-                    trace!(
-                        "Clone {clone_id}: No newer item, transitioning to BaseStreamReadyWithQueueHistory"
-                    );
-                    self.transition_on_poll(
-                        poll_base_stream(clone_id, waker, fork),
-                        BaseStreamReadyWithQueueHistory,
-                        AwaitingBaseStreamWithQueueHistory {
-                            waker: waker.clone(),
-                            last_seen_index: last_seen_queue_index,
-                        },
-                    )
-                }
+        let mut base_pending = false;
+        if fork.config.min_latency && !fork.base_exhausted && !matches!(self, BaseStreamExhausted) {
+            match self.step_fresh_first(clone_id, waker, fork) {
+                Poll::Ready(delivery) => return Poll::Ready(delivery),
+                Poll::Pending => base_pending = true,
             }
         }
+
+        if let Some(index) = self.next_queued_index(&fork.item_buffer, order) {
+            debug!("Clone {clone_id}: Delivering queued item at {index}");
+            let position = fork.item_buffer.position(index).unwrap();
+            let delivery = take_queue_item(fork, clone_id, index);
+            *self = self.after_delivering(position, order);
+            return Poll::Ready(delivery);
+        }
+
+        if matches!(self, BaseStreamExhausted) {
+            debug!("Clone {clone_id}: Base stream ended before registration");
+            *self = BaseStreamReady;
+            return Poll::Ready(Delivery::Owned(None));
+        }
+
+        if base_pending {
+            *self = next_pending_state(waker, last_seen);
+            return Poll::Pending;
+        }
+
+        trace!("Clone {clone_id}: No unseen queued item, polling base stream");
+        let poll_result = poll_base_stream(clone_id, waker, fork);
+        let ready_state = match fork.item_buffer.newest_position() {
+            Some(last_seen) => BaseStreamReadyWithQueueHistory { last_seen },
+            None => BaseStreamReady,
+        };
+        self.transition_on_poll(
+            poll_result.map(Delivery::Owned),
+            ready_state,
+            next_pending_state(waker, last_seen),
+        )
     }
 }
 
-#[inline]
-pub(crate) fn poll_base_stream<BaseStream>(
-    clone_id: usize,
-    waker: &Waker,
-    fork: &mut Fork<BaseStream>,
-) -> Poll<Option<BaseStream::Item>>
-where
-    BaseStream: Stream<Item: Clone>,
-{
-    match fork
-        .base_stream
-        .poll_next_unpin(&mut Context::from_waker(&fork.waker(waker)))
+impl CloneState {
+    /// Moves a clone of a sharded fork past the queued items routed to other
+    /// shards.
+    fn skip_routed_elsewhere<BaseStream>(&mut self, clone_id: usize, fork: &Fork<BaseStream>)
+    where
+        BaseStream: Stream<Item: Clone>,
     {
-        Poll::Ready(item) => {
-            trace!("Base stream ready with item");
-            if fork.clone_registry.has_other_clones_waiting(clone_id) {
-                trace!("Queuing item for other waiting clones");
-                fork.item_buffer.push(item.clone());
-            } else {
-                trace!("No other clones waiting, not queuing item");
-            }
-            Poll::Ready(item)
+        let order = fork.config.backlog_order;
+        while let Some(index) = self.next_queued_index(&fork.item_buffer, order)
+            && !fork.is_routed_to(clone_id, index)
+        {
+            let position = fork.item_buffer.position(index).unwrap();
+            *self = self.after_delivering(position, order);
+        }
+    }
+
+    /// Polls the base stream before the queue, for forks with
+    /// [`ForkConfig::min_latency`](crate::ForkConfig::min_latency).
+    ///
+    /// A fresh item moves the clone past the queued items it has not
+    /// received. The end of the base stream is queued behind them instead, so
+    /// the clone still receives them before it ends. Pending when the base
+    /// stream has no fresh item.
+    fn step_fresh_first<BaseStream>(
+        &mut self,
+        clone_id: usize,
+        waker: &Waker,
+        fork: &mut Fork<BaseStream>,
+    ) -> Poll<Delivery<BaseStream::Item>>
+    where
+        BaseStream: Stream<Item: Clone>,
+    {
+        let has_backlog = self
+            .next_queued_index(&fork.item_buffer, fork.config.backlog_order)
+            .is_some();
+        let item = ready!(fork.poll_base(clone_id, waker));
+        if item.is_none() && has_backlog {
+            debug!("Clone {clone_id}: Base stream ended, queueing the end behind the backlog");
+            fork.enqueue(clone_id, None);
+            return Poll::Pending;
         }
-        Poll::Pending => {
-            trace!("Base stream pending");
-            Poll::Pending
+        queue_fresh_item(clone_id, fork, item.as_ref());
+        if has_backlog {
+            debug!("Clone {clone_id}: Skipping queued backlog for a fresh item");
+            fork.defer_cleanup();
         }
+        *self = match fork.item_buffer.newest_position() {
+            Some(last_seen) => BaseStreamReadyWithQueueHistory { last_seen },
+            None => BaseStreamReady,
+        };
+        Poll::Ready(Delivery::Owned(item))
     }
 }
 
 #[inline]
-fn poll_base_with_queue_check<BaseStream>(
+pub(crate) fn poll_base_stream<BaseStream>(
     clone_id: usize,
     waker: &Waker,
     fork: &mut Fork<BaseStream>,
@@ -235,109 +352,73 @@ fn poll_base_with_queue_check<BaseStream>(
 where
     BaseStream: Stream<Item: Clone>,
 {
-    match fork
-        .base_stream
-        .poll_next_unpin(&mut Context::from_waker(&fork.waker(waker)))
-    {
-        Poll::Ready(item) => {
-            trace!("Base stream ready with item");
-
-            if fork.clone_registry.has_other_clones_waiting(clone_id) {
-                trace!("Queuing item for other interested clones");
-                fork.item_buffer.push(item.clone());
-            } else {
-                trace!("No other clones need this item");
-            }
-            Poll::Ready(item)
-        }
-        Poll::Pending => {
-            trace!("Base stream pending");
-            Poll::Pending
-        }
-    }
+    let item = ready!(fork.poll_base(clone_id, waker));
+    queue_fresh_item(clone_id, fork, item.as_ref());
+    Poll::Ready(item)
 }
 
-#[inline]
-fn next_pending_state<BaseStream>(waker: &Waker, fork: &Fork<BaseStream>) -> CloneState
-where
+/// Queues an item fresh from the base stream for the other clones, and for
+/// the polling clone if it has to acknowledge the item.
+fn queue_fresh_item<BaseStream>(
+    clone_id: usize,
+    fork: &mut Fork<BaseStream>,
+    item: Option<&BaseStream::Item>,
+) where
     BaseStream: Stream<Item: Clone>,
 {
-    use CloneState::{AwaitingBaseStream, AwaitingBaseStreamWithQueueHistory};
-    if fork.item_buffer.is_empty() {
-        AwaitingBaseStream {
-            waker: waker.clone(),
-        }
-    } else if let Some(newest_index) = fork.item_buffer.newest {
-        AwaitingBaseStreamWithQueueHistory {
-            waker: waker.clone(),
-            last_seen_index: newest_index,
-        }
+    let keep_for_ack = item.is_some() && fork.acks.is_receiving(clone_id);
+    if fork.is_wanted_by_other_clones(clone_id, item) || keep_for_ack {
+        trace!("Queuing item for other clones");
+        fork.poll_counters.record_item_clones(1);
+        fork.enqueue(clone_id, item.cloned());
     } else {
-        AwaitingBaseStream {
-            waker: waker.clone(),
-        }
+        trace!("No other clones, not queuing item");
+    }
+    if keep_for_ack && let Some(index) = fork.item_buffer.newest {
+        fork.keep_for_ack(clone_id, index);
     }
 }
 
 #[inline]
-fn pop_or_clone_oldest_unseen_queue_item<BaseStream>(
-    fork: &mut Fork<BaseStream>,
-    clone_id: usize,
-) -> (usize, Option<BaseStream::Item>)
-where
-    BaseStream: Stream<Item: Clone>,
-{
-    let previous_occupied_oldest_queue_index = fork
-        .item_buffer
-        .oldest_index()
-        .expect("Queue reported non-empty but has no oldest index - this is a bug in RingQueue");
-
-    let other_clones_want_item =
-        fork.clone_registry
-            .iter_active_with_ids()
-            .any(|(other_clone_id, _)| {
-                other_clone_id != clone_id
-                    && fork
-                        .should_clone_see_item(other_clone_id, previous_occupied_oldest_queue_index)
-            });
-
-    let oldest_queue_item = if other_clones_want_item {
-        fork.item_buffer
-            .get(previous_occupied_oldest_queue_index)
-            .unwrap()
-            .clone()
-    } else {
-        fork.item_buffer.pop_oldest().unwrap()
-    };
-
-    (previous_occupied_oldest_queue_index, oldest_queue_item)
+fn next_pending_state(waker: &Waker, last_seen: Option<QueuePosition>) -> CloneState {
+    match last_seen {
+        Some(last_seen) => AwaitingBaseStreamWithQueueHistory {
+            waker: waker.clone(),
+            last_seen,
+        },
+        None => AwaitingBaseStream {
+            waker: waker.clone(),
+        },
+    }
 }
 
+/// Takes the queued item at `newer_index` for the clone.
+///
+/// The item is removed from the queue when no other clone still needs it and
+/// no clone has to acknowledge it, and left in the queue otherwise.
 #[inline]
-fn process_newer_queue_item<BaseStream>(
+fn take_queue_item<BaseStream>(
     fork: &mut Fork<BaseStream>,
-    last_seen_queue_index: usize,
-) -> Option<(usize, Option<BaseStream::Item>)>
+    clone_id: usize,
+    newer_index: usize,
+) -> Delivery<BaseStream::Item>
 where
     BaseStream: Stream<Item: Clone>,
 {
-    let newer_index = fork
-        .item_buffer
-        .find_next_newer_index(last_seen_queue_index)?;
-
-    let item = if fork.clone_registry.count() <= 1 {
-        fork.item_buffer.remove(newer_index).unwrap()
-    } else {
-        let clones_needing_item = fork
+    fork.keep_for_ack(clone_id, newer_index);
+    // The polling clone is not in the registry while it is being stepped, so
+    // this only considers the other clones.
+    let other_clones_want_item = fork.is_held_for_ack(newer_index)
+        || fork
             .clone_registry
             .iter_active_with_ids()
-            .filter(|(clone_id, _)| fork.should_clone_see_item(*clone_id, newer_index))
-            .count();
-        match clones_needing_item {
-            0 | 1 => fork.item_buffer.remove(newer_index).unwrap(),
-            _ => fork.item_buffer.get(newer_index).unwrap().clone(),
-        }
-    };
+            .any(|(other_clone_id, _)| fork.should_clone_see_item(other_clone_id, newer_index));
 
-    Some((newer_index, item))
+    if other_clones_want_item {
+        fork.record(clone_id, PollEventKind::QueueCloned { index: newer_index });
+        Delivery::Queued(newer_index)
+    } else {
+        fork.record(clone_id, PollEventKind::QueueTaken { index: newer_index });
+        Delivery::Owned(fork.item_buffer.remove(newer_index).unwrap())
+    }
 }
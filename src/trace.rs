@@ -0,0 +1,92 @@
+//! Debugging aids: the recorded order of base-stream polls and queue
+//! operations, the reason a poll did not yield an item, and whether the next
+//! item is likely to arrive soon.
+
+use std::task::Poll;
+
+/// A single base-stream poll or queue operation, recorded in the order the
+/// fork performed it.
+///
+/// Only recorded when [`ForkConfig::record_poll_order`] is enabled. See
+/// [`CloneStream::poll_trace`].
+///
+/// [`ForkConfig::record_poll_order`]: crate::ForkConfig::record_poll_order
+/// [`CloneStream::poll_trace`]: crate::CloneStream::poll_trace
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PollEvent {
+    /// Identifier of the clone that caused the event.
+    pub clone_id: usize,
+    /// What happened.
+    pub kind: PollEventKind,
+}
+
+/// The kind of operation described by a [`PollEvent`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PollEventKind {
+    /// The base stream was polled and returned `Pending`.
+    BasePending,
+    /// The base stream was polled and returned an item.
+    BaseItem,
+    /// The base stream was polled and returned `None`.
+    BaseEnded,
+    /// An item was pushed onto the shared queue at the given index.
+    Enqueued { index: usize },
+    /// A queued item was cloned for the clone and left in the queue.
+    QueueCloned { index: usize },
+    /// A queued item was removed from the queue and handed to the clone.
+    QueueTaken { index: usize },
+}
+
+/// The result of [`CloneStream::poll_next_diagnostic`], which tells why a
+/// clone is not ready instead of just returning `Pending`.
+///
+/// [`CloneStream::poll_next_diagnostic`]: crate::CloneStream::poll_next_diagnostic
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum PollOutcome<Item> {
+    /// The clone yielded an item, or `None` if the stream ended.
+    Item(Option<Item>),
+    /// No item is queued for this clone and the base stream is pending.
+    PendingBaseStream,
+    /// No item is queued for this clone and the fork stopped polling the
+    /// base stream until the slowest clone catches up, see
+    /// [`ForkConfig::high_water`](crate::ForkConfig::high_water).
+    Backpressured,
+    /// The clone used up its credits and waits for
+    /// [`CloneStream::grant_credits`](crate::CloneStream::grant_credits).
+    PendingCredits,
+    /// The fork stopped polling the base stream because the queue is full of
+    /// items received with
+    /// [`CloneStream::next_with_ack`](crate::CloneStream::next_with_ack) that
+    /// were not acknowledged yet.
+    PendingAck,
+}
+
+impl<Item> PollOutcome<Item> {
+    /// Converts the outcome into a plain [`Poll`], forgetting why it is
+    /// pending.
+    pub fn into_poll(self) -> Poll<Option<Item>> {
+        match self {
+            PollOutcome::Item(item) => Poll::Ready(item),
+            PollOutcome::PendingBaseStream
+            | PollOutcome::Backpressured
+            | PollOutcome::PendingCredits
+            | PollOutcome::PendingAck => Poll::Pending,
+        }
+    }
+}
+
+/// How soon a clone is likely to receive its next item, returned by
+/// [`CloneStream::readiness`](crate::CloneStream::readiness).
+///
+/// This is a heuristic. Consumers can use it to decide whether to wait for
+/// the next item or do other work first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Readiness {
+    /// An item is queued for the clone and can be taken right away.
+    Ready,
+    /// The base stream produced an item recently.
+    SourceActive,
+    /// The base stream has not produced an item recently.
+    Idle,
+}
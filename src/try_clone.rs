@@ -0,0 +1,67 @@
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use futures::{Stream, StreamExt, ready};
+
+use crate::CloneStream;
+
+/// A clone of a fallible stream that ends after the first error.
+///
+/// Created with [`ForkStream::fork_try`](crate::ForkStream::fork_try). Every
+/// clone receives the first error of the base stream and then terminates, so
+/// all consumers observe the same failure.
+pub struct TryCloneStream<BaseStream>
+where
+    BaseStream: Stream<Item: Clone>,
+{
+    clone: CloneStream<BaseStream>,
+    failed: bool,
+}
+
+impl<BaseStream> TryCloneStream<BaseStream>
+where
+    BaseStream: Stream<Item: Clone>,
+{
+    pub(crate) fn new(clone: CloneStream<BaseStream>) -> Self {
+        Self {
+            clone,
+            failed: false,
+        }
+    }
+}
+
+impl<BaseStream> Clone for TryCloneStream<BaseStream>
+where
+    BaseStream: Stream<Item: Clone>,
+{
+    /// Creates a new clone. A clone of a clone that already failed is
+    /// terminated as well.
+    fn clone(&self) -> Self {
+        Self {
+            clone: self.clone.clone(),
+            failed: self.failed,
+        }
+    }
+}
+
+impl<BaseStream, Item, Error> Stream for TryCloneStream<BaseStream>
+where
+    BaseStream: Stream<Item = Result<Item, Error>>,
+    Item: Clone,
+    Error: Clone,
+{
+    type Item = Result<Item, Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if self.failed {
+            return Poll::Ready(None);
+        }
+        let item = ready!(self.clone.poll_next_unpin(cx));
+        if matches!(item, Some(Err(_))) {
+            self.failed = true;
+        }
+        Poll::Ready(item)
+    }
+}
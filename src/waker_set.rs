@@ -0,0 +1,65 @@
+use std::task::Waker;
+
+/// Wakers that are woken together, each stored only once.
+///
+/// Clones polled from the same task usually hand in wakers that wake the same
+/// task. A waker that [`Waker::will_wake`] one already in the set is not
+/// stored again, so that task is woken once instead of once per clone.
+#[derive(Debug, Default)]
+pub(crate) struct WakerSet {
+    wakers: Vec<Waker>,
+}
+
+impl WakerSet {
+    /// Adds a clone of `waker` unless an equivalent waker is stored already.
+    pub(crate) fn insert(&mut self, waker: &Waker) {
+        if !self.wakers.iter().any(|stored| stored.will_wake(waker)) {
+            self.wakers.push(waker.clone());
+        }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.wakers.len()
+    }
+
+    /// Returns the only stored waker, if there is exactly one.
+    pub(crate) fn single(&self) -> Option<&Waker> {
+        match self.wakers.as_slice() {
+            [waker] => Some(waker),
+            _ => None,
+        }
+    }
+
+    /// Wakes and removes all stored wakers.
+    pub(crate) fn wake_all(&mut self) {
+        self.wakers.drain(..).for_each(Waker::wake);
+    }
+}
+
+impl FromIterator<Waker> for WakerSet {
+    fn from_iter<Wakers: IntoIterator<Item = Waker>>(wakers: Wakers) -> Self {
+        let mut set = WakerSet::default();
+        for waker in wakers {
+            set.insert(&waker);
+        }
+        set
+    }
+}
+
+impl IntoIterator for WakerSet {
+    type Item = Waker;
+    type IntoIter = std::vec::IntoIter<Waker>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.wakers.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a WakerSet {
+    type Item = &'a Waker;
+    type IntoIter = std::slice::Iter<'a, Waker>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.wakers.iter()
+    }
+}
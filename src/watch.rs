@@ -0,0 +1,51 @@
+use futures::{Stream, stream};
+use tokio::sync::watch::Receiver;
+
+use crate::{CloneStream, ForkConfig, ForkStream};
+
+/// Extension trait to fork a [`tokio::sync::watch::Receiver`].
+pub trait ForkWatch<Item>
+where
+    Item: Clone + Send + Sync + 'static,
+{
+    /// Turns the receiver into a cloneable stream of the values it observes.
+    ///
+    /// The stream yields the current value first and then every value the
+    /// receiver sees change to. Like the receiver itself, it only observes
+    /// the latest value, so values replaced before the stream is polled are
+    /// skipped. Clones still receive every value the stream yielded, as it
+    /// forks with [`ForkConfig::deliver_to_idle_clones`]; use
+    /// [`CloneStream::latest_only`] for clones that only need the latest. The
+    /// stream ends when the sender is dropped.
+    ///
+    /// ```rust
+    /// use clone_stream::ForkWatch;
+    /// use futures::StreamExt;
+    /// use tokio::sync::watch;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let (sender, receiver) = watch::channel(0);
+    /// let mut stream = receiver.fork();
+    /// sender.send(1).unwrap();
+    /// assert_eq!(stream.next().await, Some(1));
+    /// # }
+    /// ```
+    fn fork(self) -> CloneStream<impl Stream<Item = Item>>;
+}
+
+impl<Item> ForkWatch<Item> for Receiver<Item>
+where
+    Item: Clone + Send + Sync + 'static,
+{
+    fn fork(self) -> CloneStream<impl Stream<Item = Item>> {
+        stream::unfold((self, true), |(mut receiver, first)| async move {
+            if !first && receiver.changed().await.is_err() {
+                return None;
+            }
+            let value = receiver.borrow_and_update().clone();
+            Some((value, (receiver, false)))
+        })
+        .fork_with_config(ForkConfig::delivering_to_idle_clones())
+    }
+}
@@ -0,0 +1,26 @@
+use clone_stream::{ForkConfig, ForkStream};
+use futures::{StreamExt, channel::mpsc};
+
+#[tokio::test]
+async fn aborted_clone_ends_while_sibling_continues() {
+    let (sender, receiver) = mpsc::unbounded::<usize>();
+    let mut sibling = receiver.fork_with_config(ForkConfig::delivering_to_idle_clones());
+    let (mut aborted, handle) = sibling.clone().abortable();
+
+    sender.unbounded_send(1).unwrap();
+    assert_eq!(aborted.next().await, Some(1));
+    assert_eq!(sibling.next().await, Some(1));
+
+    let waiting = tokio::spawn(async move {
+        let next = aborted.next().await;
+        (next, aborted.is_aborted())
+    });
+    tokio::task::yield_now().await;
+    handle.abort();
+    assert_eq!(waiting.await.unwrap(), (None, true));
+
+    sender.unbounded_send(2).unwrap();
+    drop(sender);
+    assert_eq!(sibling.n_queued_items(), 0, "Aborted clone unregistered");
+    assert_eq!(sibling.collect::<Vec<_>>().await, vec![2]);
+}
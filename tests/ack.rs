@@ -0,0 +1,52 @@
+use clone_stream::{ForkConfig, ForkStream};
+use futures::{StreamExt, channel::mpsc};
+
+#[tokio::test]
+async fn item_stays_buffered_until_every_clone_acks() {
+    let (sender, receiver) = mpsc::unbounded::<usize>();
+    let mut first = receiver.fork_with_config(ForkConfig::delivering_to_idle_clones());
+    let mut second = first.clone();
+
+    sender.unbounded_send(1).unwrap();
+    let (item, first_token) = first.next_with_ack().await.unwrap();
+    assert_eq!(item, 1);
+    let (item, second_token) = second.next_with_ack().await.unwrap();
+    assert_eq!(item, 1);
+    assert_eq!(
+        first.oldest_needed_index(),
+        Some(0),
+        "Received by both clones, but not acknowledged"
+    );
+
+    first_token.ack();
+    assert_eq!(
+        first.oldest_needed_index(),
+        Some(0),
+        "Only acknowledged by `first`"
+    );
+
+    drop(second_token);
+    assert_eq!(first.oldest_needed_index(), None, "Acknowledged by both");
+
+    sender.unbounded_send(2).unwrap();
+    assert_eq!(first.next().await, Some(2));
+    assert_eq!(second.next().await, Some(2));
+}
+
+#[tokio::test]
+async fn unacked_item_is_received_by_new_clone() {
+    let (sender, receiver) = mpsc::unbounded::<usize>();
+    let mut crashing = receiver.fork_with_config(ForkConfig::delivering_to_idle_clones());
+
+    sender.unbounded_send(1).unwrap();
+    let (item, token) = crashing.next_with_ack().await.unwrap();
+    assert_eq!(item, 1);
+
+    let mut replacement = crashing.clone();
+    drop(crashing);
+    assert_eq!(replacement.n_queued_items(), 1);
+    assert_eq!(replacement.next().await, Some(1));
+
+    drop(token);
+    assert_eq!(replacement.oldest_needed_index(), None);
+}
@@ -0,0 +1,64 @@
+use clone_stream::{CloneStream, ForkConfig, ForkStream};
+use futures::{StreamExt, channel::mpsc, join};
+
+#[tokio::test]
+async fn aligned_clones_receive_same_remaining_items() {
+    let (sender, receiver) = mpsc::unbounded::<usize>();
+    let mut fast = receiver.fork_with_config(ForkConfig::delivering_to_idle_clones());
+    let mut middle = fast.clone();
+    let mut slow = fast.clone();
+
+    for item in 1..=3 {
+        sender.unbounded_send(item).unwrap();
+        assert_eq!(fast.next().await, Some(item));
+    }
+    assert_eq!(slow.next().await, Some(1));
+    assert_eq!(middle.next().await, Some(1));
+    assert_eq!(middle.next().await, Some(2));
+
+    let clones = [fast, middle, slow];
+    CloneStream::align_to_slowest(&clones);
+    let [fast, middle, slow] = clones;
+
+    sender.unbounded_send(4).unwrap();
+    drop(sender);
+
+    let (fast, middle, slow) = join!(
+        fast.collect::<Vec<_>>(),
+        middle.collect::<Vec<_>>(),
+        slow.collect::<Vec<_>>()
+    );
+    assert_eq!(fast, vec![2, 3, 4]);
+    assert_eq!(middle, vec![2, 3, 4]);
+    assert_eq!(slow, vec![2, 3, 4]);
+}
+
+#[tokio::test]
+async fn unlisted_clones_keep_their_position() {
+    let (sender, receiver) = mpsc::unbounded::<usize>();
+    let mut fast = receiver.fork_with_config(ForkConfig::delivering_to_idle_clones());
+    let slow = fast.clone();
+    let mut other = fast.clone();
+
+    sender.unbounded_send(1).unwrap();
+    assert_eq!(fast.next().await, Some(1));
+    assert_eq!(other.next().await, Some(1));
+
+    let clones = [fast, slow];
+    CloneStream::align_to_slowest(&clones);
+    let [mut fast, _slow] = clones;
+
+    assert_eq!(fast.next().await, Some(1));
+    sender.unbounded_send(2).unwrap();
+    assert_eq!(other.next().await, Some(2));
+}
+
+#[test]
+#[should_panic(expected = "same fork")]
+fn clones_of_different_forks_panic() {
+    let first =
+        futures::stream::iter(0..1).fork_with_config(ForkConfig::delivering_to_idle_clones());
+    let second =
+        futures::stream::iter(0..1).fork_with_config(ForkConfig::delivering_to_idle_clones());
+    CloneStream::align_to_slowest(&[first, second]);
+}
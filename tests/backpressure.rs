@@ -0,0 +1,61 @@
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+    task::{Context, Poll},
+};
+
+use clone_stream::{ForkConfig, ForkStream, PollOutcome};
+use futures::{SinkExt, StreamExt, channel::mpsc, task::noop_waker_ref};
+
+const ITEMS: usize = 20;
+
+#[tokio::test]
+async fn producer_blocks_until_slow_clone_drains() {
+    let (mut sender, receiver) = mpsc::channel::<usize>(2);
+    let sent = Arc::new(AtomicUsize::new(0));
+    let sent_counter = sent.clone();
+    let producer = tokio::spawn(async move {
+        for item in 0..ITEMS {
+            sender.send(item).await.unwrap();
+            sent_counter.fetch_add(1, Ordering::SeqCst);
+        }
+    });
+
+    let mut fast = receiver.fork_with_config(ForkConfig {
+        high_water: Some(4),
+        low_water: 1,
+        ..ForkConfig::delivering_to_idle_clones()
+    });
+    let mut slow = fast.clone();
+
+    for expected in 0..4 {
+        assert_eq!(fast.next().await, Some(expected));
+    }
+    let mut cx = Context::from_waker(noop_waker_ref());
+    assert_eq!(
+        fast.poll_next_diagnostic(&mut cx),
+        Poll::Ready(PollOutcome::Backpressured)
+    );
+
+    for _ in 0..10 {
+        tokio::task::yield_now().await;
+    }
+    let blocked_at = sent.load(Ordering::SeqCst);
+    assert!(blocked_at < ITEMS, "Channel filled up");
+    for _ in 0..10 {
+        tokio::task::yield_now().await;
+    }
+    assert_eq!(sent.load(Ordering::SeqCst), blocked_at, "Producer blocked");
+
+    let waiting = tokio::spawn(async move { fast.collect::<Vec<_>>().await });
+    for expected in 0..3 {
+        assert_eq!(slow.next().await, Some(expected));
+    }
+    let rest = slow.collect::<Vec<_>>().await;
+    producer.await.unwrap();
+
+    assert_eq!(rest, (3..ITEMS).collect::<Vec<_>>());
+    assert_eq!(waiting.await.unwrap(), (4..ITEMS).collect::<Vec<_>>());
+}
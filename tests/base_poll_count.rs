@@ -0,0 +1,42 @@
+use clone_stream::{ForkConfig, ForkStream};
+use futures::{StreamExt, channel::mpsc, join, stream};
+
+#[tokio::test]
+async fn base_polled_once_per_item_for_all_clones() {
+    let first = stream::iter(0..5).fork_with_config(ForkConfig::delivering_to_idle_clones());
+    let second = first.clone();
+    let third = first.clone();
+    let mut observer = first.clone();
+    observer.close();
+    assert_eq!(observer.base_poll_count(), 0);
+
+    let (first, second, third) = join!(
+        first.collect::<Vec<_>>(),
+        second.collect::<Vec<_>>(),
+        third.collect::<Vec<_>>()
+    );
+
+    assert_eq!((first.len(), second.len(), third.len()), (5, 5, 5));
+    assert_eq!(observer.base_poll_count(), 6, "Five items and the end");
+}
+
+#[tokio::test]
+async fn base_poll_count_grows_with_items_not_clones() {
+    let (sender, receiver) = mpsc::unbounded::<usize>();
+    let mut first = receiver.fork_with_config(ForkConfig::delivering_to_idle_clones());
+    let mut second = first.clone();
+    let mut third = first.clone();
+
+    for item in 0..5 {
+        sender.unbounded_send(item).unwrap();
+        assert_eq!(first.next().await, Some(item));
+        assert_eq!(second.next().await, Some(item));
+        assert_eq!(third.next().await, Some(item));
+    }
+    assert_eq!(first.base_poll_count(), 5, "One base poll per item");
+
+    drop(sender);
+    let ends = join!(first.next(), second.next(), third.next());
+    assert_eq!(ends, (None, None, None));
+    assert_eq!(third.base_poll_count(), 6);
+}
@@ -0,0 +1,21 @@
+use clone_stream::{ForkConfig, ForkStream};
+use futures::{StreamExt, stream};
+
+#[tokio::test]
+async fn fast_clone_drives_base_stream() {
+    let mut fast = stream::iter(0..5).fork_with_config(ForkConfig::delivering_to_idle_clones());
+    let mut slow = fast.clone();
+    assert_eq!(fast.base_stream_driver(), None);
+
+    for expected in 0..5 {
+        assert_eq!(fast.next().await, Some(expected));
+        assert_eq!(slow.base_stream_driver(), Some(fast.id));
+    }
+
+    assert_eq!(
+        slow.next().await,
+        Some(0),
+        "Queued items do not advance the base"
+    );
+    assert_eq!(slow.base_stream_driver(), Some(fast.id));
+}
@@ -0,0 +1,45 @@
+#![cfg(feature = "broadcast")]
+
+use clone_stream::{ForkConfig, ForkReceiver, ForkStream};
+use futures::{StreamExt, channel::mpsc, join};
+use tokio::sync::broadcast;
+
+#[tokio::test]
+async fn forked_stream_into_broadcast_receiver() {
+    let (sender, receiver) = mpsc::unbounded::<usize>();
+    let clone = receiver.fork_with_config(ForkConfig::delivering_to_idle_clones());
+    let other = clone.clone();
+
+    let (bridge, mut first) = clone.into_broadcast(8);
+    let mut second = bridge.subscribe();
+    let pump = tokio::spawn(bridge);
+
+    for item in 0..3 {
+        sender.unbounded_send(item).unwrap();
+    }
+    drop(sender);
+
+    for item in 0..3 {
+        assert_eq!(first.recv().await, Ok(item));
+        assert_eq!(second.recv().await, Ok(item));
+    }
+    pump.await.unwrap();
+    assert_eq!(first.recv().await, Err(broadcast::error::RecvError::Closed));
+    assert_eq!(other.collect::<Vec<_>>().await, vec![0, 1, 2]);
+}
+
+#[tokio::test]
+async fn broadcast_receiver_into_clones() {
+    let (sender, receiver) = broadcast::channel(8);
+    let first = receiver.fork();
+    let second = first.clone();
+
+    for item in ['a', 'b', 'c'] {
+        sender.send(item).unwrap();
+    }
+    drop(sender);
+
+    let (first, second) = join!(first.collect::<Vec<_>>(), second.collect::<Vec<_>>());
+    assert_eq!(first, vec!['a', 'b', 'c']);
+    assert_eq!(second, first);
+}
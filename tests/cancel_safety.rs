@@ -0,0 +1,48 @@
+use clone_stream::{ForkConfig, ForkStream};
+use futures::{StreamExt, poll};
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::UnboundedReceiverStream;
+
+#[tokio::test]
+async fn cancelled_next_delivers_item_once() {
+    let (sender, receiver) = mpsc::unbounded_channel::<char>();
+    let mut adam = UnboundedReceiverStream::new(receiver)
+        .fork_with_config(ForkConfig::delivering_to_idle_clones());
+    let mut bob = adam.clone();
+
+    {
+        let mut next = adam.next();
+        assert!(poll!(&mut next).is_pending());
+    }
+
+    sender.send('a').unwrap();
+    drop(sender);
+
+    assert_eq!(adam.next().await, Some('a'));
+    assert_eq!(adam.next().await, None);
+    assert_eq!(bob.next().await, Some('a'));
+    assert_eq!(bob.next().await, None);
+}
+
+#[tokio::test]
+async fn cancelled_next_after_queue_history() {
+    let (sender, receiver) = mpsc::unbounded_channel::<char>();
+    let mut adam = UnboundedReceiverStream::new(receiver)
+        .fork_with_config(ForkConfig::delivering_to_idle_clones());
+    let mut bob = adam.clone();
+
+    sender.send('a').unwrap();
+    assert_eq!(adam.next().await, Some('a'));
+    assert_eq!(bob.next().await, Some('a'));
+
+    for _ in 0..3 {
+        let mut next = bob.next();
+        assert!(poll!(&mut next).is_pending());
+    }
+
+    sender.send('b').unwrap();
+    drop(sender);
+
+    assert_eq!(bob.collect::<Vec<_>>().await, vec!['b']);
+    assert_eq!(adam.collect::<Vec<_>>().await, vec!['b']);
+}
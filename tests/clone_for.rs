@@ -0,0 +1,63 @@
+use clone_stream::{ForkConfig, ForkStream};
+use futures::{StreamExt, future::poll_fn, stream};
+
+#[tokio::test]
+async fn clone_for_leaves_after_its_items() {
+    let mut full = stream::iter(0..10).fork_with_config(ForkConfig::delivering_to_idle_clones());
+    let mut limited = full.clone_for(3);
+
+    for expected in 0..3 {
+        assert_eq!(limited.next().await, Some(expected));
+    }
+    assert_eq!(full.active_clone_count(), 1, "Unregistered after 3 items");
+    assert_eq!(limited.next().await, None);
+
+    assert_eq!(full.next().await, Some(0));
+    assert_eq!(full.n_queued_items(), 2);
+    assert_eq!(full.collect::<Vec<_>>().await, (1..10).collect::<Vec<_>>());
+}
+
+#[tokio::test]
+async fn clone_for_frees_items_only_it_needed() {
+    let mut full = stream::iter(0..10).fork_with_config(ForkConfig::delivering_to_idle_clones());
+    let mut limited = full.clone_for(3);
+
+    assert_eq!(full.next().await, Some(0));
+    assert_eq!(full.next().await, Some(1));
+    assert_eq!(limited.n_queued_items(), 2);
+
+    for expected in 0..3 {
+        assert_eq!(limited.next().await, Some(expected));
+    }
+    assert_eq!(full.n_queued_items(), 1, "Item 2 stays queued for `full`");
+    assert_eq!(full.next().await, Some(2));
+    assert_eq!(full.n_queued_items(), 0);
+}
+
+#[tokio::test]
+async fn clone_for_counts_items_inspected_with_next() {
+    let full = stream::iter(0..10).fork_with_config(ForkConfig::delivering_to_idle_clones());
+    let mut limited = full.clone_for(2);
+
+    assert_eq!(
+        poll_fn(|cx| limited.with_next(cx, |item| *item)).await,
+        Some(0)
+    );
+    assert_eq!(
+        poll_fn(|cx| limited.with_next(cx, |item| *item)).await,
+        Some(1)
+    );
+    assert_eq!(full.active_clone_count(), 1, "Unregistered after 2 items");
+    assert_eq!(
+        poll_fn(|cx| limited.with_next(cx, |item| *item)).await,
+        None
+    );
+}
+
+#[tokio::test]
+async fn clone_for_counts_only_items_kept_by_filtered_view() {
+    let full = stream::iter(0..10).fork_with_config(ForkConfig::delivering_to_idle_clones());
+    let evens = full.clone_for(2).filtered_view(|item| item % 2 == 0);
+
+    assert_eq!(evens.collect::<Vec<_>>().await, vec![0, 2]);
+}
@@ -0,0 +1,25 @@
+#![cfg(all(debug_assertions, feature = "metrics"))]
+
+use clone_stream::{ForkConfig, ForkStream};
+use futures::{StreamExt, stream};
+
+#[tokio::test]
+async fn single_consumer_does_not_clone_items() {
+    let mut single = stream::iter(0..100).fork();
+
+    assert_eq!(single.by_ref().count().await, 100);
+    assert_eq!(single.clone_op_count(), 0);
+}
+
+#[tokio::test]
+async fn lagging_consumers_clone_items() {
+    let mut first = stream::iter(0..100).fork_with_config(ForkConfig::delivering_to_idle_clones());
+    let mut second = first.clone();
+
+    assert_eq!(first.by_ref().count().await, 100);
+    let queued = first.clone_op_count();
+    assert!(queued >= 100, "Every item queued for the lagging clone");
+
+    assert_eq!(second.by_ref().count().await, 100);
+    assert!(second.clone_op_count() >= queued);
+}
@@ -0,0 +1,44 @@
+use clone_stream::{ForkConfig, ForkStream};
+use futures::{StreamExt, channel::mpsc};
+
+#[tokio::test]
+async fn closed_clone_ends_while_sibling_continues() {
+    let (sender, receiver) = mpsc::unbounded::<usize>();
+    let mut open = receiver.fork_with_config(ForkConfig::delivering_to_idle_clones());
+    let mut closed = open.clone();
+
+    sender.unbounded_send(1).unwrap();
+    assert_eq!(open.next().await, Some(1));
+    assert_eq!(closed.n_queued_items(), 1);
+
+    closed.close();
+    assert_eq!(closed.next().await, None);
+    assert_eq!(open.active_clone_count(), 1);
+    assert_eq!(
+        open.n_queued_items(),
+        0,
+        "Items only the closed clone needed are discarded"
+    );
+
+    sender.unbounded_send(2).unwrap();
+    assert_eq!(open.next().await, Some(2));
+    assert_eq!(closed.next().await, None);
+}
+
+#[tokio::test]
+async fn closed_clone_keeps_its_id_until_dropped() {
+    let (_sender, receiver) = mpsc::unbounded::<usize>();
+    let first = receiver.fork_with_config(ForkConfig::delivering_to_idle_clones());
+    let mut closed = first.clone();
+    closed.close();
+
+    let new = first.clone();
+    assert_ne!(new.id, closed.id, "ID of a closed clone is not reused");
+    assert_eq!(closed.n_queued_items(), 0);
+
+    let closed_id = closed.id;
+    drop(closed);
+    let reused = first.clone();
+    assert_eq!(reused.id, closed_id);
+    assert_eq!(first.active_clone_count(), 3);
+}
@@ -0,0 +1,31 @@
+use clone_stream::{ForkConfig, ForkStream};
+use futures::stream;
+
+#[tokio::test]
+async fn reuses_buffer_across_forks() {
+    let mut buffer = Vec::with_capacity(4);
+    let capacity = buffer.capacity();
+
+    stream::iter(0..4)
+        .fork_with_config(ForkConfig::delivering_to_idle_clones())
+        .collect_into(&mut buffer)
+        .await;
+    assert_eq!(buffer, vec![0, 1, 2, 3]);
+    assert_eq!(buffer.capacity(), capacity);
+
+    buffer.clear();
+    let fork = stream::iter(4..8).fork_with_config(ForkConfig::delivering_to_idle_clones());
+    let clone = fork.clone();
+    clone.collect_into(&mut buffer).await;
+    assert_eq!(buffer, vec![4, 5, 6, 7]);
+    assert_eq!(buffer.capacity(), capacity);
+
+    buffer.clear();
+    fork.collect_into(&mut buffer).await;
+    assert_eq!(
+        buffer,
+        vec![4, 5, 6, 7],
+        "Other clone still receives all items"
+    );
+    assert_eq!(buffer.capacity(), capacity);
+}
@@ -0,0 +1,43 @@
+use std::sync::{
+    Arc,
+    atomic::{AtomicBool, Ordering},
+};
+
+use clone_stream::{ForkConfig, ForkStream};
+use futures::{StreamExt, future, stream};
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn inspecting_does_not_change_delivery() {
+    const ITEMS: usize = 200;
+    const CONSUMERS: usize = 4;
+    const INSPECTORS: usize = 4;
+
+    let forked = stream::iter(0..ITEMS).fork_with_config(ForkConfig::delivering_to_idle_clones());
+    let done = Arc::new(AtomicBool::new(false));
+
+    let inspectors = (0..INSPECTORS)
+        .map(|_| {
+            let inspected = forked.clone();
+            let done = done.clone();
+            tokio::spawn(async move {
+                while !done.load(Ordering::Relaxed) {
+                    assert!(inspected.n_queued_items() <= ITEMS + 1);
+                    tokio::task::yield_now().await;
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+    let consumers = (0..CONSUMERS)
+        .map(|_| forked.clone())
+        .map(|clone| tokio::spawn(clone.collect::<Vec<_>>()))
+        .collect::<Vec<_>>();
+    drop(forked);
+
+    let received = future::try_join_all(consumers).await.unwrap();
+    done.store(true, Ordering::Relaxed);
+    future::try_join_all(inspectors).await.unwrap();
+
+    for items in received {
+        assert_eq!(items, (0..ITEMS).collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,44 @@
+#![cfg(feature = "tokio")]
+
+use std::sync::{
+    Arc,
+    atomic::{AtomicUsize, Ordering},
+};
+
+use clone_stream::{ForkConfig, ForkStream};
+use futures::{StreamExt, stream};
+
+const BACKLOG: usize = 10_000;
+
+#[tokio::test(flavor = "current_thread")]
+async fn other_tasks_progress_while_draining_backlog() {
+    let mut ahead =
+        stream::iter(0..BACKLOG).fork_with_config(ForkConfig::delivering_to_idle_clones());
+    let mut behind = ahead.clone();
+    while ahead.next().await.is_some() {}
+    assert_eq!(behind.n_queued_items(), BACKLOG + 1);
+
+    let ticks = Arc::new(AtomicUsize::new(0));
+    let ticker = tokio::spawn({
+        let ticks = ticks.clone();
+        async move {
+            loop {
+                ticks.fetch_add(1, Ordering::SeqCst);
+                tokio::task::yield_now().await;
+            }
+        }
+    });
+
+    let mut drained = 0;
+    while behind.next().await.is_some() {
+        drained += 1;
+    }
+    let ticks_during_drain = ticks.load(Ordering::SeqCst);
+    ticker.abort();
+
+    assert_eq!(drained, BACKLOG);
+    assert!(
+        ticks_during_drain > 0,
+        "Other task should run while the clone drains its backlog"
+    );
+}
@@ -0,0 +1,54 @@
+use clone_stream::{ForkConfig, ForkStream};
+use futures::{StreamExt, stream};
+
+#[tokio::test]
+async fn count_after_consuming_two() {
+    let mut clone = stream::iter(0..5).fork_with_config(ForkConfig::delivering_to_idle_clones());
+    let sibling = clone.clone();
+    assert_eq!(clone.next().await, Some(0));
+    assert_eq!(clone.next().await, Some(1));
+
+    assert_eq!(clone.count_remaining().await, 3);
+    assert_eq!(
+        clone.count_remaining().await,
+        3,
+        "Counting does not consume"
+    );
+
+    assert_eq!(clone.collect::<Vec<_>>().await, vec![2, 3, 4]);
+    assert_eq!(sibling.collect::<Vec<_>>().await, vec![0, 1, 2, 3, 4]);
+}
+
+#[tokio::test]
+async fn count_of_exhausted_clone_is_zero() {
+    let mut clone = stream::iter(0..2).fork_with_config(ForkConfig::delivering_to_idle_clones());
+    while clone.next().await.is_some() {}
+
+    assert_eq!(clone.count_remaining().await, 0);
+}
+
+#[tokio::test]
+async fn count_includes_pushed_back_and_replayed_items() {
+    let mut first = stream::iter(0..5).fork_with_replay(2);
+    assert_eq!(first.next().await, Some(0));
+    assert_eq!(first.next().await, Some(1));
+    let mut late = first.clone();
+    let item = late.next().await.unwrap();
+    late.push_back(item);
+
+    assert_eq!(late.count_remaining().await, 5);
+    assert_eq!(late.len(), Some(5));
+    assert_eq!(late.collect::<Vec<_>>().await, vec![0, 1, 2, 3, 4]);
+}
+
+#[tokio::test]
+async fn count_ignores_backpressure() {
+    let clone = stream::iter(0..5).fork_with_config(ForkConfig {
+        high_water: Some(2),
+        ..ForkConfig::delivering_to_idle_clones()
+    });
+    let sibling = clone.clone();
+
+    assert_eq!(clone.count_remaining().await, 5);
+    assert_eq!(sibling.collect::<Vec<_>>().await, vec![0, 1, 2, 3, 4]);
+}
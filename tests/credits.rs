@@ -0,0 +1,34 @@
+use clone_stream::ForkStream;
+use futures::{StreamExt, poll, stream};
+
+#[tokio::test]
+async fn third_item_waits_for_credits() {
+    let mut stream = stream::iter(0..5).fork_with_credits();
+    stream.grant_credits(2);
+
+    assert_eq!(stream.next().await, Some(0));
+    assert_eq!(stream.next().await, Some(1));
+    assert_eq!(stream.credits(), Some(0));
+
+    let mut third = stream.next();
+    assert!(poll!(&mut third).is_pending());
+    assert!(poll!(&mut third).is_pending());
+    drop(third);
+
+    stream.grant_credits(1);
+    assert_eq!(stream.next().await, Some(2));
+}
+
+#[tokio::test]
+async fn clone_without_credits_keeps_its_items_queued() {
+    let mut first = stream::iter(0..3).fork_with_credits();
+    let mut second = first.clone();
+    first.grant_credits(4);
+
+    assert!(poll!(second.next()).is_pending());
+    assert_eq!(first.by_ref().collect::<Vec<_>>().await, vec![0, 1, 2]);
+    assert_eq!(first.credits(), Some(0), "The end used the last credit");
+
+    second.grant_credits(4);
+    assert_eq!(second.collect::<Vec<_>>().await, vec![0, 1, 2]);
+}
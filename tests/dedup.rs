@@ -0,0 +1,28 @@
+use clone_stream::ForkStream;
+use futures::{StreamExt, join, stream};
+
+#[tokio::test]
+async fn consecutive_duplicates_are_skipped() {
+    let first = stream::iter([1, 1, 2, 2, 2, 3]).fork_dedup();
+    let second = first.clone();
+
+    let (first, second) = join!(first.collect::<Vec<_>>(), second.collect::<Vec<_>>());
+
+    assert_eq!(first, vec![1, 2, 3]);
+    assert_eq!(second, vec![1, 2, 3]);
+}
+
+#[tokio::test]
+async fn dedup_depends_on_start_position() {
+    let mut first = stream::iter([1, 1, 2]).fork_dedup();
+    assert_eq!(first.next().await, Some(1));
+
+    let mut second = first.clone();
+    assert_eq!(
+        second.next().await,
+        Some(1),
+        "New clone has no previous item"
+    );
+    assert_eq!(first.next().await, Some(2));
+    assert_eq!(second.next().await, Some(2));
+}
@@ -0,0 +1,42 @@
+#![cfg(feature = "test-util")]
+
+use clone_stream::{DeliveryAuditor, ForkConfig, ForkStream};
+use futures::{StreamExt, join, stream};
+
+#[tokio::test]
+async fn every_clone_receives_every_item_once() {
+    let source: Vec<usize> = (0..50).collect();
+    let mut auditor = DeliveryAuditor::new();
+    let first =
+        stream::iter(source.clone()).fork_with_config(ForkConfig::delivering_to_idle_clones());
+    let second = auditor.watch(first.clone());
+    let third = auditor.watch(first.clone());
+    let first = auditor.watch(first);
+
+    join!(
+        first.collect::<Vec<_>>(),
+        second.collect::<Vec<_>>(),
+        third.collect::<Vec<_>>()
+    );
+
+    auditor.assert_exactly_once_broadcast(&source);
+}
+
+#[tokio::test]
+#[should_panic(expected = "Clone 1 diverged at position 2: expected Some(2), received Some(3)")]
+async fn missed_item_reported() {
+    let mut auditor = DeliveryAuditor::new();
+    auditor.watch(stream::iter([0, 1, 2, 3])).count().await;
+    auditor.watch(stream::iter([0, 1, 3])).count().await;
+
+    auditor.assert_exactly_once_broadcast(&[0, 1, 2, 3]);
+}
+
+#[tokio::test]
+#[should_panic(expected = "Clone 0 diverged at position 2: expected None, received Some(1)")]
+async fn duplicated_item_reported() {
+    let mut auditor = DeliveryAuditor::new();
+    auditor.watch(stream::iter([0, 1, 1])).count().await;
+
+    auditor.assert_exactly_once_broadcast(&[0, 1]);
+}
@@ -0,0 +1,35 @@
+use core::time::Duration;
+
+use clone_stream::{ForkConfig, ForkStream};
+use futures::{StreamExt, channel::mpsc};
+use tokio::time::timeout;
+
+#[tokio::test]
+async fn drain_queued_items_in_one_call() {
+    let (sender, receiver) = mpsc::unbounded::<usize>();
+    let mut fast = receiver.fork_with_config(ForkConfig::delivering_to_idle_clones());
+    let mut slow = fast.clone();
+
+    for item in 0..3 {
+        sender.unbounded_send(item).unwrap();
+    }
+    assert_eq!(fast.drain_ready(), vec![0, 1, 2]);
+    assert_eq!(
+        slow.drain_ready(),
+        vec![0, 1, 2],
+        "Items came from the queue"
+    );
+    assert!(slow.drain_ready().is_empty());
+
+    // Draining did not leave a stale registration behind: a real wait is
+    // still woken by the next item.
+    let waiting = tokio::spawn(async move { slow.next().await });
+    tokio::task::yield_now().await;
+    sender.unbounded_send(3).unwrap();
+
+    let next = timeout(Duration::from_millis(100), waiting)
+        .await
+        .expect("Waiting clone was not woken")
+        .unwrap();
+    assert_eq!(next, Some(3));
+}
@@ -0,0 +1,61 @@
+use std::sync::Arc;
+
+use clone_stream::{CloneStream, ForkConfig, ForkStream};
+use futures::{StreamExt, channel::mpsc};
+
+type SharedItemClone = CloneStream<mpsc::UnboundedReceiver<Arc<usize>>>;
+
+/// Forks a channel of shared items and returns a clone that has consumed
+/// `item`, which stays queued for the returned lagging clone.
+async fn queued_for_lagging_clone(
+    config: ForkConfig,
+    item: &Arc<usize>,
+) -> (
+    mpsc::UnboundedSender<Arc<usize>>,
+    SharedItemClone,
+    SharedItemClone,
+) {
+    let (sender, receiver) = mpsc::unbounded();
+    let mut ahead = receiver.fork_with_config(config);
+    let behind = ahead.clone();
+
+    sender.unbounded_send(item.clone()).unwrap();
+    assert_eq!(ahead.next().await.as_deref(), Some(&1));
+    (sender, ahead, behind)
+}
+
+#[tokio::test]
+async fn eager_cleanup_reclaims_at_drop() {
+    let item = Arc::new(1);
+    let config = ForkConfig::delivering_to_idle_clones();
+    let (_sender, _ahead, behind) = queued_for_lagging_clone(config, &item).await;
+    assert_eq!(
+        Arc::strong_count(&item),
+        2,
+        "Item is queued for the lagging clone"
+    );
+
+    drop(behind);
+    assert_eq!(Arc::strong_count(&item), 1, "Queued item reclaimed at drop");
+}
+
+#[tokio::test]
+async fn deferred_cleanup_reclaims_on_next_poll() {
+    let config = ForkConfig {
+        eager_drop_cleanup: false,
+        ..ForkConfig::delivering_to_idle_clones()
+    };
+    let item = Arc::new(1);
+    let (sender, mut ahead, behind) = queued_for_lagging_clone(config, &item).await;
+
+    drop(behind);
+    assert_eq!(Arc::strong_count(&item), 2, "Queued item kept after drop");
+
+    sender.unbounded_send(Arc::new(2)).unwrap();
+    assert_eq!(ahead.next().await.as_deref(), Some(&2));
+    assert_eq!(
+        Arc::strong_count(&item),
+        1,
+        "Queued item reclaimed on next poll"
+    );
+}
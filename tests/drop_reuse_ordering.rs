@@ -0,0 +1,82 @@
+use clone_stream::{CloneStream, ForkConfig, ForkStream};
+use futures::{FutureExt, StreamExt, channel::mpsc};
+
+const OPERATIONS: usize = 1000;
+
+/// Linear congruential generator, so every run performs the same operations.
+struct Operations(u64);
+
+impl Operations {
+    fn next_below(&mut self, bound: usize) -> usize {
+        self.0 = self
+            .0
+            .wrapping_mul(6_364_136_223_846_793_005)
+            .wrapping_add(1_442_695_040_888_963_407);
+        (self.0 >> 33) as usize % bound
+    }
+}
+
+struct Consumer {
+    clone: CloneStream<mpsc::UnboundedReceiver<u64>>,
+    received: Vec<u64>,
+}
+
+impl Consumer {
+    fn new(clone: CloneStream<mpsc::UnboundedReceiver<u64>>) -> Self {
+        Self {
+            clone,
+            received: Vec::new(),
+        }
+    }
+
+    fn assert_without_gaps(&self) {
+        assert!(
+            self.received.windows(2).all(|pair| pair[1] == pair[0] + 1),
+            "Received out of order or with gaps: {:?}",
+            self.received
+        );
+    }
+}
+
+#[test]
+fn random_clone_drop_send_keeps_order() {
+    let (sender, receiver) = mpsc::unbounded::<u64>();
+    let mut consumers = vec![Consumer::new(receiver.fork_with_config(ForkConfig {
+        initial_queue_capacity: 4,
+        ..ForkConfig::delivering_to_idle_clones()
+    }))];
+    let mut operations = Operations(0x5eed);
+    let mut sent = 0;
+
+    for _ in 0..OPERATIONS {
+        let chosen = operations.next_below(consumers.len());
+        match operations.next_below(4) {
+            0 => {
+                sender.unbounded_send(sent).unwrap();
+                sent += 1;
+            }
+            1 => consumers.push(Consumer::new(consumers[chosen].clone.clone())),
+            2 if consumers.len() > 1 => {
+                consumers.swap_remove(chosen);
+            }
+            _ => {
+                let consumer = &mut consumers[chosen];
+                if let Some(Some(item)) = consumer.clone.next().now_or_never() {
+                    consumer.received.push(item);
+                }
+            }
+        }
+        consumers.iter().for_each(Consumer::assert_without_gaps);
+    }
+    drop(sender);
+
+    for consumer in &mut consumers {
+        while let Some(Some(item)) = consumer.clone.next().now_or_never() {
+            consumer.received.push(item);
+        }
+        consumer.assert_without_gaps();
+        if let Some(&last) = consumer.received.last() {
+            assert_eq!(last, sent - 1, "Surviving clone received every item");
+        }
+    }
+}
@@ -0,0 +1,73 @@
+use std::{
+    pin::Pin,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+    task::{Context, Poll},
+};
+
+use clone_stream::{ForkConfig, ForkStream};
+use futures::{Stream, StreamExt, channel::mpsc, join};
+
+#[tokio::test]
+async fn pending_clones_resolve_when_sender_dropped() {
+    let (sender, receiver) = mpsc::unbounded::<usize>();
+    let mut first = receiver.fork();
+    let mut second = first.clone();
+
+    let ends = tokio::spawn(async move { join!(first.next(), second.next()) });
+    tokio::task::yield_now().await;
+    assert!(!ends.is_finished());
+
+    drop(sender);
+    assert_eq!(ends.await.unwrap(), (None, None));
+}
+
+/// Stream that ends once `ended` is set, without waking anyone.
+struct SilentEnd {
+    ended: Arc<AtomicBool>,
+}
+
+impl Stream for SilentEnd {
+    type Item = usize;
+
+    fn poll_next(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Option<usize>> {
+        if self.ended.load(Ordering::SeqCst) {
+            Poll::Ready(None)
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+async fn end_reaches_waiting_clone(config: ForkConfig) {
+    let ended = Arc::new(AtomicBool::new(false));
+    let mut first = SilentEnd {
+        ended: ended.clone(),
+    }
+    .fork_with_config(config);
+    let mut second = first.clone();
+
+    let waiting = tokio::spawn(async move { second.next().await });
+    tokio::task::yield_now().await;
+    assert!(!waiting.is_finished());
+
+    ended.store(true, Ordering::SeqCst);
+    assert_eq!(first.next().await, None);
+    assert_eq!(waiting.await.unwrap(), None);
+}
+
+#[tokio::test]
+async fn end_wakes_clones_waiting_on_base_stream() {
+    end_reaches_waiting_clone(ForkConfig::default()).await;
+}
+
+#[tokio::test]
+async fn end_wakes_clones_with_selective_wakeups() {
+    end_reaches_waiting_clone(ForkConfig {
+        selective_wakeups: true,
+        ..ForkConfig::default()
+    })
+    .await;
+}
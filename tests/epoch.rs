@@ -0,0 +1,29 @@
+use clone_stream::{ForkConfig, ForkStream};
+use futures::{StreamExt, stream};
+
+#[tokio::test]
+async fn items_counted_since_epoch() {
+    let mut fast = stream::iter(0..20).fork_with_config(ForkConfig::delivering_to_idle_clones());
+    let mut slow = fast.clone();
+
+    for _ in 0..5 {
+        fast.next().await;
+    }
+    let epoch = fast.mark_epoch();
+    assert_eq!(epoch, 5);
+    assert_eq!(slow.mark_epoch(), 0, "Slow clone has received nothing");
+
+    for _ in 0..7 {
+        fast.next().await;
+    }
+    assert_eq!(fast.items_since_epoch(epoch), 7);
+
+    for _ in 0..8 {
+        slow.next().await;
+    }
+    assert_eq!(
+        slow.items_since_epoch(epoch),
+        3,
+        "Only items produced after the epoch count"
+    );
+}
@@ -0,0 +1,23 @@
+use clone_stream::CloneStreamError;
+
+#[test]
+fn max_clones_exceeded_mentions_numbers_and_hint() {
+    let message = CloneStreamError::MaxClonesExceeded {
+        current_count: 5,
+        max_allowed: 7,
+    }
+    .to_string();
+
+    assert!(message.contains('5'), "{message}");
+    assert!(message.contains('7'), "{message}");
+    assert!(message.contains("max_clone_count"), "{message}");
+}
+
+#[test]
+fn clone_id_errors_mention_id() {
+    let invalid = CloneStreamError::InvalidCloneId { clone_id: 42 }.to_string();
+    let active = CloneStreamError::CloneAlreadyActive { clone_id: 43 }.to_string();
+
+    assert!(invalid.contains("42"), "{invalid}");
+    assert!(active.contains("43"), "{active}");
+}
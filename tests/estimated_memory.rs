@@ -0,0 +1,38 @@
+use clone_stream::{ForkConfig, ForkStream};
+use futures::{StreamExt, channel::mpsc};
+
+type Sample = [u64; 4];
+
+#[tokio::test]
+async fn estimate_grows_by_item_size_per_queued_item() {
+    let (sender, receiver) = mpsc::unbounded::<Sample>();
+    let mut fast = receiver.fork_with_config(ForkConfig::delivering_to_idle_clones());
+    let slow = fast.clone();
+    let empty = slow.estimated_memory();
+
+    for index in 0..5 {
+        sender.unbounded_send([index; 4]).unwrap();
+        fast.next().await;
+    }
+
+    assert_eq!(slow.n_queued_items(), 5);
+    assert_eq!(
+        slow.estimated_memory() - empty,
+        size_of::<Sample>() * slow.n_queued_items()
+    );
+    assert_eq!(fast.estimated_memory(), empty);
+}
+
+#[tokio::test]
+async fn estimate_includes_pushed_back_items() {
+    let (sender, receiver) = mpsc::unbounded::<Sample>();
+    let mut clone = receiver.fork();
+    let empty = clone.estimated_memory();
+
+    sender.unbounded_send([1; 4]).unwrap();
+    let item = clone.next().await.unwrap();
+    assert_eq!(clone.estimated_memory(), empty);
+
+    clone.push_back(item);
+    assert_eq!(clone.estimated_memory() - empty, size_of::<Sample>());
+}
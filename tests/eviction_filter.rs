@@ -0,0 +1,43 @@
+use clone_stream::{ForkConfig, ForkStream};
+use futures::{StreamExt, channel::mpsc};
+
+fn queue_of(max_queue_size: usize) -> ForkConfig {
+    ForkConfig {
+        max_queue_size,
+        ..ForkConfig::delivering_to_idle_clones()
+    }
+}
+
+#[tokio::test]
+async fn pinned_items_survive_eviction_pressure() {
+    let (sender, receiver) = mpsc::unbounded::<usize>();
+    let mut fast = receiver
+        .fork_with_config(queue_of(4))
+        .with_eviction_filter(|item| item % 3 != 0);
+    let slow = fast.clone();
+
+    for item in 0..9 {
+        sender.unbounded_send(item).unwrap();
+        assert_eq!(fast.next().await, Some(item));
+    }
+    drop(sender);
+
+    assert_eq!(slow.collect::<Vec<_>>().await, vec![0, 3, 6, 8]);
+}
+
+#[tokio::test]
+async fn oldest_is_evicted_when_everything_is_pinned() {
+    let (sender, receiver) = mpsc::unbounded::<usize>();
+    let mut fast = receiver
+        .fork_with_config(queue_of(2))
+        .with_eviction_filter(|_| false);
+    let slow = fast.clone();
+
+    for item in 0..5 {
+        sender.unbounded_send(item).unwrap();
+        assert_eq!(fast.next().await, Some(item));
+    }
+    drop(sender);
+
+    assert_eq!(slow.collect::<Vec<_>>().await, vec![3, 4]);
+}
@@ -0,0 +1,31 @@
+use clone_stream::{ForkConfig, ForkStream};
+use futures::{StreamExt, channel::mpsc, stream};
+
+#[tokio::test]
+async fn exact_len_after_base_stream_ended() {
+    let mut clone = stream::iter(0..5).fork_with_config(ForkConfig::delivering_to_idle_clones());
+    assert_eq!(clone.count_remaining().await, 5);
+
+    clone.next().await;
+    clone.next().await;
+
+    assert_eq!(clone.len(), Some(3));
+    assert_eq!(clone.by_ref().count().await, 3);
+    assert_eq!(clone.len(), Some(0));
+    assert_eq!(clone.is_empty(), Some(true));
+}
+
+#[tokio::test]
+async fn no_len_while_base_stream_active() {
+    let (sender, receiver) = mpsc::unbounded::<usize>();
+    let mut first = receiver.fork_with_config(ForkConfig::delivering_to_idle_clones());
+    let second = first.clone();
+
+    sender.unbounded_send(1).unwrap();
+    assert_eq!(first.next().await, Some(1));
+    assert_eq!(second.len(), None);
+
+    drop(sender);
+    assert_eq!(first.next().await, None);
+    assert_eq!(second.len(), Some(1));
+}
@@ -0,0 +1,47 @@
+use clone_stream::{ForkConfig, ForkStream};
+use futures::{StreamExt, channel::mpsc};
+
+#[tokio::test]
+async fn queued_backlog_before_ready_base_item() {
+    let (sender, receiver) = mpsc::unbounded::<usize>();
+    let mut fast = receiver.fork_with_config(ForkConfig::delivering_to_idle_clones());
+    let mut slow = fast.clone();
+
+    sender.unbounded_send(1).unwrap();
+    assert_eq!(fast.next().await, Some(1));
+    assert_eq!(
+        slow.next().await,
+        Some(1),
+        "Slow clone now has queue history"
+    );
+
+    sender.unbounded_send(2).unwrap();
+    sender.unbounded_send(3).unwrap();
+    assert_eq!(fast.next().await, Some(2));
+    assert_eq!(fast.next().await, Some(3));
+
+    // The base stream is ready with 4 while 2 and 3 are still queued.
+    sender.unbounded_send(4).unwrap();
+    assert_eq!(slow.next().await, Some(2));
+    assert_eq!(slow.next().await, Some(3));
+    assert_eq!(slow.next().await, Some(4));
+    assert_eq!(fast.next().await, Some(4));
+}
+
+#[tokio::test]
+async fn fresh_clone_drains_backlog_before_base_stream() {
+    let (sender, receiver) = mpsc::unbounded::<usize>();
+    let mut fast = receiver.fork_with_config(ForkConfig::delivering_to_idle_clones());
+    let mut slow = fast.clone();
+
+    for item in 0..3 {
+        sender.unbounded_send(item).unwrap();
+        assert_eq!(fast.next().await, Some(item));
+    }
+    sender.unbounded_send(3).unwrap();
+    drop(sender);
+
+    assert_eq!(slow.by_ref().collect::<Vec<_>>().await, vec![0, 1, 2, 3]);
+    assert_eq!(fast.next().await, Some(3));
+    assert_eq!(fast.next().await, None);
+}
@@ -0,0 +1,108 @@
+use std::sync::{
+    Arc,
+    atomic::{AtomicUsize, Ordering},
+};
+
+use clone_stream::{ForkConfig, ForkStream};
+use futures::{StreamExt, join, stream};
+
+/// Item that counts how often it is cloned.
+struct Counted {
+    value: usize,
+    clones: Arc<AtomicUsize>,
+}
+
+impl Clone for Counted {
+    fn clone(&self) -> Self {
+        self.clones.fetch_add(1, Ordering::SeqCst);
+        Self {
+            value: self.value,
+            clones: self.clones.clone(),
+        }
+    }
+}
+
+fn counted(values: std::ops::Range<usize>, clones: &Arc<AtomicUsize>) -> Vec<Counted> {
+    values
+        .map(|value| Counted {
+            value,
+            clones: clones.clone(),
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn clones_filter_shared_queue_differently() {
+    let evens = stream::iter(0..10).fork_with_config(ForkConfig::delivering_to_idle_clones());
+    let odds = evens.clone();
+
+    let (evens, odds) = join!(
+        evens
+            .filtered_view(|item| item % 2 == 0)
+            .collect::<Vec<_>>(),
+        odds.filtered_view(|item| item % 2 == 1).collect::<Vec<_>>()
+    );
+
+    assert_eq!(evens, vec![0, 2, 4, 6, 8]);
+    assert_eq!(odds, vec![1, 3, 5, 7, 9]);
+}
+
+#[tokio::test]
+async fn other_clones_still_receive_skipped_items() {
+    let mut all = stream::iter(0..10).fork_with_config(ForkConfig::delivering_to_idle_clones());
+    let mut large = all.clone().filtered_view(|item| *item >= 5);
+
+    assert_eq!(large.next().await, Some(5));
+    assert_eq!(
+        all.n_queued_items(),
+        6,
+        "Items 0 to 5 stay queued for `all`"
+    );
+    assert_eq!(all.next().await, Some(0));
+    assert_eq!(all.n_queued_items(), 5);
+}
+
+#[tokio::test]
+async fn pushed_back_items_count_once_towards_clone_for() {
+    let full = stream::iter(0..10).fork_with_config(ForkConfig::delivering_to_idle_clones());
+    let mut limited = full.clone_for(3);
+
+    let first = limited.next().await.unwrap();
+    let second = limited.next().await.unwrap();
+    limited.push_back(second);
+    limited.push_back(first);
+
+    let evens = limited.filtered_view(|item| item % 2 == 0);
+    assert_eq!(
+        evens.collect::<Vec<_>>().await,
+        vec![0, 2],
+        "Rejected pushed back item 1 is discarded"
+    );
+}
+
+#[tokio::test]
+async fn kept_items_are_cloned_only_for_other_clones() {
+    let clones = Arc::new(AtomicUsize::new(0));
+    let evens = stream::iter(counted(0..4, &clones))
+        .fork()
+        .filtered_view(|item| item.value % 2 == 0);
+    let values: Vec<_> = evens.map(|item| item.value).collect().await;
+    assert_eq!(values, vec![0, 2]);
+    assert_eq!(
+        clones.load(Ordering::SeqCst),
+        0,
+        "Last consumer takes items"
+    );
+
+    let mut all = stream::iter(counted(0..4, &clones))
+        .fork_with_config(ForkConfig::delivering_to_idle_clones());
+    let mut evens = all.clone().filtered_view(|item| item.value % 2 == 0);
+    assert_eq!(evens.next().await.map(|item| item.value), Some(0));
+    assert_eq!(evens.next().await.map(|item| item.value), Some(2));
+    assert_eq!(
+        clones.load(Ordering::SeqCst),
+        3,
+        "Only the copies of items 0 to 2 queued for `all` are made"
+    );
+    assert_eq!(all.next().await.map(|item| item.value), Some(0));
+}
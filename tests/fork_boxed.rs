@@ -0,0 +1,23 @@
+use clone_stream::{BoxedCloneStream, ForkStream};
+use futures::{StreamExt, channel::mpsc, join};
+
+fn squares(receiver: mpsc::UnboundedReceiver<i32>) -> BoxedCloneStream<i32> {
+    receiver.map(|n| n * n).fork_boxed()
+}
+
+#[tokio::test]
+async fn boxed_clone_stream_from_function() {
+    let (sender, receiver) = mpsc::unbounded();
+    let first = squares(receiver);
+    let second = first.clone();
+
+    let send = async move {
+        for n in 1..=3 {
+            sender.unbounded_send(n).unwrap();
+        }
+    };
+    let (first, second, ()) = join!(first.collect::<Vec<_>>(), second.collect::<Vec<_>>(), send);
+
+    assert_eq!(first, vec![1, 4, 9]);
+    assert_eq!(second, first);
+}
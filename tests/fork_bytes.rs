@@ -0,0 +1,47 @@
+#![cfg(feature = "bytes")]
+
+use bytes::Bytes;
+use clone_stream::ForkStream;
+use futures::{StreamExt, channel::mpsc, join};
+
+#[tokio::test]
+async fn clones_share_backing_storage() {
+    let (sender, receiver) = mpsc::unbounded::<Bytes>();
+    let first = receiver.fork_bytes();
+    let second = first.clone();
+    let third = first.clone();
+
+    let chunks = [Bytes::from(vec![1_u8; 1024]), Bytes::from(vec![2_u8; 16])];
+    for chunk in &chunks {
+        sender.unbounded_send(chunk.clone()).unwrap();
+    }
+    drop(sender);
+
+    let (first, second, third) = join!(
+        first.collect::<Vec<_>>(),
+        second.collect::<Vec<_>>(),
+        third.collect::<Vec<_>>()
+    );
+
+    for received in [first, second, third] {
+        assert_eq!(received, chunks);
+        for (chunk, original) in received.iter().zip(&chunks) {
+            assert_eq!(chunk.as_ptr(), original.as_ptr(), "Chunk was copied");
+        }
+    }
+}
+
+#[tokio::test]
+async fn buffered_bytes_sums_queued_chunks() {
+    let (sender, receiver) = mpsc::unbounded::<Bytes>();
+    let mut ahead = receiver.fork_bytes();
+    let behind = ahead.clone();
+
+    sender.unbounded_send(Bytes::from_static(b"abc")).unwrap();
+    sender.unbounded_send(Bytes::from_static(b"de")).unwrap();
+    ahead.next().await;
+    ahead.next().await;
+
+    assert_eq!(behind.buffered_bytes(), 5);
+    assert_eq!(ahead.buffered_bytes(), 0);
+}
@@ -0,0 +1,13 @@
+use clone_stream::ForkStream;
+use futures::{StreamExt, join, stream};
+
+#[tokio::test]
+async fn clones_receive_identical_batches() {
+    let first = stream::iter(0..5).fork_chunks(2);
+    let second = first.clone();
+
+    let (first, second) = join!(first.collect::<Vec<_>>(), second.collect::<Vec<_>>());
+
+    assert_eq!(first, vec![vec![0, 1], vec![2, 3], vec![4]]);
+    assert_eq!(second, first);
+}
@@ -0,0 +1,34 @@
+use clone_stream::{ForkConfig, ForkStream, GroupStats};
+use futures::{StreamExt, stream};
+
+#[tokio::test]
+async fn group_drains_full_sequence_per_clone() {
+    let source = stream::iter(0..10).fork_with_config(ForkConfig::delivering_to_idle_clones());
+    let mut group = source.group(3);
+    drop(source);
+
+    let drained = group.drain_all().await;
+
+    assert_eq!(drained.len(), 3);
+    for items in drained {
+        assert_eq!(items, (0..10).collect::<Vec<_>>());
+    }
+}
+
+#[tokio::test]
+async fn stats_and_close_all() {
+    let source = stream::iter(0..4).fork_with_config(ForkConfig::delivering_to_idle_clones());
+    let mut group = source.group(2);
+    drop(source);
+
+    assert_eq!(group.clones_mut()[0].next().await, Some(0));
+    assert_eq!(
+        group.stats(),
+        GroupStats {
+            queued_items: vec![0, 1]
+        }
+    );
+
+    group.close_all();
+    assert_eq!(group.drain_all().await, vec![Vec::<i32>::new(), Vec::new()]);
+}
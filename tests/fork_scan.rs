@@ -0,0 +1,29 @@
+use std::sync::{
+    Arc,
+    atomic::{AtomicUsize, Ordering},
+};
+
+use clone_stream::ForkStream;
+use futures::{StreamExt, join, stream};
+
+#[tokio::test]
+async fn clones_share_running_sum() {
+    let steps = Arc::new(AtomicUsize::new(0));
+    let step_counter = steps.clone();
+    let first = stream::iter([3, 1, 4, 1, 5]).fork_scan(0, move |sum, item| {
+        step_counter.fetch_add(1, Ordering::SeqCst);
+        *sum += item;
+        *sum
+    });
+    let second = first.clone();
+
+    let (first, second) = join!(first.collect::<Vec<_>>(), second.collect::<Vec<_>>());
+
+    assert_eq!(first, vec![3, 4, 8, 9, 14]);
+    assert_eq!(second, first);
+    assert_eq!(
+        steps.load(Ordering::SeqCst),
+        5,
+        "Accumulator advanced once per item"
+    );
+}
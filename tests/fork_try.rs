@@ -0,0 +1,23 @@
+use clone_stream::ForkStream;
+use futures::{StreamExt, join, stream};
+
+#[tokio::test]
+async fn every_clone_receives_error_then_ends() {
+    let first = stream::iter([Ok(1), Ok(2), Err("broken"), Ok(3)]).fork_try();
+    let second = first.clone();
+
+    let (first, second) = join!(first.collect::<Vec<_>>(), second.collect::<Vec<_>>());
+
+    assert_eq!(first, vec![Ok(1), Ok(2), Err("broken")]);
+    assert_eq!(second, vec![Ok(1), Ok(2), Err("broken")]);
+}
+
+#[tokio::test]
+async fn clone_after_error_is_terminated() {
+    let mut first = stream::iter([Err::<u8, _>("broken"), Ok(1)]).fork_try();
+
+    assert_eq!(first.next().await, Some(Err("broken")));
+    let mut second = first.clone();
+    assert_eq!(first.next().await, None);
+    assert_eq!(second.next().await, None);
+}
@@ -0,0 +1,31 @@
+use std::sync::{
+    Arc,
+    atomic::{AtomicUsize, Ordering},
+};
+
+use clone_stream::ForkStream;
+use futures::{StreamExt, join, stream};
+
+#[tokio::test]
+async fn clones_see_identical_parse_results() {
+    let parses = Arc::new(AtomicUsize::new(0));
+    let parse_counter = parses.clone();
+    let first = stream::iter(["4", "four", "2", "-1"]).fork_try_map(move |text| {
+        parse_counter.fetch_add(1, Ordering::SeqCst);
+        text.parse::<u32>().map_err(|error| error.to_string())
+    });
+    let second = first.clone();
+
+    let (first, second) = join!(first.collect::<Vec<_>>(), second.collect::<Vec<_>>());
+
+    let outcomes = first.iter().map(Result::is_ok).collect::<Vec<_>>();
+    assert_eq!(
+        outcomes,
+        vec![true, false, true, false],
+        "Errors do not end the stream"
+    );
+    assert_eq!(first[0], Ok(4));
+    assert_eq!(first[2], Ok(2));
+    assert_eq!(second, first);
+    assert_eq!(parses.load(Ordering::SeqCst), 4, "Mapped once per item");
+}
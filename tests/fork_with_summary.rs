@@ -0,0 +1,35 @@
+use clone_stream::ForkStream;
+use futures::{StreamExt, future::Either, join, stream};
+
+fn as_result<Item, Summary>(item: Either<Item, Summary>) -> Result<Item, Summary> {
+    match item {
+        Either::Left(item) => Ok(item),
+        Either::Right(summary) => Err(summary),
+    }
+}
+
+#[tokio::test]
+async fn every_clone_receives_summary_once() {
+    let first = stream::iter([10, 20, 30]).fork_with_summary(|count| format!("{count} items"));
+    let second = first.clone();
+
+    let (first, second) = join!(
+        first.map(as_result).collect::<Vec<_>>(),
+        second.map(as_result).collect::<Vec<_>>()
+    );
+
+    assert_eq!(
+        first,
+        vec![Ok(10), Ok(20), Ok(30), Err("3 items".to_string())]
+    );
+    assert_eq!(second, first);
+}
+
+#[tokio::test]
+async fn empty_stream_still_summarized() {
+    let summarized = stream::iter(Vec::<u8>::new()).fork_with_summary(|count| count);
+
+    let items = summarized.map(as_result).collect::<Vec<_>>().await;
+
+    assert_eq!(items, vec![Err(0)]);
+}
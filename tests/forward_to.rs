@@ -0,0 +1,27 @@
+use clone_stream::{ForkConfig, ForkStream};
+use futures::{StreamExt, channel::mpsc, join, stream};
+
+#[tokio::test]
+async fn forward_one_clone_while_sibling_collects() {
+    let forwarded = stream::iter(0..5).fork_with_config(ForkConfig::delivering_to_idle_clones());
+    let sibling = forwarded.clone();
+    let (sender, receiver) = mpsc::unbounded();
+
+    let (forwarding, from_sibling) =
+        join!(forwarded.forward_to(sender), sibling.collect::<Vec<_>>());
+
+    forwarding.unwrap();
+    assert_eq!(from_sibling, vec![0, 1, 2, 3, 4]);
+    assert_eq!(receiver.collect::<Vec<_>>().await, vec![0, 1, 2, 3, 4]);
+}
+
+#[tokio::test]
+async fn forward_respects_bounded_sink() {
+    let forwarded = stream::iter(0..10).fork_with_config(ForkConfig::delivering_to_idle_clones());
+    let (sender, receiver) = mpsc::channel(1);
+
+    let (forwarding, collected) = join!(forwarded.forward_to(sender), receiver.collect::<Vec<_>>());
+
+    forwarding.unwrap();
+    assert_eq!(collected, (0..10).collect::<Vec<_>>());
+}
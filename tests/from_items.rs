@@ -0,0 +1,27 @@
+use std::collections::VecDeque;
+
+use clone_stream::CloneStream;
+use futures::{StreamExt, join};
+
+#[tokio::test]
+async fn both_clones_collect_all_items() {
+    let stream = CloneStream::from_items(vec![1, 2, 3]);
+    let clone = stream.clone();
+
+    let (first, second) = join!(stream.collect::<Vec<_>>(), clone.collect::<Vec<_>>());
+
+    assert_eq!(first, vec![1, 2, 3]);
+    assert_eq!(second, vec![1, 2, 3]);
+}
+
+#[tokio::test]
+async fn from_vec_deque_keeps_order() {
+    let items: VecDeque<_> = [3, 2, 1].into_iter().collect();
+    let stream = CloneStream::from(items);
+    let clone = stream.clone();
+
+    let (first, second) = join!(stream.collect::<Vec<_>>(), clone.collect::<Vec<_>>());
+
+    assert_eq!(first, vec![3, 2, 1]);
+    assert_eq!(second, vec![3, 2, 1]);
+}
@@ -0,0 +1,45 @@
+use std::task::{Context, Poll};
+
+use clone_stream::{ForkConfig, ForkStream};
+use futures::{StreamExt, stream, task::noop_waker_ref};
+
+fn traced_config() -> ForkConfig {
+    ForkConfig {
+        record_poll_order: true,
+        ..ForkConfig::default()
+    }
+}
+
+#[test]
+fn fused_clone_skips_fork_after_termination() {
+    let mut clone = stream::iter(0..1)
+        .fork_with_config(traced_config())
+        .fuse_terminated();
+    let mut cx = Context::from_waker(noop_waker_ref());
+
+    assert_eq!(clone.poll_next_unpin(&mut cx), Poll::Ready(Some(0)));
+    assert_eq!(clone.poll_next_unpin(&mut cx), Poll::Ready(None));
+    let events_at_termination = clone.poll_trace().len();
+
+    for _ in 0..10 {
+        assert_eq!(clone.poll_next_unpin(&mut cx), Poll::Ready(None));
+    }
+    assert_eq!(
+        clone.poll_trace().len(),
+        events_at_termination,
+        "Fused clone should not poll the fork after termination"
+    );
+}
+
+#[test]
+fn unfused_clone_keeps_polling_fork() {
+    let mut clone = stream::iter(0..1).fork_with_config(traced_config());
+    let mut cx = Context::from_waker(noop_waker_ref());
+
+    assert_eq!(clone.poll_next_unpin(&mut cx), Poll::Ready(Some(0)));
+    assert_eq!(clone.poll_next_unpin(&mut cx), Poll::Ready(None));
+    let events_at_termination = clone.poll_trace().len();
+
+    assert_eq!(clone.poll_next_unpin(&mut cx), Poll::Ready(None));
+    assert!(clone.poll_trace().len() > events_at_termination);
+}
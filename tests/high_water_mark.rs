@@ -0,0 +1,44 @@
+use clone_stream::{ForkConfig, ForkStream};
+use futures::{StreamExt, channel::mpsc};
+
+#[tokio::test]
+async fn high_water_mark_survives_draining() {
+    let (sender, receiver) = mpsc::unbounded();
+    let mut fast = receiver.fork_with_config(ForkConfig::delivering_to_idle_clones());
+    let mut slow = fast.clone();
+    assert_eq!(fast.buffer_high_water_mark(), 0);
+
+    for item in 0..7 {
+        sender.unbounded_send(item).unwrap();
+        fast.next().await;
+    }
+    assert_eq!(slow.n_queued_items(), 7);
+
+    for _ in 0..7 {
+        slow.next().await;
+    }
+    assert_eq!(slow.n_queued_items(), 0);
+    assert_eq!(slow.buffer_high_water_mark(), 7);
+    assert_eq!(fast.buffer_high_water_mark(), 7);
+}
+
+#[tokio::test]
+async fn reset_starts_from_current_queue_length() {
+    let (sender, receiver) = mpsc::unbounded();
+    let mut fast = receiver.fork_with_config(ForkConfig::delivering_to_idle_clones());
+    let mut slow = fast.clone();
+
+    for item in 0..3 {
+        sender.unbounded_send(item).unwrap();
+        fast.next().await;
+    }
+    slow.next().await;
+    slow.next().await;
+
+    fast.reset_high_water();
+    assert_eq!(fast.buffer_high_water_mark(), 1);
+
+    slow.next().await;
+    fast.reset_high_water();
+    assert_eq!(slow.buffer_high_water_mark(), 0);
+}
@@ -0,0 +1,36 @@
+use std::task::{Context, Poll};
+
+use clone_stream::ForkStream;
+use futures::{StreamExt, task::noop_waker_ref};
+
+#[test]
+fn registers_exactly_n_clones() {
+    let (sender, receiver) = futures::channel::mpsc::unbounded::<usize>();
+    let mut clones = receiver.fork_with_initial_clones(3);
+
+    assert_eq!(clones.len(), 3);
+    let mut cx = Context::from_waker(noop_waker_ref());
+    for clone in &mut clones {
+        assert_eq!(
+            clone.active_clone_count(),
+            3,
+            "No stray clone should be registered"
+        );
+        assert_eq!(clone.poll_next_unpin(&mut cx), Poll::Pending);
+    }
+
+    sender.unbounded_send(1).unwrap();
+
+    for clone in &mut clones {
+        assert_eq!(clone.poll_next_unpin(&mut cx), Poll::Ready(Some(1)));
+    }
+}
+
+#[test]
+fn zero_initial_clones() {
+    assert!(
+        futures::stream::iter(0..3)
+            .fork_with_initial_clones(0)
+            .is_empty()
+    );
+}
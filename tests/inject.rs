@@ -0,0 +1,38 @@
+#![cfg(feature = "test-util")]
+
+use clone_stream::{CloneStreamError, ForkStream};
+use futures::{StreamExt, channel::mpsc, join, stream};
+
+#[tokio::test]
+async fn clones_receive_injected_items_in_order() {
+    let (_sender, receiver) = mpsc::unbounded::<char>();
+    let first = receiver.fork();
+    let second = first.clone();
+    let injector = first.clone();
+
+    let waiting = tokio::spawn(async move {
+        join!(
+            first.take(3).collect::<Vec<_>>(),
+            second.take(3).collect::<Vec<_>>()
+        )
+    });
+    tokio::task::yield_now().await;
+    assert!(!waiting.is_finished());
+
+    for item in ['a', 'b', 'c'] {
+        injector.inject(item).unwrap();
+    }
+
+    let (first, second) = waiting.await.unwrap();
+    assert_eq!(first, vec!['a', 'b', 'c']);
+    assert_eq!(second, first);
+}
+
+#[tokio::test]
+async fn injection_after_end_is_rejected() {
+    let mut clone = stream::iter([1]).fork();
+    assert_eq!(clone.next().await, Some(1));
+    assert_eq!(clone.next().await, None);
+
+    assert_eq!(clone.inject(2), Err(CloneStreamError::BaseStreamEnded));
+}
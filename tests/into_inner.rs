@@ -0,0 +1,79 @@
+use core::{
+    marker::PhantomPinned,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use clone_stream::{ForkConfig, ForkStream};
+use futures::{Stream, StreamExt, channel::mpsc, stream};
+
+/// A counting stream that is not `Unpin`.
+#[derive(Debug)]
+struct PinnedCounter {
+    next: usize,
+    end: usize,
+    _pinned: PhantomPinned,
+}
+
+impl Stream for PinnedCounter {
+    type Item = usize;
+
+    fn poll_next(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Option<usize>> {
+        // SAFETY: `next` is not structurally pinned.
+        let this = unsafe { self.get_unchecked_mut() };
+        if this.next == this.end {
+            return Poll::Ready(None);
+        }
+        this.next += 1;
+        Poll::Ready(Some(this.next - 1))
+    }
+}
+
+#[tokio::test]
+async fn recover_pinned_base_stream_from_last_clone() {
+    let mut stream = PinnedCounter {
+        next: 0,
+        end: 4,
+        _pinned: PhantomPinned,
+    }
+    .fork_with_config(ForkConfig::delivering_to_idle_clones());
+    let clone = stream.clone();
+    assert_eq!(stream.next().await, Some(0));
+
+    let stream = stream
+        .into_inner_pinned()
+        .expect_err("Another clone still exists");
+    drop(clone);
+
+    let mut base_stream = stream
+        .into_inner_pinned()
+        .unwrap_or_else(|_| panic!("Last clone should release the base stream"));
+    assert_eq!(
+        base_stream.as_mut().collect::<Vec<_>>().await,
+        vec![1, 2, 3]
+    );
+}
+
+#[tokio::test]
+async fn handle_keeps_base_stream() {
+    let stream = stream::iter(0..3).fork_with_config(ForkConfig::delivering_to_idle_clones());
+    let handle = stream.handle();
+
+    let stream = stream.into_inner().expect_err("Handle still exists");
+    drop(handle);
+    assert!(stream.into_inner().is_ok());
+}
+
+#[tokio::test]
+async fn queued_items_keep_base_stream() {
+    let (sender, receiver) = mpsc::unbounded::<usize>();
+    let mut ahead = receiver.fork_with_config(ForkConfig::delivering_to_idle_clones());
+    let behind = ahead.clone();
+    sender.unbounded_send(1).unwrap();
+    assert_eq!(ahead.next().await, Some(1));
+    drop(ahead);
+
+    let mut behind = behind.into_inner().expect_err("Item 1 is still queued");
+    assert_eq!(behind.next().await, Some(1));
+    assert!(behind.into_inner().is_ok());
+}
@@ -0,0 +1,47 @@
+use core::time::Duration;
+
+use clone_stream::{ForkConfig, ForkStream};
+use futures::{StreamExt, channel::mpsc};
+use tokio::time::sleep;
+
+#[tokio::test]
+async fn slow_clone_skips_expired_items() {
+    let (sender, receiver) = mpsc::unbounded::<usize>();
+    let mut fast = receiver.fork_with_config(ForkConfig {
+        item_ttl: Some(Duration::from_millis(10)),
+        ..ForkConfig::delivering_to_idle_clones()
+    });
+    let mut slow = fast.clone();
+
+    sender.unbounded_send(1).unwrap();
+    sender.unbounded_send(2).unwrap();
+    assert_eq!(fast.next().await, Some(1));
+    assert_eq!(fast.next().await, Some(2));
+    assert_eq!(slow.n_queued_items(), 2);
+
+    sleep(Duration::from_millis(20)).await;
+    sender.unbounded_send(3).unwrap();
+    assert_eq!(fast.next().await, Some(3));
+    drop(sender);
+
+    assert_eq!(slow.by_ref().collect::<Vec<_>>().await, vec![3]);
+    assert_eq!(slow.n_expired_items(), 2);
+    assert_eq!(fast.n_expired_items(), 0);
+}
+
+#[tokio::test]
+async fn items_within_ttl_are_kept() {
+    let (sender, receiver) = mpsc::unbounded::<usize>();
+    let mut fast = receiver.fork_with_config(ForkConfig {
+        item_ttl: Some(Duration::from_secs(60)),
+        ..ForkConfig::delivering_to_idle_clones()
+    });
+    let slow = fast.clone();
+
+    sender.unbounded_send(1).unwrap();
+    sender.unbounded_send(2).unwrap();
+    drop(sender);
+    assert_eq!(fast.by_ref().collect::<Vec<_>>().await, vec![1, 2]);
+
+    assert_eq!(slow.collect::<Vec<_>>().await, vec![1, 2]);
+}
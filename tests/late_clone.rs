@@ -1,7 +1,7 @@
 use core::time::Duration;
 
-use clone_stream::ForkStream;
-use futures::{StreamExt, future::try_join_all};
+use clone_stream::{ForkConfig, ForkStream, QueueBackend};
+use futures::{StreamExt, future::try_join_all, stream};
 use tokio::time::Instant;
 mod util;
 
@@ -58,3 +58,30 @@ async fn late_clone() {
         .await
         .unwrap();
 }
+
+#[tokio::test]
+async fn late_clone_catches_up_on_large_backlog() {
+    for queue_backend in [QueueBackend::Sparse, QueueBackend::Dense] {
+        let mut early = stream::iter(0..10_000).fork_with_config(ForkConfig {
+            queue_backend,
+            ..ForkConfig::delivering_to_idle_clones()
+        });
+        let holder = early.clone();
+        assert_eq!(early.by_ref().count().await, 10_000);
+
+        let late = holder.clone();
+        assert_eq!(
+            late.n_queued_items(),
+            10_001,
+            "The backlog and the end of the base stream are queued"
+        );
+        drop(holder);
+        drop(early);
+
+        assert_eq!(
+            late.collect::<Vec<_>>().await,
+            (0..10_000).collect::<Vec<_>>(),
+            "Late clone with {queue_backend:?} queue should receive the whole backlog in order"
+        );
+    }
+}
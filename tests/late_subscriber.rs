@@ -0,0 +1,27 @@
+use std::task::{Context, Poll};
+
+use clone_stream::ForkStream;
+use futures::{StreamExt, future, stream, stream::FusedStream, task::noop_waker_ref};
+
+#[test]
+fn clone_after_termination_is_terminated() {
+    let mut fork = stream::iter(0..3).fuse().fork();
+    let mut cx = Context::from_waker(noop_waker_ref());
+    while let Poll::Ready(Some(_)) = fork.poll_next_unpin(&mut cx) {}
+    assert!(fork.is_terminated());
+
+    let mut late = fork.clone();
+    assert!(late.is_terminated());
+    assert_eq!(late.poll_next_unpin(&mut cx), Poll::Ready(None));
+}
+
+#[test]
+fn clone_after_termination_does_not_poll_base_stream() {
+    // `unfold` panics when it is polled again after it ended.
+    let mut fork = stream::unfold(0, |n| future::ready((n < 3).then_some((n, n + 1)))).fork();
+    let mut cx = Context::from_waker(noop_waker_ref());
+    while let Poll::Ready(Some(_)) = fork.poll_next_unpin(&mut cx) {}
+
+    let mut late = fork.clone();
+    assert_eq!(late.poll_next_unpin(&mut cx), Poll::Ready(None));
+}
@@ -0,0 +1,48 @@
+use clone_stream::{ForkConfig, ForkStream};
+use futures::{StreamExt, channel::mpsc, stream};
+
+#[tokio::test]
+async fn burst_yields_only_newest() {
+    let (sender, receiver) = mpsc::unbounded::<usize>();
+    let mut every = receiver.fork_with_config(ForkConfig::delivering_to_idle_clones());
+    let mut latest = every.clone().latest_only();
+
+    for item in 0..5 {
+        sender.unbounded_send(item).unwrap();
+        assert_eq!(every.next().await, Some(item));
+    }
+    assert_eq!(latest.next().await, Some(4));
+
+    sender.unbounded_send(5).unwrap();
+    assert_eq!(latest.next().await, Some(5));
+
+    drop(sender);
+    assert_eq!(latest.next().await, None);
+    assert_eq!(
+        every.by_ref().collect::<Vec<_>>().await,
+        vec![5],
+        "Sibling clone still receives every item"
+    );
+}
+
+#[tokio::test]
+async fn newest_item_before_end() {
+    let (sender, receiver) = mpsc::unbounded::<usize>();
+    let every = receiver.fork_with_config(ForkConfig::delivering_to_idle_clones());
+    let mut latest = every.clone().latest_only();
+
+    sender.unbounded_send(1).unwrap();
+    sender.unbounded_send(2).unwrap();
+    drop(sender);
+    assert_eq!(every.collect::<Vec<_>>().await, vec![1, 2]);
+
+    assert_eq!(latest.next().await, Some(2));
+    assert_eq!(latest.next().await, None);
+}
+
+#[tokio::test]
+async fn always_ready_base_stream_yields_each_item() {
+    let latest = stream::iter(0..).fork().latest_only();
+
+    assert_eq!(latest.take(3).collect::<Vec<_>>().await, vec![0, 1, 2]);
+}
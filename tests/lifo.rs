@@ -0,0 +1,59 @@
+use clone_stream::ForkStream;
+use futures::{StreamExt, channel::mpsc};
+
+#[tokio::test]
+async fn buffered_backlog_is_yielded_newest_first() {
+    let (sender, receiver) = mpsc::unbounded();
+    let mut fast = receiver.fork_lifo();
+    let mut slow = fast.clone();
+
+    for item in [1, 2, 3] {
+        sender.unbounded_send(item).unwrap();
+        assert_eq!(fast.next().await, Some(item));
+    }
+
+    assert_eq!(slow.next().await, Some(3));
+    assert_eq!(slow.next().await, Some(2));
+    assert_eq!(slow.next().await, Some(1));
+}
+
+#[tokio::test]
+async fn items_queued_during_backlog_follow_it() {
+    let (sender, receiver) = mpsc::unbounded();
+    let mut fast = receiver.fork_lifo();
+    let mut slow = fast.clone();
+
+    for item in [1, 2, 3] {
+        sender.unbounded_send(item).unwrap();
+        fast.next().await;
+    }
+    assert_eq!(slow.next().await, Some(3));
+
+    for item in [4, 5] {
+        sender.unbounded_send(item).unwrap();
+        fast.next().await;
+    }
+    drop(sender);
+
+    assert_eq!(slow.collect::<Vec<_>>().await, vec![2, 1, 5, 4]);
+    assert_eq!(fast.next().await, None);
+}
+
+#[tokio::test]
+async fn backlog_shared_with_other_clones_stays_queued() {
+    let (sender, receiver) = mpsc::unbounded();
+    let mut fast = receiver.fork_lifo();
+    let mut first = fast.clone();
+    let mut second = fast.clone();
+
+    for item in [1, 2] {
+        sender.unbounded_send(item).unwrap();
+        fast.next().await;
+    }
+
+    assert_eq!(first.next().await, Some(2));
+    assert_eq!(first.next().await, Some(1));
+    assert_eq!(second.next().await, Some(2));
+    assert_eq!(second.next().await, Some(1));
+    assert_eq!(first.n_queued_items(), 0);
+}
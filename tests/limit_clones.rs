@@ -15,3 +15,20 @@ async fn test_clone_count_limit_error() {
 
     let _clone3 = original.clone(); // This will panic
 }
+
+#[test]
+fn clones_remaining_counts_down() {
+    let original = stream::iter(vec![1, 2, 3]).fork_with_limits(1000, 3);
+    assert_eq!(original.clones_remaining(), 2);
+
+    let clone1 = original.clone();
+    let _clone2 = original.clone();
+    assert_eq!(original.clones_remaining(), 0);
+
+    drop(clone1);
+    assert_eq!(
+        original.clones_remaining(),
+        1,
+        "Dropping a clone should free up a slot"
+    );
+}
@@ -0,0 +1,32 @@
+#![cfg(feature = "metrics")]
+
+use std::thread;
+
+use clone_stream::{ForkConfig, ForkStream};
+use futures::{StreamExt, executor::block_on, stream};
+
+const CLONES: usize = 32;
+const ITEMS: usize = 2000;
+
+#[test]
+fn contending_clones_are_counted() {
+    let stream = stream::iter(0..ITEMS).fork_with_config(ForkConfig::delivering_to_idle_clones());
+    let clones: Vec<_> = (0..CLONES).map(|_| stream.clone()).collect();
+    drop(stream);
+
+    let observer = clones[0].clone();
+    let threads: Vec<_> = clones
+        .into_iter()
+        .map(|clone| thread::spawn(move || block_on(clone.count())))
+        .collect();
+    for thread in threads {
+        assert_eq!(thread.join().unwrap(), ITEMS);
+    }
+
+    let metrics = observer.poll_metrics();
+    assert!(
+        metrics.lock_waits > 0,
+        "{CLONES} threads polling the same fork should contend for its lock"
+    );
+    assert!(metrics.slow_lock_waits <= metrics.lock_waits);
+}
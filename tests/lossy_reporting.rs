@@ -0,0 +1,39 @@
+use clone_stream::{ForkConfig, ForkStream, Lagged};
+use futures::{StreamExt, stream};
+
+fn queue_of_one() -> ForkConfig {
+    ForkConfig {
+        max_queue_size: 1,
+        max_clone_count: 2,
+        ..ForkConfig::delivering_to_idle_clones()
+    }
+}
+
+#[tokio::test]
+async fn slow_clone_receives_lagged_with_skip_count() {
+    let mut slow = stream::iter(0..5).fork_lossy_reporting(queue_of_one());
+    let mut fast = slow.clone();
+
+    assert_eq!(slow.next().await, Some(Ok(0)));
+    for expected in 0..5 {
+        assert_eq!(fast.next().await, Some(Ok(expected)));
+    }
+
+    assert_eq!(slow.next().await, Some(Err(Lagged(3))));
+    assert_eq!(slow.next().await, Some(Ok(4)));
+    assert_eq!(slow.next().await, None);
+    assert_eq!(fast.next().await, None);
+}
+
+#[tokio::test]
+async fn clone_keeping_up_never_lags() {
+    let mut first = stream::iter(0..5).fork_lossy_reporting(queue_of_one());
+    let mut second = first.clone();
+
+    for expected in 0..5 {
+        assert_eq!(first.next().await, Some(Ok(expected)));
+        assert_eq!(second.next().await, Some(Ok(expected)));
+    }
+    assert_eq!(first.next().await, None);
+    assert_eq!(second.next().await, None);
+}
@@ -0,0 +1,46 @@
+use clone_stream::{CloneStreamError, ForkConfig, ForkStream};
+use futures::{StreamExt, stream};
+
+fn limited(max_total_memory_bytes: usize) -> ForkConfig {
+    ForkConfig {
+        max_total_memory_bytes: Some(max_total_memory_bytes),
+        ..ForkConfig::delivering_to_idle_clones()
+    }
+}
+
+#[tokio::test]
+async fn try_clone_fails_over_memory_cap() {
+    let mut fast = stream::iter(0..100_u64).fork_with_config(limited(1_000));
+    let lagging = fast.try_clone().unwrap();
+
+    for _ in 0..100 {
+        fast.next().await;
+    }
+    assert_eq!(lagging.n_queued_items(), 100);
+
+    assert_eq!(
+        fast.try_clone().err(),
+        Some(CloneStreamError::MemoryLimitExceeded {
+            estimated_bytes: 1_600,
+            max_bytes: 1_000,
+        })
+    );
+}
+
+#[tokio::test]
+async fn item_size_counts_heap_memory() {
+    let mut fast = stream::iter(vec![vec![0_u8; 64]; 4])
+        .fork_with_config(limited(300))
+        .with_item_size(|bytes: &Vec<u8>| bytes.len());
+    let _lagging = fast.clone();
+
+    fast.next().await;
+    assert!(fast.try_clone().is_ok(), "One queued item counted twice");
+
+    fast.next().await;
+    fast.next().await;
+    assert!(
+        fast.try_clone().is_err(),
+        "Three queued items counted twice"
+    );
+}
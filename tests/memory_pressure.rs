@@ -0,0 +1,76 @@
+use std::{
+    sync::{Arc, Mutex},
+    task::{Context, Poll, Wake, Waker},
+};
+
+use clone_stream::{ForkConfig, ForkStream};
+use futures::{StreamExt, channel::mpsc};
+
+/// Waker that logs its name when woken.
+struct NamedWaker {
+    name: &'static str,
+    log: Arc<Mutex<Vec<&'static str>>>,
+}
+
+impl Wake for NamedWaker {
+    fn wake(self: Arc<Self>) {
+        self.log.lock().unwrap().push(self.name);
+    }
+}
+
+/// Leaves `slow` one item behind `mid` while all clones wait on the base
+/// stream, then returns the order in which a new item wakes them.
+fn wake_order(memory_pressure_threshold: Option<usize>) -> Vec<&'static str> {
+    let (sender, receiver) = mpsc::unbounded::<usize>();
+    let mut fast = receiver.fork_with_config(ForkConfig {
+        memory_pressure_threshold,
+        ..ForkConfig::delivering_to_idle_clones()
+    });
+    let mut mid = fast.clone();
+    let mut slow = fast.clone();
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let waker = |name| {
+        Waker::from(Arc::new(NamedWaker {
+            name,
+            log: log.clone(),
+        }))
+    };
+    let (fast_waker, mid_waker, slow_waker) = (waker("fast"), waker("mid"), waker("slow"));
+
+    assert!(
+        slow.poll_next_unpin(&mut Context::from_waker(&slow_waker))
+            .is_pending()
+    );
+    sender.unbounded_send(1).unwrap();
+    assert_eq!(
+        fast.poll_next_unpin(&mut Context::from_waker(&fast_waker)),
+        Poll::Ready(Some(1))
+    );
+    assert_eq!(
+        mid.poll_next_unpin(&mut Context::from_waker(&mid_waker)),
+        Poll::Ready(Some(1))
+    );
+    assert!(
+        mid.poll_next_unpin(&mut Context::from_waker(&mid_waker))
+            .is_pending()
+    );
+    assert!(
+        fast.poll_next_unpin(&mut Context::from_waker(&fast_waker))
+            .is_pending()
+    );
+    log.lock().unwrap().clear();
+
+    sender.unbounded_send(2).unwrap();
+    log.lock().unwrap().clone()
+}
+
+#[test]
+fn slowest_clone_woken_first_under_pressure() {
+    assert_eq!(wake_order(Some(0)), vec!["slow", "mid", "fast"]);
+}
+
+#[test]
+fn registration_order_without_pressure() {
+    assert_eq!(wake_order(None), vec!["mid", "slow", "fast"]);
+    assert_eq!(wake_order(Some(1)), vec!["mid", "slow", "fast"]);
+}
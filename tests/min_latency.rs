@@ -0,0 +1,48 @@
+use clone_stream::{ForkConfig, ForkStream};
+use futures::{StreamExt, channel::mpsc};
+
+fn min_latency() -> ForkConfig {
+    ForkConfig {
+        min_latency: true,
+        ..ForkConfig::delivering_to_idle_clones()
+    }
+}
+
+#[tokio::test]
+async fn active_clone_receives_fresh_item_first() {
+    let (sender, receiver) = mpsc::unbounded::<usize>();
+    let mut active = receiver.fork_with_config(min_latency());
+    let mut sibling = active.clone();
+
+    sender.unbounded_send(1).unwrap();
+    sender.unbounded_send(2).unwrap();
+    assert_eq!(sibling.next().await, Some(1));
+    assert_eq!(sibling.next().await, Some(2));
+    assert_eq!(active.n_queued_items(), 2);
+
+    sender.unbounded_send(3).unwrap();
+    assert_eq!(active.next().await, Some(3), "Fresh item skips the queue");
+    assert_eq!(active.n_queued_items(), 0);
+
+    drop(sender);
+    assert_eq!(sibling.collect::<Vec<_>>().await, vec![3]);
+    assert_eq!(active.collect::<Vec<_>>().await, Vec::<usize>::new());
+}
+
+#[tokio::test]
+async fn backlog_delivered_while_base_stream_pending() {
+    let (sender, receiver) = mpsc::unbounded::<usize>();
+    let mut active = receiver.fork_with_config(min_latency());
+    let mut sibling = active.clone();
+
+    sender.unbounded_send(1).unwrap();
+    assert_eq!(sibling.next().await, Some(1));
+    assert_eq!(active.next().await, Some(1));
+
+    sender.unbounded_send(2).unwrap();
+    assert_eq!(sibling.next().await, Some(2));
+    drop(sender);
+
+    assert_eq!(active.collect::<Vec<_>>().await, vec![2]);
+    assert_eq!(sibling.collect::<Vec<_>>().await, Vec::<usize>::new());
+}
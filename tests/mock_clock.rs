@@ -0,0 +1,56 @@
+use core::time::Duration;
+use std::{
+    sync::{Arc, Mutex},
+    time::Instant,
+};
+
+use clone_stream::{Clock, ForkStream, Readiness};
+use futures::{StreamExt, channel::mpsc};
+
+/// Clock that only moves when the test advances it.
+struct MockClock {
+    now: Mutex<Instant>,
+}
+
+impl MockClock {
+    fn new() -> Arc<Self> {
+        Arc::new(Self {
+            now: Mutex::new(Instant::now()),
+        })
+    }
+
+    fn advance(&self, duration: Duration) {
+        *self.now.lock().unwrap() += duration;
+    }
+}
+
+impl Clock for MockClock {
+    fn now(&self) -> Instant {
+        *self.now.lock().unwrap()
+    }
+}
+
+#[tokio::test]
+async fn readiness_follows_mock_clock() {
+    let clock = MockClock::new();
+    let (sender, receiver) = mpsc::unbounded::<usize>();
+    let mut clone = receiver.fork_with_clock(clock.clone());
+
+    sender.unbounded_send(0).unwrap();
+    assert_eq!(clone.next().await, Some(0));
+    assert_eq!(clone.readiness(), Readiness::SourceActive);
+
+    clock.advance(Duration::from_millis(999));
+    assert_eq!(clone.readiness(), Readiness::SourceActive);
+
+    clock.advance(Duration::from_millis(1));
+    assert_eq!(clone.readiness(), Readiness::Idle);
+
+    sender.unbounded_send(1).unwrap();
+    assert_eq!(clone.next().await, Some(1));
+    assert_eq!(
+        clone.readiness(),
+        Readiness::SourceActive,
+        "A new item restarts the idle timeout"
+    );
+}
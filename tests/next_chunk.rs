@@ -0,0 +1,23 @@
+use clone_stream::{ForkConfig, ForkStream};
+use futures::stream;
+
+#[tokio::test]
+async fn two_chunks_from_six_items() {
+    let mut first = stream::iter(0..6).fork_with_config(ForkConfig::delivering_to_idle_clones());
+    let mut second = first.clone();
+
+    assert_eq!(first.next_chunk::<3>().await, Some([0, 1, 2]));
+    assert_eq!(first.next_chunk::<3>().await, Some([3, 4, 5]));
+    assert_eq!(first.next_chunk::<3>().await, None);
+
+    assert_eq!(second.next_chunk::<2>().await, Some([0, 1]));
+    assert_eq!(second.next_chunk::<4>().await, Some([2, 3, 4, 5]));
+}
+
+#[tokio::test]
+async fn partial_chunk_at_end_is_none() {
+    let mut clone = stream::iter(0..4).fork_with_config(ForkConfig::delivering_to_idle_clones());
+
+    assert_eq!(clone.next_chunk::<3>().await, Some([0, 1, 2]));
+    assert_eq!(clone.next_chunk::<3>().await, None);
+}
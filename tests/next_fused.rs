@@ -0,0 +1,46 @@
+use clone_stream::ForkStream;
+use futures::{channel::mpsc, select, stream};
+
+#[tokio::test]
+async fn next_fused_in_select_loop() {
+    let (sender, receiver) = mpsc::unbounded::<usize>();
+    let mut first = receiver.fork();
+    let mut second = first.clone();
+    for item in [1, 2, 3] {
+        sender.unbounded_send(item).unwrap();
+    }
+    drop(sender);
+
+    let (mut from_first, mut from_second) = (Vec::new(), Vec::new());
+    loop {
+        select! {
+            item = first.next_fused() => match item {
+                Some(item) => from_first.push(item),
+                None => break,
+            },
+            item = second.next_fused() => from_second.extend(item),
+        }
+    }
+
+    assert_eq!(from_first, vec![1, 2, 3]);
+    assert!(from_second.iter().all(|item| from_first.contains(item)));
+    assert!(from_second.is_sorted());
+}
+
+#[tokio::test]
+async fn completed_next_fused_is_skipped() {
+    let mut clone = stream::iter(Vec::<usize>::new()).fork();
+    let mut next = clone.next_fused();
+
+    let first = select! {
+        item = next => item,
+        complete => Some(0),
+    };
+    assert_eq!(first, None, "Stream ended");
+
+    let second = select! {
+        _ = next => panic!("Completed future polled again"),
+        complete => "complete",
+    };
+    assert_eq!(second, "complete");
+}
@@ -0,0 +1,18 @@
+use clone_stream::{ForkConfig, ForkStream};
+use futures::{StreamExt, join, stream};
+
+#[tokio::test]
+async fn observer_sees_only_its_clone() {
+    let observed = stream::iter(0..4).fork_with_config(ForkConfig::delivering_to_idle_clones());
+    let sibling = observed.clone();
+
+    let mut observed_items = Vec::new();
+    let observed = observed.observe(|item| observed_items.push(*item));
+
+    let (from_observed, from_sibling) =
+        join!(observed.collect::<Vec<_>>(), sibling.collect::<Vec<_>>());
+
+    assert_eq!(from_observed, vec![0, 1, 2, 3]);
+    assert_eq!(from_sibling, vec![0, 1, 2, 3]);
+    assert_eq!(observed_items, vec![0, 1, 2, 3]);
+}
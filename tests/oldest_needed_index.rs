@@ -0,0 +1,40 @@
+use clone_stream::{ForkConfig, ForkStream};
+use futures::{StreamExt, stream};
+
+#[tokio::test]
+async fn oldest_needed_index_follows_slowest_clone() {
+    let mut fast = stream::iter(0..10).fork_with_config(ForkConfig::delivering_to_idle_clones());
+    let mut middle = fast.clone();
+    let mut slow = fast.clone();
+    assert_eq!(fast.oldest_needed_index(), None);
+
+    for _ in 0..6 {
+        fast.next().await;
+    }
+    for _ in 0..4 {
+        middle.next().await;
+    }
+    for _ in 0..2 {
+        slow.next().await;
+    }
+
+    assert_eq!(slow.n_queued_items(), 4);
+    assert_eq!(
+        fast.oldest_needed_index(),
+        Some(2),
+        "Item 2 is next for `slow`"
+    );
+
+    slow.next().await;
+    slow.next().await;
+    assert_eq!(
+        fast.oldest_needed_index(),
+        Some(4),
+        "Item 4 is next for both"
+    );
+
+    drop(slow);
+    middle.next().await;
+    middle.next().await;
+    assert_eq!(fast.oldest_needed_index(), None, "Every clone is caught up");
+}
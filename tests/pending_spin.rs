@@ -0,0 +1,58 @@
+use std::{
+    sync::Mutex,
+    task::{Context, Poll},
+};
+
+use clone_stream::{ForkConfig, ForkStream};
+use futures::{StreamExt, stream, task::noop_waker_ref};
+use log::{Level, Log, Metadata, Record};
+
+/// Logger that keeps every warning.
+struct CapturedWarnings(Mutex<Vec<String>>);
+
+impl Log for CapturedWarnings {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= Level::Warn
+    }
+
+    fn log(&self, record: &Record) {
+        if self.enabled(record.metadata()) {
+            self.0.lock().unwrap().push(record.args().to_string());
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+static WARNINGS: CapturedWarnings = CapturedWarnings(Mutex::new(Vec::new()));
+
+#[test]
+fn warning_after_too_many_pending_polls() {
+    log::set_logger(&WARNINGS).unwrap();
+    log::set_max_level(log::LevelFilter::Warn);
+
+    // Never registers the waker, so nothing would poll it again.
+    let misbehaving = stream::poll_fn(|_| Poll::<Option<usize>>::Pending);
+    let mut clone = misbehaving.fork_with_config(ForkConfig {
+        max_consecutive_pending: Some(3),
+        ..ForkConfig::default()
+    });
+    let mut cx = Context::from_waker(noop_waker_ref());
+
+    for _ in 0..3 {
+        assert!(clone.poll_next_unpin(&mut cx).is_pending());
+    }
+    assert!(WARNINGS.0.lock().unwrap().is_empty());
+
+    for _ in 0..3 {
+        assert!(clone.poll_next_unpin(&mut cx).is_pending());
+    }
+    assert_eq!(
+        WARNINGS.0.lock().unwrap().clone(),
+        vec![
+            "Base stream returned pending 4 times in a row. It may not wake the waker it is \
+             polled with."
+                .to_string()
+        ]
+    );
+}
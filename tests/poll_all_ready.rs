@@ -0,0 +1,36 @@
+use clone_stream::{CloneStream, ForkConfig, ForkStream};
+use futures::{StreamExt, channel::mpsc};
+
+#[tokio::test]
+async fn three_clones_two_buffered_items() {
+    let (sender, receiver) = mpsc::unbounded::<usize>();
+    let mut ahead = receiver.fork_with_config(ForkConfig::delivering_to_idle_clones());
+    let mut clones = [ahead.clone(), ahead.clone()];
+
+    sender.unbounded_send(1).unwrap();
+    sender.unbounded_send(2).unwrap();
+    assert_eq!(ahead.next().await, Some(1));
+    assert_eq!(ahead.next().await, Some(2));
+
+    assert_eq!(
+        CloneStream::poll_all_ready(&mut clones),
+        vec![Some(1), Some(1)]
+    );
+
+    let [first, second] = clones;
+    let mut clones = [ahead, first, second];
+    assert_eq!(
+        CloneStream::poll_all_ready(&mut clones),
+        vec![None, Some(2), Some(2)]
+    );
+    assert_eq!(
+        CloneStream::poll_all_ready(&mut clones),
+        vec![None, None, None]
+    );
+
+    sender.unbounded_send(3).unwrap();
+    assert_eq!(
+        CloneStream::poll_all_ready(&mut clones),
+        vec![Some(3), Some(3), Some(3)]
+    );
+}
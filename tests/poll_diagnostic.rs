@@ -0,0 +1,79 @@
+use std::task::{Context, Poll};
+
+use clone_stream::{ForkConfig, ForkStream, PollOutcome};
+use futures::{executor::block_on, stream, task::noop_waker_ref};
+
+#[test]
+fn distinguishes_pending_base_stream_from_items() {
+    let (sender, receiver) = futures::channel::mpsc::unbounded::<char>();
+    let mut adam = receiver.fork();
+    let mut bob = adam.clone();
+    let mut cx = Context::from_waker(noop_waker_ref());
+
+    assert_eq!(
+        adam.poll_next_diagnostic(&mut cx),
+        Poll::Ready(PollOutcome::PendingBaseStream)
+    );
+    assert_eq!(
+        bob.poll_next_diagnostic(&mut cx),
+        Poll::Ready(PollOutcome::PendingBaseStream)
+    );
+
+    sender.unbounded_send('a').unwrap();
+    assert_eq!(
+        adam.poll_next_diagnostic(&mut cx),
+        Poll::Ready(PollOutcome::Item(Some('a')))
+    );
+    assert_eq!(
+        bob.poll_next_diagnostic(&mut cx)
+            .map(PollOutcome::into_poll),
+        Poll::Ready(Poll::Ready(Some('a'))),
+        "Queued item should be delivered to the second clone"
+    );
+
+    drop(sender);
+    assert_eq!(
+        adam.poll_next_diagnostic(&mut cx),
+        Poll::Ready(PollOutcome::Item(None))
+    );
+}
+
+#[test]
+fn reports_clone_without_credits() {
+    let mut stream = stream::iter(0..3).fork_with_credits();
+    let mut cx = Context::from_waker(noop_waker_ref());
+
+    assert_eq!(
+        stream.poll_next_diagnostic(&mut cx),
+        Poll::Ready(PollOutcome::PendingCredits)
+    );
+    stream.grant_credits(1);
+    assert_eq!(
+        stream.poll_next_diagnostic(&mut cx),
+        Poll::Ready(PollOutcome::Item(Some(0)))
+    );
+}
+
+#[test]
+fn reports_queue_full_of_unacknowledged_items() {
+    let mut stream = stream::iter(0..5).fork_with_config(ForkConfig {
+        high_water: Some(2),
+        low_water: 0,
+        ..ForkConfig::default()
+    });
+    let mut cx = Context::from_waker(noop_waker_ref());
+
+    let (_, first_token) = block_on(stream.next_with_ack()).unwrap();
+    let (_, second_token) = block_on(stream.next_with_ack()).unwrap();
+    assert_eq!(
+        stream.poll_next_diagnostic(&mut cx),
+        Poll::Ready(PollOutcome::PendingAck)
+    );
+
+    first_token.ack();
+    second_token.ack();
+    assert_eq!(
+        stream.poll_next_diagnostic(&mut cx),
+        Poll::Ready(PollOutcome::Item(Some(2)))
+    );
+}
@@ -0,0 +1,72 @@
+#![cfg(feature = "metrics")]
+
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+    task::{Context, Poll, Wake, Waker},
+};
+
+use clone_stream::{ForkConfig, ForkStream, PollMetrics};
+use futures::StreamExt;
+
+#[derive(Default)]
+struct MockWaker {
+    wakes: AtomicUsize,
+}
+
+impl Wake for MockWaker {
+    fn wake(self: Arc<Self>) {
+        self.wakes.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+/// Lets a lagging clone fall behind while the base stream produces two items,
+/// then runs its task once per wakeup. Each run polls until pending, like a
+/// task that drains its clone.
+fn metrics_after_lagging_clone_catches_up(config: ForkConfig) -> PollMetrics {
+    let (sender, receiver) = futures::channel::mpsc::unbounded::<usize>();
+    let mut fast = receiver.fork_with_config(config);
+    let mut lagging = fast.clone();
+    let lagging_waker = Arc::new(MockWaker::default());
+    let lagging_cx_waker = Waker::from(lagging_waker.clone());
+    let mut lagging_cx = Context::from_waker(&lagging_cx_waker);
+    let mut fast_cx = Context::from_waker(Waker::noop());
+
+    assert_eq!(fast.poll_next_unpin(&mut fast_cx), Poll::Pending);
+    assert_eq!(lagging.poll_next_unpin(&mut lagging_cx), Poll::Pending);
+
+    sender.unbounded_send(1).unwrap();
+    assert_eq!(fast.poll_next_unpin(&mut fast_cx), Poll::Ready(Some(1)));
+    assert_eq!(fast.poll_next_unpin(&mut fast_cx), Poll::Pending);
+    sender.unbounded_send(2).unwrap();
+
+    for _ in 0..lagging_waker.wakes.load(Ordering::SeqCst) {
+        while let Poll::Ready(Some(_)) = lagging.poll_next_unpin(&mut lagging_cx) {}
+    }
+    fast.poll_metrics()
+}
+
+#[test]
+fn default_wakeups_cause_spurious_polls() {
+    let metrics = metrics_after_lagging_clone_catches_up(ForkConfig::default());
+
+    assert_eq!(metrics.spurious_polls, 1, "{metrics:?}");
+    assert!(metrics.polls > metrics.spurious_polls);
+}
+
+#[test]
+fn selective_wakeups_avoid_spurious_polls() {
+    let default = metrics_after_lagging_clone_catches_up(ForkConfig::default());
+    let selective = metrics_after_lagging_clone_catches_up(ForkConfig {
+        selective_wakeups: true,
+        ..ForkConfig::default()
+    });
+
+    assert_eq!(selective.spurious_polls, 0, "{selective:?}");
+    assert!(
+        selective.wakeups < default.wakeups,
+        "{selective:?} {default:?}"
+    );
+}
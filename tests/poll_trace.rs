@@ -0,0 +1,47 @@
+use std::task::{Context, Poll};
+
+use clone_stream::{ForkConfig, ForkStream, PollEvent, PollEventKind};
+use futures::{StreamExt, task::noop_waker_ref};
+
+#[test]
+fn records_known_interleaving() {
+    let (sender, receiver) = futures::channel::mpsc::unbounded::<u32>();
+    let config = ForkConfig {
+        record_poll_order: true,
+        ..ForkConfig::default()
+    };
+    let mut adam = receiver.fork_with_config(config);
+    let mut bob = adam.clone();
+    let mut cx = Context::from_waker(noop_waker_ref());
+
+    assert_eq!(adam.poll_next_unpin(&mut cx), Poll::Pending);
+    assert_eq!(bob.poll_next_unpin(&mut cx), Poll::Pending);
+    sender.unbounded_send(1).unwrap();
+    assert_eq!(adam.poll_next_unpin(&mut cx), Poll::Ready(Some(1)));
+    assert_eq!(bob.poll_next_unpin(&mut cx), Poll::Ready(Some(1)));
+
+    let event = |clone_id, kind| PollEvent { clone_id, kind };
+    assert_eq!(
+        adam.poll_trace(),
+        vec![
+            event(adam.id, PollEventKind::BasePending),
+            event(bob.id, PollEventKind::BasePending),
+            event(adam.id, PollEventKind::BaseItem),
+            event(adam.id, PollEventKind::Enqueued { index: 0 }),
+            event(bob.id, PollEventKind::QueueTaken { index: 0 }),
+        ],
+        "Trace should match the order in which the clones were polled"
+    );
+    assert_eq!(
+        adam.poll_trace(),
+        bob.poll_trace(),
+        "All clones of a fork should share the same trace"
+    );
+}
+
+#[test]
+fn no_trace_by_default() {
+    let mut clone = futures::stream::iter(0..3).fork();
+    futures::executor::block_on(async { while clone.next().await.is_some() {} });
+    assert!(clone.poll_trace().is_empty());
+}
@@ -0,0 +1,60 @@
+use std::marker::PhantomPinned;
+
+use clone_stream::{ForkConfig, ForkStream};
+use futures::{Stream, StreamExt, future::poll_fn, stream};
+
+#[tokio::test]
+async fn pushed_back_item_returned_again() {
+    let mut parser = stream::iter(0..3).fork_with_config(ForkConfig::delivering_to_idle_clones());
+    let sibling = parser.clone();
+
+    let first = parser.next().await.unwrap();
+    parser.push_back(first);
+
+    assert_eq!(parser.next().await, Some(0));
+    assert_eq!(parser.collect::<Vec<_>>().await, vec![1, 2]);
+    assert_eq!(sibling.collect::<Vec<_>>().await, vec![0, 1, 2]);
+}
+
+#[tokio::test]
+async fn push_backs_stack() {
+    let mut parser = stream::iter(0..3).fork_with_config(ForkConfig::delivering_to_idle_clones());
+
+    let first = parser.next().await.unwrap();
+    let second = parser.next().await.unwrap();
+    parser.push_back(second);
+    parser.push_back(first);
+
+    assert_eq!(parser.size_hint(), (3, Some(3)));
+    assert_eq!(parser.collect::<Vec<_>>().await, vec![0, 1, 2]);
+}
+
+#[tokio::test]
+async fn with_next_inspects_pushed_back_item_first() {
+    let mut parser = stream::iter(0..3).fork();
+
+    let first = parser.next().await.unwrap();
+    parser.push_back(first);
+
+    assert_eq!(
+        poll_fn(|cx| parser.with_next(cx, |item| item * 10)).await,
+        Some(0)
+    );
+    assert_eq!(
+        poll_fn(|cx| parser.with_next(cx, |item| item * 10)).await,
+        Some(10)
+    );
+    assert_eq!(parser.collect::<Vec<_>>().await, vec![2]);
+}
+
+#[tokio::test]
+async fn clone_of_items_that_are_not_unpin_is_unpin() {
+    fn assert_unpin<Clone: Unpin>(_: &Clone) {}
+
+    let mut clone = stream::iter([PhantomPinned]).fork();
+    assert_unpin(&clone);
+
+    let item = clone.next().await.unwrap();
+    clone.push_back(item);
+    assert!(clone.next().await.is_some());
+}
@@ -0,0 +1,36 @@
+use clone_stream::{ForkConfig, ForkStream, QueueBackend};
+use futures::{StreamExt, join, stream};
+
+async fn deliveries(queue_backend: QueueBackend) -> (Vec<usize>, Vec<usize>, Vec<usize>) {
+    let mut fast = stream::iter(0..100).fork_with_config(ForkConfig {
+        max_queue_size: 16,
+        queue_backend,
+        ..ForkConfig::default()
+    });
+    let lagging = fast.clone();
+    let filtering = fast.clone();
+
+    let mut fast_items = Vec::new();
+    for _ in 0..40 {
+        fast_items.extend(fast.next().await);
+    }
+    let (rest, lagging, filtering) = join!(
+        fast.collect::<Vec<_>>(),
+        lagging.collect::<Vec<_>>(),
+        filtering
+            .filtered_view(|item| item % 3 == 0)
+            .collect::<Vec<_>>()
+    );
+    fast_items.extend(rest);
+    (fast_items, lagging, filtering)
+}
+
+#[tokio::test]
+async fn backends_deliver_identically() {
+    let sparse = deliveries(QueueBackend::Sparse).await;
+    let dense = deliveries(QueueBackend::Dense).await;
+
+    assert_eq!(sparse.0, (0..100).collect::<Vec<_>>());
+    assert!(sparse.1.len() < 100, "Lagging clone missed evicted items");
+    assert_eq!(dense, sparse);
+}
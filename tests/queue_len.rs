@@ -1,6 +1,6 @@
 use core::time::Duration;
 
-use clone_stream::ForkStream;
+use clone_stream::{ForkConfig, ForkStream};
 use futures::{StreamExt, future::join_all};
 use tokio::{select, time::Instant};
 use util::until;
@@ -47,3 +47,22 @@ async fn queue_length() {
         result.as_ref().unwrap();
     });
 }
+
+#[tokio::test]
+async fn has_buffered_only_when_behind() {
+    let (sender, receiver) = futures::channel::mpsc::unbounded::<usize>();
+    let mut ahead = receiver.fork_with_config(ForkConfig::delivering_to_idle_clones());
+    let mut behind = ahead.clone();
+    assert!(!behind.has_buffered());
+
+    sender.unbounded_send(1).unwrap();
+    assert_eq!(ahead.next().await, Some(1));
+    assert!(
+        !ahead.has_buffered(),
+        "Up-to-date clone has nothing buffered"
+    );
+    assert!(behind.has_buffered(), "Lagging clone has the item buffered");
+
+    assert_eq!(behind.next().await, Some(1));
+    assert!(!behind.has_buffered());
+}
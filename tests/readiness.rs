@@ -0,0 +1,31 @@
+use core::time::Duration;
+
+use clone_stream::{ForkConfig, ForkStream, Readiness};
+use futures::{StreamExt, channel::mpsc};
+use tokio::time::sleep;
+
+#[tokio::test]
+async fn burst_then_quiet() {
+    let config = ForkConfig {
+        idle_after: Duration::from_millis(20),
+        ..ForkConfig::delivering_to_idle_clones()
+    };
+    let (sender, receiver) = mpsc::unbounded::<usize>();
+    let mut ahead = receiver.fork_with_config(config);
+    let mut behind = ahead.clone();
+    assert_eq!(ahead.readiness(), Readiness::Idle, "Nothing produced yet");
+
+    for item in 0..3 {
+        sender.unbounded_send(item).unwrap();
+        assert_eq!(ahead.next().await, Some(item));
+    }
+    assert_eq!(behind.readiness(), Readiness::Ready);
+    assert_eq!(ahead.readiness(), Readiness::SourceActive);
+
+    assert_eq!(behind.drain_ready(), vec![0, 1, 2]);
+    assert_eq!(behind.readiness(), Readiness::SourceActive);
+
+    sleep(Duration::from_millis(40)).await;
+    assert_eq!(ahead.readiness(), Readiness::Idle);
+    assert_eq!(behind.readiness(), Readiness::Idle);
+}
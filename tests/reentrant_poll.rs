@@ -0,0 +1,80 @@
+use core::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+use std::sync::{Arc, Mutex};
+
+use clone_stream::{CloneStream, ForkStream};
+use futures::{Stream, StreamExt, executor::block_on};
+use log::{Level, LevelFilter, Log, Metadata, Record};
+
+/// Collects the messages of warnings and errors.
+struct CapturingLogger;
+
+static MESSAGES: Mutex<Vec<String>> = Mutex::new(Vec::new());
+static LOGGER: CapturingLogger = CapturingLogger;
+
+impl Log for CapturingLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= Level::Warn
+    }
+
+    fn log(&self, record: &Record) {
+        if self.enabled(record.metadata()) {
+            MESSAGES.lock().unwrap().push(record.args().to_string());
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+type SharedClone = Arc<Mutex<Option<CloneStream<ReentrantStream>>>>;
+
+/// A base stream that polls a clone of its own fork before yielding.
+struct ReentrantStream {
+    own_clone: SharedClone,
+    remaining: usize,
+}
+
+impl Stream for ReentrantStream {
+    type Item = usize;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<usize>> {
+        if let Some(own_clone) = self.own_clone.lock().unwrap().as_mut() {
+            assert!(own_clone.poll_next_unpin(cx).is_pending());
+        }
+        if self.remaining == 0 {
+            return Poll::Ready(None);
+        }
+        self.remaining -= 1;
+        Poll::Ready(Some(self.remaining))
+    }
+}
+
+#[test]
+fn reentrant_poll_returns_pending_and_logs() {
+    log::set_logger(&LOGGER).unwrap();
+    log::set_max_level(LevelFilter::Warn);
+
+    let own_clone = SharedClone::default();
+    let stream = ReentrantStream {
+        own_clone: own_clone.clone(),
+        remaining: 2,
+    }
+    .fork();
+    *own_clone.lock().unwrap() = Some(stream.clone());
+
+    let items = block_on(stream.collect::<Vec<_>>());
+    assert_eq!(items, vec![1, 0]);
+
+    let messages = MESSAGES.lock().unwrap();
+    assert!(
+        messages
+            .iter()
+            .any(|message| message.contains("polled from inside the base stream")),
+        "Expected a logged reentrancy error, got {messages:?}"
+    );
+    drop(messages);
+
+    own_clone.lock().unwrap().take();
+}
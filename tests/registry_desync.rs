@@ -0,0 +1,48 @@
+use std::thread;
+
+use clone_stream::ForkStream;
+use futures::{StreamExt, executor::block_on, stream};
+
+#[tokio::test]
+async fn unregistered_clone_ends_instead_of_panicking() {
+    let mut clone = stream::iter(0..3).fork();
+    let registered_id = clone.id;
+    clone.id = registered_id + 100;
+
+    assert_eq!(clone.next().await, None);
+
+    clone.id = registered_id;
+    assert_eq!(clone.next().await, Some(0));
+}
+
+#[test]
+fn dropping_clones_while_others_poll() {
+    const THREADS: usize = 8;
+    const ROUNDS: usize = 500;
+
+    let stream = stream::iter(0..ROUNDS).fork();
+    let threads: Vec<_> = (0..THREADS)
+        .map(|thread_index| {
+            let source = stream.clone();
+            thread::spawn(move || {
+                block_on(async {
+                    let mut clone = source.clone();
+                    for round in 0..ROUNDS {
+                        if round % (thread_index + 2) == 0 {
+                            clone = source.clone();
+                        }
+                        if clone.next().await.is_none() {
+                            break;
+                        }
+                    }
+                });
+            })
+        })
+        .collect();
+    drop(stream);
+    for thread in threads {
+        thread
+            .join()
+            .expect("Polling while clones drop must not panic");
+    }
+}
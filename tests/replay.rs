@@ -0,0 +1,34 @@
+use clone_stream::ForkStream;
+use futures::{StreamExt, channel::mpsc, stream};
+
+#[tokio::test]
+async fn late_clone_receives_last_items() {
+    let mut early = stream::iter(0..10).fork_with_replay(3);
+    assert_eq!(
+        early.by_ref().collect::<Vec<_>>().await,
+        (0..10).collect::<Vec<_>>()
+    );
+
+    let late = early.clone();
+    assert_eq!(late.n_queued_items(), 3);
+    assert_eq!(late.collect::<Vec<_>>().await, vec![7, 8, 9]);
+}
+
+#[tokio::test]
+async fn replayed_items_precede_live_items() {
+    let (sender, receiver) = mpsc::unbounded::<usize>();
+    let mut early = receiver.fork_with_replay(2);
+
+    sender.unbounded_send(1).unwrap();
+    assert_eq!(early.next().await, Some(1));
+
+    let mut late = early.clone();
+    sender.unbounded_send(2).unwrap();
+    drop(sender);
+
+    assert_eq!(late.by_ref().collect::<Vec<_>>().await, vec![1, 2]);
+    assert_eq!(early.collect::<Vec<_>>().await, vec![2]);
+
+    let last = late.clone();
+    assert_eq!(last.collect::<Vec<_>>().await, vec![1, 2]);
+}
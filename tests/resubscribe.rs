@@ -0,0 +1,60 @@
+use clone_stream::{ForkConfig, ForkStream};
+use futures::{StreamExt, channel::mpsc, stream};
+
+#[tokio::test]
+async fn resubscribed_clone_misses_items_sent_while_closed() {
+    let (sender, receiver) = mpsc::unbounded::<usize>();
+    let mut live = receiver.fork_with_config(ForkConfig::delivering_to_idle_clones());
+    let mut paused = live.clone();
+
+    sender.unbounded_send(1).unwrap();
+    assert_eq!(live.next().await, Some(1));
+    paused.close();
+
+    for item in [2, 3] {
+        sender.unbounded_send(item).unwrap();
+        assert_eq!(live.next().await, Some(item));
+    }
+    assert_eq!(paused.next().await, None);
+
+    paused.resubscribe();
+    for item in [4, 5] {
+        sender.unbounded_send(item).unwrap();
+        assert_eq!(live.next().await, Some(item));
+    }
+    drop(sender);
+
+    assert_eq!(paused.collect::<Vec<_>>().await, vec![4, 5]);
+}
+
+#[tokio::test]
+async fn resubscribing_open_clone_skips_its_backlog() {
+    let (sender, receiver) = mpsc::unbounded::<usize>();
+    let mut live = receiver.fork_with_config(ForkConfig::delivering_to_idle_clones());
+    let mut behind = live.clone();
+
+    sender.unbounded_send(1).unwrap();
+    assert_eq!(live.next().await, Some(1));
+    assert_eq!(behind.n_queued_items(), 1);
+
+    behind.resubscribe();
+    assert_eq!(behind.n_queued_items(), 0);
+    assert_eq!(live.n_queued_items(), 0, "Skipped items are discarded");
+
+    sender.unbounded_send(2).unwrap();
+    assert_eq!(behind.next().await, Some(2));
+    assert_eq!(live.next().await, Some(2));
+}
+
+#[tokio::test]
+async fn resubscribing_after_end_yields_none() {
+    let mut first = stream::iter([1]).fork_with_config(ForkConfig::delivering_to_idle_clones());
+    let mut second = first.clone();
+    second.close();
+
+    assert_eq!(first.next().await, Some(1));
+    assert_eq!(first.next().await, None);
+
+    second.resubscribe();
+    assert_eq!(second.next().await, None);
+}
@@ -0,0 +1,27 @@
+use clone_stream::{ForkConfig, ForkStream};
+use futures::{StreamExt, stream};
+
+#[tokio::test]
+async fn lockstep_then_divergence() {
+    let mut first = stream::iter(0..10).fork_with_config(ForkConfig::delivering_to_idle_clones());
+    let mut second = first.clone();
+    assert!(first.same_position(&second));
+
+    for _ in 0..3 {
+        first.next().await;
+        second.next().await;
+    }
+    assert!(first.same_position(&second));
+    assert!(second.same_position(&first));
+
+    first.next().await;
+    assert!(!first.same_position(&second));
+}
+
+#[tokio::test]
+async fn clones_of_different_forks_differ() {
+    let first = stream::iter(0..3).fork_with_config(ForkConfig::delivering_to_idle_clones());
+    let other = stream::iter(0..3).fork_with_config(ForkConfig::delivering_to_idle_clones());
+
+    assert!(!first.same_position(&other));
+}
@@ -0,0 +1,53 @@
+use std::task::Poll;
+
+use clone_stream::{CloneStream, ForkConfig, ForkStream};
+use futures::stream;
+
+/// Polls four clones of a base stream that is only ready on every other poll,
+/// so the order of polling decides which clones receive each item.
+fn deliveries(scheduler_seed: Option<u64>) -> Vec<Vec<Option<usize>>> {
+    let mut polls = 0;
+    let mut produced = 0;
+    let base = stream::poll_fn(move |_| {
+        polls += 1;
+        if polls % 2 == 0 {
+            produced += 1;
+            Poll::Ready(Some(produced))
+        } else {
+            Poll::Pending
+        }
+    });
+    let first = base.fork_with_config(ForkConfig {
+        scheduler_seed,
+        ..ForkConfig::default()
+    });
+    let mut clones: Vec<_> = (0..3).map(|_| first.clone()).collect();
+    clones.push(first);
+
+    (0..20)
+        .map(|_| CloneStream::poll_all_ready(&mut clones))
+        .collect()
+}
+
+#[test]
+fn same_seed_reproduces_interleaving() {
+    let first_run = deliveries(Some(42));
+
+    assert_eq!(deliveries(Some(42)), first_run);
+    assert_ne!(
+        first_run,
+        deliveries(None),
+        "Seeded order differs from slice order"
+    );
+}
+
+#[test]
+fn same_seed_reproduces_delivery_counts() {
+    let counts = |runs: Vec<Vec<Option<usize>>>| -> Vec<usize> {
+        (0..4)
+            .map(|clone| runs.iter().filter(|round| round[clone].is_some()).count())
+            .collect()
+    };
+
+    assert_eq!(counts(deliveries(Some(7))), counts(deliveries(Some(7))));
+}
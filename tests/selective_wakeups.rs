@@ -0,0 +1,77 @@
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+    task::{Context, Poll, Wake, Waker},
+};
+
+use clone_stream::{ForkConfig, ForkStream};
+use futures::StreamExt;
+
+#[derive(Default)]
+struct MockWaker {
+    wakes: AtomicUsize,
+}
+
+impl MockWaker {
+    fn wakes(&self) -> usize {
+        self.wakes.load(Ordering::SeqCst)
+    }
+}
+
+impl Wake for MockWaker {
+    fn wake(self: Arc<Self>) {
+        self.wakes.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+/// Returns how often the lagging clone was woken.
+fn wakes_of_lagging_clone(config: ForkConfig) -> usize {
+    let (sender, receiver) = futures::channel::mpsc::unbounded::<usize>();
+    let mut fast = receiver.fork_with_config(config);
+    let mut lagging = fast.clone();
+    let fast_waker = Arc::new(MockWaker::default());
+    let lagging_waker = Arc::new(MockWaker::default());
+    let fast_cx_waker = Waker::from(fast_waker.clone());
+    let mut fast_cx = Context::from_waker(&fast_cx_waker);
+    let lagging_cx_waker = Waker::from(lagging_waker.clone());
+    let mut lagging_cx = Context::from_waker(&lagging_cx_waker);
+
+    assert_eq!(fast.poll_next_unpin(&mut fast_cx), Poll::Pending);
+    assert_eq!(lagging.poll_next_unpin(&mut lagging_cx), Poll::Pending);
+
+    sender.unbounded_send(1).unwrap();
+    assert_eq!(lagging_waker.wakes(), 1);
+    assert_eq!(fast.poll_next_unpin(&mut fast_cx), Poll::Ready(Some(1)));
+    assert_eq!(fast.poll_next_unpin(&mut fast_cx), Poll::Pending);
+
+    // The lagging clone was woken for item 1 but has not polled since.
+    sender.unbounded_send(2).unwrap();
+    assert_eq!(fast_waker.wakes(), 2);
+
+    assert_eq!(
+        lagging.poll_next_unpin(&mut lagging_cx),
+        Poll::Ready(Some(1))
+    );
+    assert_eq!(fast.poll_next_unpin(&mut fast_cx), Poll::Ready(Some(2)));
+    lagging_waker.wakes()
+}
+
+#[test]
+fn default_wakes_lagging_clone_again() {
+    assert_eq!(wakes_of_lagging_clone(ForkConfig::default()), 2);
+}
+
+#[test]
+fn selective_does_not_wake_lagging_clone_again() {
+    let config = ForkConfig {
+        selective_wakeups: true,
+        ..ForkConfig::default()
+    };
+    assert_eq!(
+        wakes_of_lagging_clone(config),
+        1,
+        "A clone that was already woken should not be woken again before it polls"
+    );
+}
@@ -0,0 +1,51 @@
+use clone_stream::ForkStream;
+use futures::{StreamExt, channel::mpsc, join, stream};
+
+#[derive(Clone, Debug, PartialEq)]
+struct Packet {
+    seq: u64,
+}
+
+#[tokio::test]
+async fn skipped_sequence_is_detected() {
+    let (sender, receiver) = mpsc::unbounded();
+    let mut first = receiver.fork_sequenced(|packet: &Packet| packet.seq);
+    let second = first.clone();
+    assert_eq!(first.last_sequence(), None);
+
+    let send = async move {
+        for seq in [1, 3] {
+            sender.unbounded_send(Packet { seq }).unwrap();
+        }
+    };
+    let (first_items, second_items, ()) = join!(
+        first.by_ref().collect::<Vec<_>>(),
+        second.collect::<Vec<_>>(),
+        send
+    );
+
+    assert_eq!(first_items, second_items);
+    assert_eq!(first.last_sequence(), Some(3));
+    assert_eq!(first.missing_sequences(), vec![2..3]);
+}
+
+#[tokio::test]
+async fn late_arrival_fills_gap() {
+    let mut stream = stream::iter([1, 5, 3]).fork_sequenced(|item| *item);
+
+    assert_eq!(stream.next().await, Some(1));
+    assert_eq!(stream.next().await, Some(5));
+    assert_eq!(stream.missing_sequences(), vec![2..5]);
+
+    assert_eq!(stream.next().await, Some(3));
+    assert_eq!(stream.missing_sequences(), vec![2..3, 4..5]);
+    assert_eq!(stream.last_sequence(), Some(5));
+}
+
+#[tokio::test]
+async fn unsequenced_fork_reports_nothing() {
+    let mut stream = stream::iter([1, 5]).fork();
+    while stream.next().await.is_some() {}
+    assert_eq!(stream.last_sequence(), None);
+    assert!(stream.missing_sequences().is_empty());
+}
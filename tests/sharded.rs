@@ -0,0 +1,43 @@
+use clone_stream::ForkStream;
+use futures::{StreamExt, future::join_all, stream};
+
+#[tokio::test]
+async fn even_and_odd_shards() {
+    let shards = stream::iter(0..10).fork_sharded(2, |x| *x);
+    assert_eq!(shards.len(), 2);
+
+    let collected = join_all(shards.into_iter().map(StreamExt::collect::<Vec<_>>)).await;
+
+    assert_eq!(collected[0], vec![0, 2, 4, 6, 8]);
+    assert_eq!(collected[1], vec![1, 3, 5, 7, 9]);
+}
+
+#[tokio::test]
+async fn shards_consumed_one_after_another() {
+    let mut shards = stream::iter(0..9).fork_sharded(3, |x| *x);
+    let third = shards.pop().unwrap();
+    let second = shards.pop().unwrap();
+    let first = shards.pop().unwrap();
+    assert_eq!(third.index(), 2);
+
+    assert_eq!(third.collect::<Vec<_>>().await, vec![2, 5, 8]);
+    assert_eq!(first.collect::<Vec<_>>().await, vec![0, 3, 6]);
+    assert_eq!(second.collect::<Vec<_>>().await, vec![1, 4, 7]);
+}
+
+#[test]
+fn zero_shards() {
+    assert!(stream::iter(0..3).fork_sharded(0, |x| *x).is_empty());
+}
+
+#[tokio::test]
+async fn slow_shard_holds_only_its_own_items() {
+    let mut shards = stream::iter(0..10).fork_sharded(2, |x| *x);
+    let odd = shards.pop().unwrap();
+    let mut even = shards.pop().unwrap();
+
+    assert_eq!(even.by_ref().collect::<Vec<_>>().await, vec![0, 2, 4, 6, 8]);
+    assert_eq!(even.n_queued_items(), 0);
+    assert_eq!(odd.n_queued_items(), 6, "Odd items and the end");
+    assert_eq!(odd.collect::<Vec<_>>().await, vec![1, 3, 5, 7, 9]);
+}
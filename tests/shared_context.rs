@@ -0,0 +1,32 @@
+use clone_stream::ForkStream;
+use futures::stream;
+
+#[test]
+fn clones_read_attached_name() {
+    let first = stream::iter(0..3)
+        .fork()
+        .with_shared_context(String::from("sensor"));
+    let second = first.clone();
+
+    assert_eq!(first.context::<String>().as_deref(), Some("sensor"));
+    assert_eq!(second.context::<String>().as_deref(), Some("sensor"));
+}
+
+#[test]
+fn context_is_shared_with_existing_clones() {
+    let first = stream::iter(0..3).fork();
+    let second = first.clone();
+    assert_eq!(second.context::<String>(), None);
+
+    let _first = first.with_shared_context(String::from("late"));
+
+    assert_eq!(second.context::<String>().as_deref(), Some("late"));
+}
+
+#[test]
+fn context_of_other_type_is_none() {
+    let stream = stream::iter(0..3).fork().with_shared_context(7_u32);
+
+    assert_eq!(stream.context::<String>(), None);
+    assert_eq!(stream.context::<u32>(), Some(7));
+}
@@ -0,0 +1,23 @@
+use clone_stream::ForkStream;
+use futures::{SinkExt, StreamExt, executor::block_on};
+
+#[test]
+fn subscribe_after_all_clones_dropped() {
+    let (mut sender, receiver) = futures::channel::mpsc::unbounded::<usize>();
+
+    let first = receiver.fork();
+    let second = first.clone();
+    let handle = first.handle();
+    drop((first, second));
+
+    block_on(async {
+        let mut subscriber = handle.subscribe();
+        assert_eq!(subscriber.active_clone_count(), 1, "Handle is not a clone");
+
+        sender.send(1).await.unwrap();
+        assert_eq!(subscriber.next().await, Some(1));
+
+        drop(sender);
+        assert_eq!(subscriber.next().await, None);
+    });
+}
@@ -0,0 +1,68 @@
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+    task::{Context, Poll, Wake, Waker},
+};
+
+use clone_stream::{ForkConfig, ForkStream};
+use futures::StreamExt;
+
+#[derive(Default)]
+struct MockWaker {
+    wakes: AtomicUsize,
+}
+
+impl Wake for MockWaker {
+    fn wake(self: Arc<Self>) {
+        self.wakes.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+/// Polls `clone_count` clones from the same task and returns how often that
+/// task is woken by a single new item.
+fn wakes_of_shared_task(config: ForkConfig, clone_count: usize) -> usize {
+    let (sender, receiver) = futures::channel::mpsc::unbounded::<usize>();
+    let first = receiver.fork_with_config(config);
+    let mut clones: Vec<_> = (1..clone_count).map(|_| first.clone()).collect();
+    clones.push(first);
+    let task = Arc::new(MockWaker::default());
+    let waker = Waker::from(task.clone());
+    let mut cx = Context::from_waker(&waker);
+
+    for clone in &mut clones {
+        assert_eq!(clone.poll_next_unpin(&mut cx), Poll::Pending);
+    }
+
+    sender.unbounded_send(1).unwrap();
+    let wake_count = task.wakes.load(Ordering::SeqCst);
+
+    for clone in &mut clones {
+        assert_eq!(clone.poll_next_unpin(&mut cx), Poll::Ready(Some(1)));
+    }
+    wake_count
+}
+
+fn selective_wakeups() -> ForkConfig {
+    ForkConfig {
+        selective_wakeups: true,
+        ..ForkConfig::default()
+    }
+}
+
+#[test]
+fn shared_task_woken_once() {
+    assert_eq!(wakes_of_shared_task(ForkConfig::default(), 2), 1);
+}
+
+#[test]
+fn shared_task_woken_once_with_selective_wakeups() {
+    assert_eq!(wakes_of_shared_task(selective_wakeups(), 2), 1);
+}
+
+#[test]
+fn task_polling_three_clones_woken_once() {
+    assert_eq!(wakes_of_shared_task(ForkConfig::default(), 3), 1);
+    assert_eq!(wakes_of_shared_task(selective_wakeups(), 3), 1);
+}
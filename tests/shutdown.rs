@@ -0,0 +1,57 @@
+use std::sync::{
+    Arc,
+    atomic::{AtomicBool, Ordering},
+};
+
+use clone_stream::{ForkConfig, ForkStream};
+use futures::{StreamExt, channel::mpsc, join};
+
+#[tokio::test]
+async fn shutdown_ends_all_clones() {
+    let (sender, receiver) = mpsc::unbounded::<usize>();
+    let mut first = receiver.fork_with_config(ForkConfig::delivering_to_idle_clones());
+    let mut second = first.clone();
+    let mut third = first.clone();
+
+    sender.unbounded_send(1).unwrap();
+    assert_eq!(first.next().await, Some(1));
+    assert_eq!(second.next().await, Some(1));
+
+    let waiting = tokio::spawn(async move { join!(first.next(), second.next()) });
+    tokio::task::yield_now().await;
+    assert!(!waiting.is_finished());
+
+    third.shutdown();
+    assert_eq!(waiting.await.unwrap(), (None, None));
+    assert!(third.is_shut_down());
+    assert_eq!(third.next().await, None, "Queued item discarded");
+
+    let late = third.clone();
+    assert_eq!(late.collect::<Vec<_>>().await, Vec::<usize>::new());
+    assert!(sender.unbounded_send(2).is_err(), "Receiver dropped");
+}
+
+struct DropFlag(Arc<AtomicBool>);
+
+impl Drop for DropFlag {
+    fn drop(&mut self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+}
+
+#[tokio::test]
+async fn shutdown_drops_base_stream() {
+    let dropped = Arc::new(AtomicBool::new(false));
+    let flag = DropFlag(dropped.clone());
+    let clone = futures::stream::repeat(0)
+        .map(move |item| {
+            let _ = &flag;
+            item
+        })
+        .fork_with_config(ForkConfig::delivering_to_idle_clones());
+
+    clone.shutdown();
+
+    assert!(dropped.load(Ordering::SeqCst));
+    assert!(clone.into_inner().is_err());
+}
@@ -0,0 +1,52 @@
+use std::sync::Mutex;
+
+use clone_stream::{ForkConfig, ForkStream};
+use futures::{StreamExt, stream};
+use log::{Level, Log, Metadata, Record};
+
+/// Logger that keeps every warning.
+struct CapturedWarnings(Mutex<Vec<String>>);
+
+impl Log for CapturedWarnings {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= Level::Warn
+    }
+
+    fn log(&self, record: &Record) {
+        if self.enabled(record.metadata()) {
+            self.0.lock().unwrap().push(record.args().to_string());
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+static WARNINGS: CapturedWarnings = CapturedWarnings(Mutex::new(Vec::new()));
+
+#[tokio::test]
+async fn warning_names_slow_clone() {
+    log::set_logger(&WARNINGS).unwrap();
+    log::set_max_level(log::LevelFilter::Warn);
+
+    let mut fast = stream::iter(0..10).fork_with_config(ForkConfig {
+        slow_clone_warn_threshold: Some(3),
+        ..ForkConfig::delivering_to_idle_clones()
+    });
+    let slow = fast.clone();
+
+    for _ in 0..3 {
+        fast.next().await;
+    }
+    assert!(WARNINGS.0.lock().unwrap().is_empty());
+
+    fast.next().await;
+    fast.next().await;
+    let warnings = WARNINGS.0.lock().unwrap().clone();
+    assert_eq!(
+        warnings,
+        vec![format!(
+            "Clone {} lags 4 items behind, over the threshold of 3.",
+            slow.id
+        )]
+    );
+}
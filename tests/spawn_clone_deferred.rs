@@ -0,0 +1,87 @@
+use core::{
+    pin::{Pin, pin},
+    task::{Context, Poll},
+};
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use clone_stream::{CloneStream, ForkConfig, ForkStream};
+use futures::{FutureExt, Stream, StreamExt, executor::block_on, stream};
+use tokio::time::timeout;
+
+#[tokio::test]
+async fn clone_from_for_each_callback() {
+    let clone = stream::iter(0..3).fork_with_config(ForkConfig::delivering_to_idle_clones());
+    let source = clone.clone();
+    let spawned = Mutex::new(Vec::new());
+
+    timeout(
+        Duration::from_secs(1),
+        clone.for_each(|item| {
+            let source = &source;
+            let spawned = &spawned;
+            async move {
+                let new_clone = source.spawn_clone_deferred().await;
+                spawned.lock().unwrap().push((item, new_clone));
+            }
+        }),
+    )
+    .await
+    .expect("Cloning from the callback deadlocked");
+
+    let spawned = spawned.into_inner().unwrap();
+    assert_eq!(
+        spawned.iter().map(|(item, _)| *item).collect::<Vec<_>>(),
+        vec![0, 1, 2]
+    );
+    for (_, new_clone) in spawned {
+        assert_eq!(
+            new_clone.collect::<Vec<_>>().await,
+            vec![0, 1, 2],
+            "Items stay queued for `source`, which has not received them"
+        );
+    }
+}
+
+type SharedClone = Arc<Mutex<Option<CloneStream<SpawningStream>>>>;
+
+/// A base stream that tries to clone its own fork before yielding.
+struct SpawningStream {
+    own_clone: SharedClone,
+    remaining: usize,
+}
+
+impl Stream for SpawningStream {
+    type Item = usize;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<usize>> {
+        if let Some(own_clone) = self.own_clone.lock().unwrap().as_ref() {
+            let mut spawned = pin!(own_clone.spawn_clone_deferred());
+            assert!(spawned.poll_unpin(cx).is_pending(), "The fork is locked");
+        }
+        if self.remaining == 0 {
+            return Poll::Ready(None);
+        }
+        self.remaining -= 1;
+        Poll::Ready(Some(self.remaining))
+    }
+}
+
+#[test]
+fn clone_from_base_stream_is_deferred() {
+    let own_clone = SharedClone::default();
+    let stream = SpawningStream {
+        own_clone: own_clone.clone(),
+        remaining: 2,
+    }
+    .fork_with_config(ForkConfig::delivering_to_idle_clones());
+    *own_clone.lock().unwrap() = Some(stream.clone());
+
+    assert_eq!(block_on(stream.collect::<Vec<_>>()), vec![1, 0]);
+
+    let source = own_clone.lock().unwrap().take().unwrap();
+    let spawned = block_on(source.spawn_clone_deferred());
+    assert!(spawned.same_position(&source));
+}
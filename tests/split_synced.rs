@@ -0,0 +1,59 @@
+use std::sync::{
+    Arc,
+    atomic::{AtomicUsize, Ordering},
+};
+
+use clone_stream::{ForkConfig, ForkStream};
+use futures::{StreamExt, future::join_all, stream};
+
+#[tokio::test]
+async fn clones_rendezvous_before_continuing() {
+    let source = stream::iter(0..6).fork_with_config(ForkConfig::delivering_to_idle_clones());
+    let (clones, barrier) = source.split_synced(3);
+    drop(source);
+    let arrived = Arc::new(AtomicUsize::new(0));
+
+    let consumers = clones.into_iter().map(|mut clone| {
+        let barrier = barrier.clone();
+        let arrived = arrived.clone();
+        async move {
+            let mut before = Vec::new();
+            for _ in 0..3 {
+                before.push(clone.next().await.unwrap());
+            }
+            arrived.fetch_add(1, Ordering::SeqCst);
+            barrier.wait().await;
+            let seen_at_barrier = arrived.load(Ordering::SeqCst);
+            let after: Vec<_> = clone.collect().await;
+            (before, seen_at_barrier, after)
+        }
+    });
+
+    for (before, seen_at_barrier, after) in join_all(consumers).await {
+        assert_eq!(before, vec![0, 1, 2]);
+        assert_eq!(seen_at_barrier, 3, "Every clone reached the barrier first");
+        assert_eq!(after, vec![3, 4, 5]);
+    }
+}
+
+#[tokio::test]
+async fn barrier_can_be_reused() {
+    let source = stream::iter(0..2).fork_with_config(ForkConfig::delivering_to_idle_clones());
+    let (clones, barrier) = source.split_synced(2);
+
+    let rounds = clones.into_iter().map(|mut clone| {
+        let barrier = barrier.clone();
+        async move {
+            let mut items = Vec::new();
+            while let Some(item) = clone.next().await {
+                items.push(item);
+                barrier.wait().await;
+            }
+            items
+        }
+    });
+
+    for items in join_all(rounds).await {
+        assert_eq!(items, vec![0, 1]);
+    }
+}
@@ -0,0 +1,68 @@
+use std::task::Poll;
+
+use clone_stream::{ForkConfig, ForkStream};
+use futures::channel::mpsc;
+mod util;
+
+use util::StepScheduler;
+
+#[test]
+fn both_pending_send_two_receive_two_twice() {
+    let (sender, receiver) = mpsc::unbounded::<usize>();
+    let adam = receiver.fork_with_config(ForkConfig::delivering_to_idle_clones());
+    let bob = adam.clone();
+    let mut scheduler = StepScheduler::new();
+    scheduler.add("adam", adam);
+    scheduler.add("bob", bob);
+
+    for round in 0..2 {
+        assert_eq!(
+            scheduler.run(&["adam", "bob"]),
+            [Poll::Pending, Poll::Pending],
+            "Nothing sent yet in round {round}"
+        );
+        sender.unbounded_send(2 * round).unwrap();
+        sender.unbounded_send(2 * round + 1).unwrap();
+        assert_eq!(
+            scheduler.run(&["adam", "adam", "bob", "bob"]),
+            [
+                Poll::Ready(Some(2 * round)),
+                Poll::Ready(Some(2 * round + 1)),
+                Poll::Ready(Some(2 * round)),
+                Poll::Ready(Some(2 * round + 1)),
+            ],
+            "Both clones receive both items of round {round}"
+        );
+    }
+
+    assert_eq!(scheduler.received("adam"), vec![0, 1, 2, 3]);
+    assert_eq!(scheduler.received("bob"), vec![0, 1, 2, 3]);
+    assert_eq!(scheduler.log().len(), 12);
+}
+
+#[test]
+fn interleaved_polls_receive_in_order() {
+    let (sender, receiver) = mpsc::unbounded::<usize>();
+    let adam = receiver.fork_with_config(ForkConfig::delivering_to_idle_clones());
+    let bob = adam.clone();
+    let mut scheduler = StepScheduler::new();
+    scheduler.add("adam", adam);
+    scheduler.add("bob", bob);
+
+    sender.unbounded_send(0).unwrap();
+    sender.unbounded_send(1).unwrap();
+    drop(sender);
+
+    assert_eq!(
+        scheduler.run(&["adam", "bob", "bob", "adam", "bob", "adam"]),
+        [
+            Poll::Ready(Some(0)),
+            Poll::Ready(Some(0)),
+            Poll::Ready(Some(1)),
+            Poll::Ready(Some(1)),
+            Poll::Ready(None),
+            Poll::Ready(None),
+        ]
+    );
+    assert_eq!(scheduler.log()[2], ("bob", Poll::Ready(Some(1))));
+}
@@ -0,0 +1,26 @@
+use clone_stream::CloneStream;
+use futures::{StreamExt, join, stream};
+
+#[tokio::test]
+async fn clones_receive_concatenated_inner_items() {
+    let streams = stream::iter([stream::iter(vec![1, 2]), stream::iter(vec![3, 4, 5])]);
+    let first = CloneStream::fork_from_stream_of_streams(streams);
+    let second = first.clone();
+
+    let (first, second) = join!(first.collect::<Vec<_>>(), second.collect::<Vec<_>>());
+
+    assert_eq!(first, vec![1, 2, 3, 4, 5]);
+    assert_eq!(second, vec![1, 2, 3, 4, 5]);
+}
+
+#[tokio::test]
+async fn inner_streams_need_not_be_clone() {
+    let streams = stream::iter(0..2).map(|outer| stream::once(async move { outer * 10 }));
+    let first = CloneStream::fork_from_stream_of_streams(streams);
+    let second = first.clone();
+
+    let (first, second) = join!(first.collect::<Vec<_>>(), second.collect::<Vec<_>>());
+
+    assert_eq!(first, vec![0, 10]);
+    assert_eq!(second, vec![0, 10]);
+}
@@ -0,0 +1,42 @@
+use clone_stream::{ForkConfig, ForkStream};
+use futures::{StreamExt, channel::mpsc, join};
+
+fn config() -> ForkConfig {
+    ForkConfig {
+        subscribe_on_first_poll: true,
+        ..ForkConfig::delivering_to_idle_clones()
+    }
+}
+
+#[tokio::test]
+async fn never_polled_clone_holds_no_items() {
+    let (sender, receiver) = mpsc::unbounded::<usize>();
+    let mut polled = receiver.fork_with_config(config());
+    let idle = polled.clone();
+
+    for item in 0..100 {
+        sender.unbounded_send(item).unwrap();
+        assert_eq!(polled.next().await, Some(item));
+    }
+    assert_eq!(idle.n_queued_items(), 0);
+    assert_eq!(polled.oldest_needed_index(), None);
+}
+
+#[tokio::test]
+async fn clone_receives_items_from_its_first_poll_on() {
+    let (sender, receiver) = mpsc::unbounded::<usize>();
+    let mut first = receiver.fork_with_config(config());
+    let mut second = first.clone();
+
+    sender.unbounded_send(0).unwrap();
+    assert_eq!(first.next().await, Some(0));
+
+    let waiting = second.next();
+    sender.unbounded_send(1).unwrap();
+    assert_eq!(waiting.await, Some(1));
+    drop(sender);
+
+    let (first, second) = join!(first.collect::<Vec<_>>(), second.collect::<Vec<_>>());
+    assert_eq!(first, vec![1]);
+    assert_eq!(second, Vec::<usize>::new());
+}
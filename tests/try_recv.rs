@@ -0,0 +1,28 @@
+use clone_stream::{ForkConfig, ForkStream, TryRecvError};
+use futures::channel::mpsc;
+
+#[test]
+fn try_recv_item_then_empty_then_disconnected() {
+    let (sender, receiver) = mpsc::unbounded::<usize>();
+    let mut clone = receiver.fork_with_config(ForkConfig::delivering_to_idle_clones());
+
+    sender.unbounded_send(1).unwrap();
+    assert_eq!(clone.try_recv(), Ok(1));
+    assert_eq!(clone.try_recv(), Err(TryRecvError::Empty));
+
+    drop(sender);
+    assert_eq!(clone.try_recv(), Err(TryRecvError::Disconnected));
+}
+
+#[test]
+fn try_recv_from_queue_of_other_clone() {
+    let (sender, receiver) = mpsc::unbounded::<usize>();
+    let mut first = receiver.fork_with_config(ForkConfig::delivering_to_idle_clones());
+    let mut second = first.clone();
+
+    sender.unbounded_send(1).unwrap();
+    assert_eq!(first.try_recv(), Ok(1));
+    assert_eq!(first.try_recv(), Err(TryRecvError::Empty));
+    assert_eq!(second.try_recv(), Ok(1));
+    assert_eq!(second.try_recv(), Err(TryRecvError::Empty));
+}
@@ -1,8 +1,81 @@
 #![allow(dead_code)]
-use std::{future::Future, time::Duration};
+use std::{
+    fmt::Debug,
+    future::Future,
+    task::{Context, Poll},
+    time::Duration,
+};
 
+use futures::{Stream, StreamExt, task::noop_waker_ref};
 use tokio::time::{Instant, sleep_until};
 
 pub fn until(start: Instant, n: usize) -> impl Future<Output = ()> {
     sleep_until(start + Duration::from_millis(10) * n as u32)
 }
+
+/// Polls named clones one poll at a time with a no-op waker, recording the
+/// outcome of every poll, to write poll-ordering tests declaratively.
+pub(crate) struct StepScheduler<Clone>
+where
+    Clone: Stream,
+{
+    clones: Vec<(&'static str, Clone)>,
+    log: Vec<(&'static str, Poll<Option<Clone::Item>>)>,
+}
+
+impl<Clone> StepScheduler<Clone>
+where
+    Clone: Stream + Unpin,
+    Clone::Item: std::clone::Clone + Debug,
+{
+    pub(crate) fn new() -> Self {
+        Self {
+            clones: Vec::new(),
+            log: Vec::new(),
+        }
+    }
+
+    pub(crate) fn add(&mut self, name: &'static str, clone: Clone) {
+        assert!(
+            self.clones.iter().all(|(added, _)| *added != name),
+            "Clone {name} was added twice"
+        );
+        self.clones.push((name, clone));
+    }
+
+    /// Polls the clone called `name` once.
+    pub(crate) fn step(&mut self, name: &'static str) -> Poll<Option<Clone::Item>> {
+        let (_, clone) = self
+            .clones
+            .iter_mut()
+            .find(|(added, _)| *added == name)
+            .unwrap_or_else(|| panic!("No clone called {name}"));
+        let outcome = clone.poll_next_unpin(&mut Context::from_waker(noop_waker_ref()));
+        self.log.push((name, outcome.clone()));
+        outcome
+    }
+
+    /// Polls the clones in the order of `names`, once per occurrence, and
+    /// returns the outcome of each poll.
+    pub(crate) fn run(&mut self, names: &[&'static str]) -> Vec<Poll<Option<Clone::Item>>> {
+        names.iter().map(|name| self.step(name)).collect()
+    }
+
+    /// Returns the items the clone called `name` has received so far.
+    pub(crate) fn received(&self, name: &'static str) -> Vec<Clone::Item> {
+        self.log
+            .iter()
+            .filter(|(polled, _)| *polled == name)
+            .filter_map(|(_, outcome)| match outcome {
+                Poll::Ready(Some(item)) => Some(item.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Returns every poll so far, oldest first, with the name of the polled
+    /// clone.
+    pub(crate) fn log(&self) -> &[(&'static str, Poll<Option<Clone::Item>>)] {
+        &self.log
+    }
+}
@@ -0,0 +1,68 @@
+use clone_stream::{ConfigError, ForkConfig, ForkStream};
+use futures::{StreamExt, stream};
+
+#[test]
+fn default_config_is_valid() {
+    assert_eq!(ForkConfig::default().validate(), Ok(()));
+}
+
+#[test]
+fn every_problem_is_reported() {
+    let config = ForkConfig {
+        max_clone_count: 0,
+        max_queue_size: 0,
+        initial_queue_capacity: 8,
+        high_water: Some(2),
+        low_water: 2,
+        ..ForkConfig::default()
+    };
+
+    assert_eq!(
+        config.validate(),
+        Err(vec![
+            ConfigError::ZeroCloneCount,
+            ConfigError::ZeroQueueSize,
+            ConfigError::InitialCapacityExceedsMax {
+                initial_capacity: 8,
+                max_queue_size: 0,
+            },
+            ConfigError::LowWaterNotBelowHighWater {
+                low_water: 2,
+                high_water: 2,
+            },
+            ConfigError::HighWaterExceedsMaxQueueSize {
+                high_water: 2,
+                max_queue_size: 0,
+            },
+        ])
+    );
+}
+
+#[test]
+fn high_water_above_queue_size_is_reported() {
+    let config = ForkConfig {
+        max_queue_size: 4,
+        initial_queue_capacity: 4,
+        high_water: Some(8),
+        ..ForkConfig::default()
+    };
+
+    assert_eq!(
+        config.validate(),
+        Err(vec![ConfigError::HighWaterExceedsMaxQueueSize {
+            high_water: 8,
+            max_queue_size: 4,
+        }])
+    );
+}
+
+#[tokio::test]
+async fn try_fork_with_valid_config() {
+    let config = ForkConfig {
+        max_queue_size: 4,
+        initial_queue_capacity: 4,
+        ..ForkConfig::default()
+    };
+    let stream = stream::iter(0..3).try_fork_with_config(config).unwrap();
+    assert_eq!(stream.collect::<Vec<_>>().await, vec![0, 1, 2]);
+}
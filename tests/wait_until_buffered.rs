@@ -0,0 +1,40 @@
+use core::time::Duration;
+
+use clone_stream::ForkStream;
+use futures::{StreamExt, channel::mpsc, join, stream};
+use tokio::time::timeout;
+
+#[tokio::test]
+async fn wait_for_three_buffered_items() {
+    let (sender, receiver) = mpsc::unbounded::<usize>();
+    let mut producer = receiver.fork();
+    let mut waiting = producer.clone();
+
+    let producing = tokio::spawn(async move {
+        for item in 0..3 {
+            tokio::task::yield_now().await;
+            sender.unbounded_send(item).unwrap();
+            assert_eq!(producer.next().await, Some(item));
+        }
+        (sender, producer)
+    });
+
+    timeout(Duration::from_secs(1), waiting.wait_until_buffered(3))
+        .await
+        .expect("Three items should have been buffered");
+    assert_eq!(waiting.n_queued_items(), 3);
+    assert_eq!(waiting.drain_ready(), vec![0, 1, 2]);
+
+    let _keep_alive = producing.await.unwrap();
+}
+
+#[tokio::test]
+async fn already_buffered_completes_immediately() {
+    let mut ahead = stream::iter(0..2).fork();
+    let behind = ahead.clone();
+    let ((), first) = join!(behind.wait_until_buffered(1), ahead.next());
+    assert_eq!(first, Some(0));
+
+    behind.wait_until_buffered(1).await;
+    behind.wait_until_buffered(0).await;
+}
@@ -0,0 +1,29 @@
+#![cfg(feature = "watch")]
+
+use clone_stream::ForkWatch;
+use futures::StreamExt;
+use tokio::sync::watch;
+
+#[tokio::test]
+async fn clones_observe_latest_watch_value() {
+    let (sender, receiver) = watch::channel("initial");
+    let mut first = receiver.fork();
+    let mut second = first.clone();
+
+    sender.send("replaced").unwrap();
+    sender.send("latest").unwrap();
+    assert_eq!(
+        first.next().await,
+        Some("latest"),
+        "Replaced values skipped"
+    );
+    assert_eq!(second.next().await, Some("latest"));
+
+    sender.send("update").unwrap();
+    assert_eq!(second.next().await, Some("update"));
+    assert_eq!(first.next().await, Some("update"));
+
+    drop(sender);
+    assert_eq!(first.next().await, None);
+    assert_eq!(second.next().await, None);
+}
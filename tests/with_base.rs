@@ -0,0 +1,41 @@
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use clone_stream::ForkStream;
+use futures::{Stream, StreamExt};
+
+/// Stream of a connection that exposes its identifier.
+struct Connection {
+    id: u32,
+    messages: Vec<&'static str>,
+}
+
+impl Connection {
+    fn connection_id(&self) -> u32 {
+        self.id
+    }
+}
+
+impl Stream for Connection {
+    type Item = &'static str;
+
+    fn poll_next(mut self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Poll::Ready(self.messages.pop())
+    }
+}
+
+#[tokio::test]
+async fn base_stream_methods_reachable_from_clones() {
+    let mut first = Connection {
+        id: 7,
+        messages: vec!["b", "a"],
+    }
+    .fork();
+    let second = first.clone();
+
+    assert_eq!(first.next().await, Some("a"));
+    assert_eq!(first.with_base(Connection::connection_id), 7);
+    assert_eq!(second.with_base(|base| base.messages.len()), 1);
+}
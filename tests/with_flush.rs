@@ -0,0 +1,65 @@
+use std::{
+    pin::Pin,
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+    task::{Context, Poll},
+};
+
+use clone_stream::{ForkConfig, ForkStream};
+use futures::{Stream, StreamExt};
+
+/// Source that only releases an item once it was flushed after the
+/// previous poll.
+#[derive(Default)]
+struct BufferedSource {
+    produced: usize,
+    flushed: bool,
+    polls: usize,
+}
+
+impl BufferedSource {
+    fn flush(&mut self) {
+        self.flushed = true;
+    }
+}
+
+impl Stream for BufferedSource {
+    type Item = usize;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<usize>> {
+        self.polls += 1;
+        if self.produced == 3 {
+            return Poll::Ready(None);
+        }
+        if self.polls > 1 && !self.flushed {
+            cx.waker().wake_by_ref();
+            return Poll::Pending;
+        }
+        self.flushed = false;
+        self.produced += 1;
+        Poll::Ready(Some(self.produced))
+    }
+}
+
+#[tokio::test]
+async fn flush_runs_after_every_base_poll() {
+    let flushes = Arc::new(AtomicUsize::new(0));
+    let flush_counter = flushes.clone();
+    let first = BufferedSource::default()
+        .fork_with_config(ForkConfig::delivering_to_idle_clones())
+        .with_flush(move |source| {
+            flush_counter.fetch_add(1, Ordering::SeqCst);
+            source.flush();
+        });
+    let second = first.clone();
+
+    assert_eq!(first.collect::<Vec<_>>().await, vec![1, 2, 3]);
+    assert_eq!(second.collect::<Vec<_>>().await, vec![1, 2, 3]);
+    assert_eq!(
+        flushes.load(Ordering::SeqCst),
+        4,
+        "Flushed after three items and the end"
+    );
+}
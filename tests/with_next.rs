@@ -0,0 +1,83 @@
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+    task::Poll,
+};
+
+use clone_stream::{ForkConfig, ForkStream};
+use futures::{StreamExt, channel::mpsc, future::poll_fn};
+
+/// Large item that counts how often it is cloned.
+struct Frame {
+    id: usize,
+    pixels: Vec<u8>,
+    clones: Arc<AtomicUsize>,
+}
+
+impl Clone for Frame {
+    fn clone(&self) -> Self {
+        self.clones.fetch_add(1, Ordering::SeqCst);
+        Self {
+            id: self.id,
+            pixels: self.pixels.clone(),
+            clones: self.clones.clone(),
+        }
+    }
+}
+
+#[tokio::test]
+async fn projection_from_queue_does_not_clone() {
+    let clones = Arc::new(AtomicUsize::new(0));
+    let (sender, receiver) = mpsc::unbounded();
+    let mut fast = receiver.fork_with_config(ForkConfig::delivering_to_idle_clones());
+    let mut reader = fast.clone();
+    let mut slow = fast.clone();
+
+    sender
+        .unbounded_send(Frame {
+            id: 7,
+            pixels: vec![0; 1 << 16],
+            clones: clones.clone(),
+        })
+        .unwrap();
+    assert_eq!(fast.next().await.map(|frame| frame.id), Some(7));
+    assert_eq!(clones.load(Ordering::SeqCst), 1, "Queued for the others");
+
+    let id = poll_fn(|cx| reader.with_next(cx, |frame| frame.id)).await;
+    assert_eq!(id, Some(7));
+    assert_eq!(
+        clones.load(Ordering::SeqCst),
+        1,
+        "Reader borrowed the queued frame"
+    );
+
+    let frame = slow.next().await.unwrap();
+    assert_eq!(frame.pixels.len(), 1 << 16);
+    assert_eq!(
+        clones.load(Ordering::SeqCst),
+        1,
+        "Last clone takes the frame"
+    );
+}
+
+#[tokio::test]
+async fn with_next_reports_pending_and_end() {
+    let (sender, receiver) = mpsc::unbounded::<usize>();
+    let mut clone = receiver.fork_with_config(ForkConfig::delivering_to_idle_clones());
+
+    let pending = poll_fn(|cx| Poll::Ready(clone.with_next(cx, |item| *item))).await;
+    assert_eq!(pending, Poll::Pending);
+
+    sender.unbounded_send(3).unwrap();
+    drop(sender);
+    assert_eq!(
+        poll_fn(|cx| clone.with_next(cx, |item| item * 2)).await,
+        Some(6)
+    );
+    assert_eq!(
+        poll_fn(|cx| clone.with_next(cx, |item| item * 2)).await,
+        None
+    );
+}